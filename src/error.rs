@@ -0,0 +1,136 @@
+//! The error type shared by the non-panicking `try_` counterparts of panicking operations.
+//!
+//! Some panicking operations deliberately have no `try_` counterpart:
+//!
+//! - Indexing, and accessors that panic only when given something out of bounds, such as
+//!   [`row`](crate::prelude::Grid::row) or [`col`](crate::prelude::Grid::col),
+//!   where a `get_` accessor or [`in_bounds`](crate::prelude::Grid::in_bounds) check already covers the failure.
+//! - Builder methods that panic only when called in the wrong order, such as `with_rules` on a search that has already started,
+//!   since the misuse is in the caller's code rather than its input.
+//! - [`Grid::cast`](crate::prelude::Grid::cast), which is `unsafe` and panics only if the types differ in size or alignment, a mistake in the caller's code.
+
+use crate::{rect::Rect, vector::Vector};
+
+use std::{error::Error, fmt};
+
+/// An error returned by the `try_` counterparts of operations that would otherwise panic.
+///
+/// Every `try_` counterpart returns this error, even when its only way to fail is being given something out of bounds,
+/// so callers can always tell why it failed. Accessors named `get`, such as [`Grid::get`](crate::prelude::Grid::get),
+/// return an `Option` instead.
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// assert_eq!(
+///     Grid::try_new(0, 3, 'x'),
+///     Err(GridError::InvalidDimensions { width: 0, height: 3 }),
+/// );
+///
+/// let mut grid = Grid::try_new(4, 3, '.').unwrap();
+///
+/// assert_eq!(
+///     grid.try_swap(v(0, 0), v(4, 0)),
+///     Err(GridError::OutOfBounds { dim: v(4, 3), region: Rect::new(v(4, 0), v(1, 1)) }),
+/// );
+/// assert_eq!(
+///     grid.try_swap(v(0, 0), v(4, 0)).unwrap_err().to_string(),
+///     "out of bounds: the dimensions are (4, 3) but the position is (4, 0)",
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum GridError {
+    /// The dimensions of a grid would not be positive.
+    InvalidDimensions { width: i64, height: i64 },
+    /// The dimensions of a grid would be too large for its values to be stored.
+    TooLarge { width: i64, height: i64 },
+    /// Something that must have particular dimensions, such as a second grid, has different dimensions.
+    DimensionMismatch { expected: Vector, found: Vector },
+    /// A position, row, column or region is not entirely in bounds of a grid with the given dimensions.
+    ///
+    /// A single position is reported as a region with dimensions `(1, 1)`.
+    OutOfBounds { dim: Vector, region: Rect },
+    /// Rows given as input do not all have the same length. Rows are numbered from 0.
+    RaggedInput {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// An iterator ran out before the number of values needed were taken from it.
+    TooFewValues { expected: usize, found: usize },
+    /// An argument was invalid for a reason other than the above.
+    InvalidArgument(&'static str),
+}
+
+impl GridError {
+    /// Returns an `OutOfBounds` error for a single position.
+    pub(crate) fn out_of_bounds(dim: Vector, pos: Vector) -> Self {
+        Self::OutOfBounds {
+            dim,
+            region: Rect::new(pos, Vector::new(1, 1)),
+        }
+    }
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDimensions { width, height } => {
+                write!(f, "dimensions must be positive: ({width}, {height})")
+            }
+            Self::TooLarge { width, height } => {
+                write!(f, "dimensions are too large: ({width}, {height})")
+            }
+            Self::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "dimension mismatch: expected {expected} but found {found}"
+                )
+            }
+            Self::OutOfBounds { dim, region } if region.dim == Vector::new(1, 1) => write!(
+                f,
+                "out of bounds: the dimensions are {dim} but the position is {}",
+                region.pos
+            ),
+            Self::OutOfBounds { dim, region } => write!(
+                f,
+                "out of bounds: the dimensions are {dim} but the region is {region}"
+            ),
+            Self::RaggedInput {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "ragged input: row {row} has {found} values but {expected} were expected"
+            ),
+            Self::TooFewValues { expected, found } => write!(
+                f,
+                "too few values: {expected} were needed but only {found} were given"
+            ),
+            Self::InvalidArgument(reason) => write!(f, "invalid argument: {reason}"),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/// Unwraps the result of a `try_` counterpart, panicking with the error's message from the caller's location.
+pub(crate) trait OrPanic<T> {
+    #[track_caller]
+    fn or_panic(self) -> T;
+}
+
+impl<T> OrPanic<T> for Result<T, GridError> {
+    #[track_caller]
+    #[inline]
+    fn or_panic(self) -> T {
+        match self {
+            Ok(value) => value,
+            Err(error) => panic!("{error}"),
+        }
+    }
+}
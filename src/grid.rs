@@ -1,8 +1,65 @@
 //! A simple generic heap-allocated 2D grid struct.
 
+pub mod aoe;
+mod blit;
+pub mod blocks;
+pub mod bsp;
+pub mod cave;
+pub mod column;
+pub mod cover;
+pub mod crossword;
+pub mod diagonal;
+pub mod diffusion;
+pub mod dual;
+pub mod dungeon;
+pub mod encoded;
+pub mod entry;
+pub mod erosion;
+pub mod flow;
+pub mod fog;
+pub mod formats;
+pub mod frontier;
+pub mod groups;
+pub mod heap;
+pub mod influence;
 pub mod iterators;
+pub mod layout;
+pub mod light;
+pub mod match3;
+pub mod meta;
+pub mod nd;
+mod neighbors;
+pub mod nonogram;
+pub mod occupancy;
+pub mod packed;
+pub mod packing;
+pub mod pathfinding;
+pub mod regions;
+mod reshape;
+pub mod row;
+pub mod sand;
+pub mod set;
+pub mod shared;
+pub mod spanning;
+pub mod spiral;
+pub mod symmetry;
+pub mod system;
+pub mod terrain;
+pub mod tile;
+pub mod transaction;
+mod transform;
+pub mod turtle;
+pub mod view;
+pub mod visibility;
+pub mod voronoi;
+pub mod walker;
+pub mod zip;
 
-use crate::vector::Vector;
+use crate::{
+    error::{GridError, OrPanic},
+    rect::Rect,
+    vector::Vector,
+};
 
 use std::{
     fmt,
@@ -60,13 +117,29 @@ impl<T: Clone> Grid<T> {
     /// ```
     #[track_caller]
     pub fn new(width: i64, height: i64, value: T) -> Self {
-        let size = size(width, height);
-        let mut raw = Vec::with_capacity(size);
+        Self::try_new(width, height, value).or_panic()
+    }
+
+    /// Constructs a new `Grid<T>` with the given dimensions, initialising all values to `value`,
+    /// or returns an error if the dimensions are not positive or too large, including if the values cannot be allocated.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// assert_eq!(Grid::try_new(2, 1, 'x'), Ok(Grid::from_nested_iter([['x', 'x']])));
+    /// assert_eq!(Grid::try_new(2, -1, 'x'), Err(GridError::InvalidDimensions { width: 2, height: -1 }));
+    /// assert_eq!(Grid::try_new(i64::MAX, 2, 'x'), Err(GridError::TooLarge { width: i64::MAX, height: 2 }));
+    /// assert_eq!(Grid::try_new(1 << 31, 1 << 31, 0u64), Err(GridError::TooLarge { width: 1 << 31, height: 1 << 31 }));
+    /// ```
+    pub fn try_new(width: i64, height: i64, value: T) -> Result<Self, GridError> {
+        let (mut raw, size) = try_alloc(width, height)?;
         raw.resize(size, value);
-        Self {
+        Ok(Self {
             raw,
             dim: Vector::new(width, height),
-        }
+        })
     }
 }
 
@@ -89,13 +162,13 @@ impl<T: Default> Grid<T> {
     /// ```
     #[track_caller]
     pub fn default(width: i64, height: i64) -> Self {
-        let size = size(width, height);
-        let mut raw = Vec::with_capacity(size);
-        raw.resize_with(size, Default::default);
-        Self {
-            raw,
-            dim: Vector::new(width, height),
-        }
+        Self::try_default(width, height).or_panic()
+    }
+
+    /// Constructs a new `Grid<T>` with the given dimensions, initialising all values to their default value,
+    /// or returns an error if the dimensions are not positive or too large.
+    pub fn try_default(width: i64, height: i64) -> Result<Self, GridError> {
+        Self::try_from_simple_fn(width, height, T::default)
     }
 }
 
@@ -119,13 +192,21 @@ impl<T> Grid<T> {
     where
         F: FnMut() -> T,
     {
-        let size = size(width, height);
-        let mut raw = Vec::with_capacity(size);
+        Self::try_from_simple_fn(width, height, f).or_panic()
+    }
+
+    /// Constructs a new `Grid<T>` with the given dimensions, computing all initial values from the closure `f`,
+    /// or returns an error if the dimensions are not positive or too large.
+    pub fn try_from_simple_fn<F>(width: i64, height: i64, f: F) -> Result<Self, GridError>
+    where
+        F: FnMut() -> T,
+    {
+        let (mut raw, size) = try_alloc(width, height)?;
         raw.resize_with(size, f);
-        Self {
+        Ok(Self {
             raw,
             dim: Vector::new(width, height),
-        }
+        })
     }
 
     /// Constructs a new `Grid<T>` with the given dimensions, computing all initial values from the closure `f` which maps each position to a value.
@@ -143,20 +224,40 @@ impl<T> Grid<T> {
     /// assert_eq!(grid[v(7, 9)], 16);
     /// ```
     #[track_caller]
-    pub fn from_fn<F>(width: i64, height: i64, mut f: F) -> Self
+    pub fn from_fn<F>(width: i64, height: i64, f: F) -> Self
     where
         F: FnMut(Vector) -> T,
     {
-        let mut raw = Vec::with_capacity(size(width, height));
+        Self::try_from_fn(width, height, f).or_panic()
+    }
+
+    /// Constructs a new `Grid<T>` with the given dimensions, computing all initial values from the closure `f` which maps each position to a value,
+    /// or returns an error if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid = Grid::try_from_fn(3, 2, |pos| pos.x * pos.y).unwrap();
+    ///
+    /// assert_eq!(grid[v(2, 1)], 2);
+    /// assert!(Grid::try_from_fn(0, 0, |pos| pos.x).is_err());
+    /// ```
+    pub fn try_from_fn<F>(width: i64, height: i64, mut f: F) -> Result<Self, GridError>
+    where
+        F: FnMut(Vector) -> T,
+    {
+        let (mut raw, _) = try_alloc(width, height)?;
         for y in 0..height {
             for x in 0..width {
                 raw.push(f(Vector::new(x, y)));
             }
         }
-        Self {
+        Ok(Self {
             raw,
             dim: Vector::new(width, height),
-        }
+        })
     }
 
     /// Constructs a new `Grid<T>` with the given dimensions and values computed by an iterator in row-major order.
@@ -181,16 +282,36 @@ impl<T> Grid<T> {
     where
         I: IntoIterator<Item = T>,
     {
-        let size = size(width, height);
-        let mut raw = Vec::with_capacity(size);
-        let mut values = values.into_iter();
-        for _ in 0..size {
-            raw.push(values.next().expect("iterator too short"));
+        Self::try_from_iter(width, height, values).or_panic()
+    }
+
+    /// Constructs a new `Grid<T>` with the given dimensions and values computed by an iterator in row-major order,
+    /// or returns an error if the dimensions are not positive or too large, or if the iterator runs out before the grid is filled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// assert_eq!(Grid::try_from_iter(2, 1, [1, 2, 3]), Ok(Grid::from_nested_iter([[1, 2]])));
+    /// assert_eq!(Grid::try_from_iter(2, 2, [1, 2, 3]), Err(GridError::TooFewValues { expected: 4, found: 3 }));
+    /// ```
+    pub fn try_from_iter<I>(width: i64, height: i64, values: I) -> Result<Self, GridError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let (mut raw, size) = try_alloc(width, height)?;
+        raw.extend(values.into_iter().take(size));
+        if raw.len() < size {
+            return Err(GridError::TooFewValues {
+                expected: size,
+                found: raw.len(),
+            });
         }
-        Self {
+        Ok(Self {
             raw,
             dim: Vector::new(width, height),
-        }
+        })
     }
 
     /// Constructs a new `Grid<T>` from an iterator of iterators, where each inner iterator defines a row.
@@ -215,6 +336,29 @@ impl<T> Grid<T> {
     /// ```
     #[track_caller]
     pub fn from_nested_iter<I, J>(values: I) -> Self
+    where
+        I: IntoIterator<Item = J>,
+        J: IntoIterator<Item = T>,
+    {
+        Self::try_from_nested_iter(values).or_panic()
+    }
+
+    /// Constructs a new `Grid<T>` from an iterator of iterators, where each inner iterator defines a row,
+    /// or returns an error if not all inner iterators are the same length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let lines = ["#..", ".#.", "..##"];
+    ///
+    /// assert_eq!(
+    ///     Grid::try_from_nested_iter(lines.iter().map(|line| line.chars())),
+    ///     Err(GridError::RaggedInput { row: 2, expected: 3, found: 4 }),
+    /// );
+    /// ```
+    pub fn try_from_nested_iter<I, J>(values: I) -> Result<Self, GridError>
     where
         I: IntoIterator<Item = J>,
         J: IntoIterator<Item = T>,
@@ -222,31 +366,33 @@ impl<T> Grid<T> {
         let mut values = values.into_iter();
         let mut raw = Vec::new();
         let Some(first) = values.next() else {
-            return Self {
+            return Ok(Self {
                 raw,
                 dim: Vector::new(0, 0),
-            };
+            });
         };
         for value in first.into_iter() {
             raw.push(value);
         }
-        let width = raw.len() as i64;
+        let width = raw.len();
         let mut height = 1;
         for inner in values {
-            height += 1;
-            let mut count = 0;
-            for value in inner.into_iter() {
-                count += 1;
-                raw.push(value);
-            }
-            if count != width {
-                panic!("not all inner iterators are the same length");
+            let start = raw.len();
+            raw.extend(inner);
+            let found = raw.len() - start;
+            if found != width {
+                return Err(GridError::RaggedInput {
+                    row: height,
+                    expected: width,
+                    found,
+                });
             }
+            height += 1;
         }
-        Self {
+        Ok(Self {
             raw,
-            dim: Vector::new(width, height),
-        }
+            dim: Vector::new(width as i64, height as i64),
+        })
     }
 
     /// Returns the width of the grid.
@@ -361,6 +507,123 @@ impl<T> Grid<T> {
         Some(std::mem::replace(self.get_mut(pos)?, value))
     }
 
+    /// Swaps the values at two positions of the grid.
+    ///
+    /// Panics if either position is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut puzzle: Grid<u8> = Grid::from_nested_iter([[1, 2], [0, 3]]);
+    ///
+    /// puzzle.swap(v(0, 1), v(1, 1));
+    ///
+    /// assert_eq!(puzzle, Grid::from_nested_iter([[1, 2], [3, 0]]));
+    /// ```
+    #[track_caller]
+    pub fn swap(&mut self, a: Vector, b: Vector) {
+        self.try_swap(a, b).or_panic();
+    }
+
+    /// Swaps the values at two positions of the grid.
+    ///
+    /// Returns an error if either position is out of bounds, in which case nothing changes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<char> = Grid::from_nested_iter(["ab".chars()]);
+    ///
+    /// assert_eq!(grid.try_swap(v(0, 0), v(1, 0)), Ok(()));
+    /// assert!(grid.try_swap(v(0, 0), v(2, 0)).is_err());
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter(["ba".chars()]));
+    /// ```
+    pub fn try_swap(&mut self, a: Vector, b: Vector) -> Result<(), GridError> {
+        let index = |pos| {
+            self.get_index(pos)
+                .ok_or(GridError::out_of_bounds(self.dim, pos))
+        };
+        let (a, b) = (index(a)?, index(b)?);
+        self.raw.swap(a, b);
+        Ok(())
+    }
+
+    /// Overwrites every value in the grid with a clone of `value`, reusing the existing allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut scratch: Grid<u32> = Grid::from_fn(3, 2, |pos| (pos.x + pos.y) as u32);
+    ///
+    /// scratch.fill(0);
+    ///
+    /// assert_eq!(scratch, Grid::new(3, 2, 0));
+    /// ```
+    pub fn fill(&mut self, value: T)
+    where
+        T: Clone,
+    {
+        self.raw.fill(value);
+    }
+
+    /// Overwrites every value in the grid with the value returned by calling `f`, in row-major order,
+    /// reusing the existing allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(2, 2, 9);
+    ///
+    /// let mut next = 0;
+    /// grid.fill_with(|| {
+    ///     next += 1;
+    ///     next
+    /// });
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2], [3, 4]]));
+    /// ```
+    pub fn fill_with<F>(&mut self, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.raw.fill_with(f);
+    }
+
+    /// Updates the value at the given position of the grid to the result of the closure `f` applied to the current value.
+    ///
+    /// Returns the old value at that position, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(8, 10, 5);
+    ///
+    /// assert_eq!(grid.update(v(2, 3), |count| count + 1), Some(5));
+    /// assert_eq!(grid.update(v(2, 3), |count| count + 1), Some(6));
+    /// assert_eq!(grid.update(v(8, 3), |count| count + 1), None);
+    ///
+    /// assert_eq!(grid[v(2, 3)], 7);
+    /// ```
+    pub fn update<F>(&mut self, pos: Vector, f: F) -> Option<T>
+    where
+        F: FnOnce(&T) -> T,
+    {
+        let value = self.get_mut(pos)?;
+        let new = f(value);
+        Some(std::mem::replace(value, new))
+    }
+
     /// Returns `true` if the given position is within the bounds of the grid, or `false` otherwise.
     ///
     /// # Examples
@@ -381,6 +644,154 @@ impl<T> Grid<T> {
         (0..self.width()).contains(&pos.x) && (0..self.height()).contains(&pos.y)
     }
 
+    /// Returns the position within the bounds of the grid that is closest to the given position.
+    ///
+    /// Panics if the grid has no positions, because its width or height is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    ///
+    /// assert_eq!(grid.clamp_pos(v(3, 4)), v(3, 4));
+    /// assert_eq!(grid.clamp_pos(v(-3, 4)), v(0, 4));
+    /// assert_eq!(grid.clamp_pos(v(12, 15)), v(7, 9));
+    /// ```
+    #[track_caller]
+    pub fn clamp_pos(&self, pos: Vector) -> Vector {
+        self.try_clamp_pos(pos).or_panic()
+    }
+
+    /// Returns the position within the bounds of the grid that is closest to the given position, as in [`clamp_pos`](Self::clamp_pos),
+    /// or an error if the grid has no positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    /// let empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    ///
+    /// assert_eq!(grid.try_clamp_pos(v(-3, 12)), Ok(v(0, 9)));
+    /// assert_eq!(empty.try_clamp_pos(v(0, 0)), Err(GridError::InvalidDimensions { width: 0, height: 2 }));
+    /// ```
+    pub fn try_clamp_pos(&self, pos: Vector) -> Result<Vector, GridError> {
+        self.check_not_empty()?;
+        Ok(pos.clamp(Vector::new(0, 0), self.dim - Vector::new(1, 1)))
+    }
+
+    /// Returns the given position wrapped around the edges of the grid, as if the grid were a torus.
+    ///
+    /// Panics if the grid has no positions, because its width or height is `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    ///
+    /// assert_eq!(grid.wrap_pos(v(3, 4)), v(3, 4));
+    /// assert_eq!(grid.wrap_pos(v(-1, 4)), v(7, 4));
+    /// assert_eq!(grid.wrap_pos(v(17, -21)), v(1, 9));
+    /// ```
+    #[track_caller]
+    pub fn wrap_pos(&self, pos: Vector) -> Vector {
+        self.try_wrap_pos(pos).or_panic()
+    }
+
+    /// Returns the given position wrapped around the edges of the grid, as in [`wrap_pos`](Self::wrap_pos),
+    /// or an error if the grid has no positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    /// let empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    ///
+    /// assert_eq!(grid.try_wrap_pos(v(-1, 21)), Ok(v(7, 1)));
+    /// assert!(empty.try_wrap_pos(v(0, 0)).is_err());
+    /// ```
+    pub fn try_wrap_pos(&self, pos: Vector) -> Result<Vector, GridError> {
+        self.check_not_empty()?;
+        Ok(pos.rem_euclid(self.dim))
+    }
+
+    /// Returns an error if the grid has no positions.
+    fn check_not_empty(&self) -> Result<(), GridError> {
+        if self.raw.is_empty() {
+            return Err(GridError::InvalidDimensions {
+                width: self.dim.x,
+                height: self.dim.y,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns the position `pos + delta`, or `None` if it is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    ///
+    /// assert_eq!(grid.offset(v(3, 4), EAST), Some(v(4, 4)));
+    /// assert_eq!(grid.offset(v(0, 4), WEST), None);
+    /// assert_eq!(grid.offset(v(5, 5), v(2, 4)), Some(v(7, 9)));
+    /// ```
+    pub fn offset(&self, pos: Vector, delta: Vector) -> Option<Vector> {
+        let pos = pos + delta;
+        self.in_bounds(pos).then_some(pos)
+    }
+
+    /// Returns `true` if the given position is in bounds and on the outer edge of the grid, or `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    ///
+    /// assert_eq!(grid.is_border(v(0, 4)), true);
+    /// assert_eq!(grid.is_border(v(3, 9)), true);
+    /// assert_eq!(grid.is_border(v(7, 0)), true);
+    /// assert_eq!(grid.is_border(v(3, 4)), false);
+    /// assert_eq!(grid.is_border(v(8, 4)), false);
+    /// ```
+    pub fn is_border(&self, pos: Vector) -> bool {
+        self.in_bounds(pos)
+            && (pos.x == 0 || pos.y == 0 || pos.x == self.width() - 1 || pos.y == self.height() - 1)
+    }
+
+    /// Returns `true` if the given position is one of the four corners of the grid, or `false` otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(8, 10, 0);
+    ///
+    /// assert_eq!(grid.is_corner(v(0, 0)), true);
+    /// assert_eq!(grid.is_corner(v(7, 9)), true);
+    /// assert_eq!(grid.is_corner(v(0, 9)), true);
+    /// assert_eq!(grid.is_corner(v(0, 4)), false);
+    /// assert_eq!(grid.is_corner(v(8, 10)), false);
+    /// ```
+    pub fn is_corner(&self, pos: Vector) -> bool {
+        self.in_bounds(pos)
+            && (pos.x == 0 || pos.x == self.width() - 1)
+            && (pos.y == 0 || pos.y == self.height() - 1)
+    }
+
     fn get_index(&self, pos: Vector) -> Option<usize> {
         self.in_bounds(pos)
             .then(|| pos.x as usize + ((pos.y as usize) * (self.width() as usize)))
@@ -497,6 +908,160 @@ impl<T> Grid<T> {
         }
         Grid { raw, dim }
     }
+
+    /// Combines the values of two grids with the same dimensions to create a new grid,
+    /// where each value is `f` applied to the values at the same position in `self` and `other`.
+    ///
+    /// Panics if the dimensions of the grids differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let terrain: Grid<char> = Grid::from_nested_iter(["..~".chars(), ".~~".chars()]);
+    /// let costs: Grid<u64> = Grid::from_nested_iter([[1, 2, 3], [4, 5, 6]]);
+    ///
+    /// let total = terrain.zip_map(&costs, |&c, &cost| if c == '~' { cost * 10 } else { cost });
+    ///
+    /// assert_eq!(total, Grid::from_nested_iter([[1, 2, 30], [4, 50, 60]]));
+    /// ```
+    #[track_caller]
+    pub fn zip_map<S, F, U>(&self, other: &Grid<S>, f: F) -> Grid<U>
+    where
+        F: FnMut(&T, &S) -> U,
+    {
+        self.try_zip_map(other, f).or_panic()
+    }
+
+    /// Combines the values of two grids with the same dimensions to create a new grid, as in [`zip_map`](Self::zip_map),
+    /// or returns an error if the dimensions of the grids differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let before: Grid<bool> = Grid::from_nested_iter([[false, true], [true, true]]);
+    /// let after: Grid<bool> = Grid::from_nested_iter([[true, true], [false, true]]);
+    ///
+    /// let changed = before.try_zip_map(&after, |a, b| a != b).unwrap();
+    /// assert_eq!(changed, Grid::from_nested_iter([[true, false], [true, false]]));
+    ///
+    /// assert_eq!(
+    ///     before.try_zip_map(&Grid::new(3, 2, false), |a, b| a != b),
+    ///     Err(GridError::DimensionMismatch { expected: v(2, 2), found: v(3, 2) }),
+    /// );
+    /// ```
+    pub fn try_zip_map<S, F, U>(&self, other: &Grid<S>, mut f: F) -> Result<Grid<U>, GridError>
+    where
+        F: FnMut(&T, &S) -> U,
+    {
+        if other.dim != self.dim {
+            return Err(GridError::DimensionMismatch {
+                expected: self.dim,
+                found: other.dim,
+            });
+        }
+        let raw = self
+            .raw
+            .iter()
+            .zip(&other.raw)
+            .map(|(a, b)| f(a, b))
+            .collect();
+        Ok(Grid { raw, dim: self.dim })
+    }
+
+    /// Applies `f` to every value of the grid in place, in row-major order, without allocating a new grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(15, 14, 11);
+    ///
+    /// grid.apply(|value| *value += 2);
+    ///
+    /// assert_eq!(grid[v(2, 3)], 13);
+    /// ```
+    pub fn apply<F>(&mut self, f: F)
+    where
+        F: FnMut(&mut T),
+    {
+        self.raw.iter_mut().for_each(f);
+    }
+
+    /// Applies `f` to every value of the grid and its position in place, in row-major order, without allocating a new grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::new(5, 6, 3);
+    ///
+    /// grid.pos_apply(|pos, value| *value += pos.x);
+    ///
+    /// assert_eq!(grid[v(1, 4)], 4);
+    /// assert_eq!(grid[v(3, 0)], 6);
+    /// ```
+    pub fn pos_apply<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Vector, &mut T),
+    {
+        for (pos, value) in self.iter_mut_positions() {
+            f(pos, value);
+        }
+    }
+
+    /// Reinterprets the values of the grid as values of type `U` without copying the underlying buffer.
+    ///
+    /// This is intended for converting between a type and a `#[repr(transparent)]` wrapper around it.
+    ///
+    /// Consumes `self`.
+    ///
+    /// Panics if `T` and `U` differ in size or alignment.
+    ///
+    /// # Safety
+    ///
+    /// Every value in the grid must be a valid value of type `U` when reinterpreted bit-for-bit.
+    /// This holds when `U` is a `#[repr(transparent)]` wrapper around `T` (or vice versa) with no additional invariants.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// #[repr(transparent)]
+    /// #[derive(Debug, PartialEq)]
+    /// struct Height(u8);
+    ///
+    /// let grid: Grid<u8> = Grid::from_fn(4, 3, |pos| (pos.x + pos.y) as u8);
+    ///
+    /// let heights: Grid<Height> = unsafe { grid.cast() };
+    ///
+    /// assert_eq!(heights[v(3, 2)], Height(5));
+    ///
+    /// let grid: Grid<u8> = unsafe { heights.cast() };
+    ///
+    /// assert_eq!(grid[v(1, 2)], 3);
+    /// ```
+    #[track_caller]
+    pub unsafe fn cast<U>(self) -> Grid<U> {
+        if std::mem::size_of::<T>() != std::mem::size_of::<U>() {
+            panic!("cannot cast between types of different sizes");
+        }
+        if std::mem::align_of::<T>() != std::mem::align_of::<U>() {
+            panic!("cannot cast between types of different alignments");
+        }
+        let mut raw = std::mem::ManuallyDrop::new(self.raw);
+        let (ptr, len, capacity) = (raw.as_mut_ptr(), raw.len(), raw.capacity());
+        Grid {
+            raw: Vec::from_raw_parts(ptr.cast::<U>(), len, capacity),
+            dim: self.dim,
+        }
+    }
 }
 
 impl<T> Index<Vector> for Grid<T> {
@@ -547,13 +1112,51 @@ impl<T: fmt::Display> fmt::Debug for Grid<T> {
     }
 }
 
-#[track_caller]
-fn size(width: i64, height: i64) -> usize {
+/// Returns the number of values in a grid of `T` with the given dimensions,
+/// or an error if they are not positive or the values would take more than `isize::MAX` bytes.
+fn try_size<T>(width: i64, height: i64) -> Result<usize, GridError> {
     if width <= 0 || height <= 0 {
-        panic!("dimensions must be positive: ({width}, {height})");
+        return Err(GridError::InvalidDimensions { width, height });
+    }
+    (width as usize)
+        .checked_mul(height as usize)
+        .filter(|&size| {
+            size.checked_mul(std::mem::size_of::<T>())
+                .is_some_and(|bytes| bytes <= isize::MAX as usize)
+        })
+        .ok_or(GridError::TooLarge { width, height })
+}
+
+/// Returns an empty vector with room for every value in a grid of `T` with the given dimensions, and the number of values,
+/// or an error if the dimensions are not positive or too large, or if the allocation fails.
+fn try_alloc<T>(width: i64, height: i64) -> Result<(Vec<T>, usize), GridError> {
+    let size = try_size::<T>(width, height)?;
+    Ok((try_reserve(size, width, height)?, size))
+}
+
+/// Returns an empty vector with room for `len` values of a grid with the given dimensions,
+/// or an error if the allocation fails.
+fn try_reserve<T>(len: usize, width: i64, height: i64) -> Result<Vec<T>, GridError> {
+    let mut raw = Vec::new();
+    raw.try_reserve_exact(len)
+        .map_err(|_| GridError::TooLarge { width, height })?;
+    Ok(raw)
+}
+
+/// Returns an error if `region` is empty, or if it is not entirely in bounds of a grid with dimensions `dim`,
+/// without overflowing however far out of bounds it is.
+fn check_region(dim: Vector, region: Rect) -> Result<(), GridError> {
+    if region.is_empty() {
+        return Err(GridError::InvalidDimensions {
+            width: region.dim.x,
+            height: region.dim.y,
+        });
     }
-    if let Some(size) = (width as usize).checked_mul(height as usize) {
-        return size;
+    let fits = |start: i64, len: i64, limit: i64| {
+        start >= 0 && start.checked_add(len).is_some_and(|end| end <= limit)
+    };
+    if !fits(region.pos.x, region.dim.x, dim.x) || !fits(region.pos.y, region.dim.y, dim.y) {
+        return Err(GridError::OutOfBounds { dim, region });
     }
-    panic!("dimensions are too large: ({width}, {height})");
+    Ok(())
 }
@@ -0,0 +1,131 @@
+//! Area of effect templates, for targeting abilities in tactics games.
+
+use crate::{
+    grid::{visibility::Line, Grid},
+    rect::Rect,
+    vector::{Metric, Vector},
+};
+
+/// The shape of an area of effect, relative to the position it originates from.
+///
+/// Distances are measured from the origin, and include both ends, so a burst of radius `1`
+/// under [`Metric::Chebyshev`] is a 3x3 square.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AreaOfEffect {
+    /// Every position within `radius` of the origin, including the origin itself.
+    Burst { radius: i64, metric: Metric },
+    /// Every position at least `inner` and at most `outer` from the origin.
+    Donut {
+        inner: i64,
+        outer: i64,
+        metric: Metric,
+    },
+    /// The `length` positions of the straight [`Line`] heading from the origin in `direction`, not including the origin.
+    Line { direction: Vector, length: i64 },
+    /// A quarter turn wide cone opening from the origin in `direction`, up to `length` away in Chebyshev distance,
+    /// not including the origin.
+    ///
+    /// Facing in an orthogonal direction, each step away from the origin is two positions wider than the last.
+    /// Facing diagonally, the cone is a square corner.
+    Cone { direction: Vector, length: i64 },
+}
+
+impl AreaOfEffect {
+    /// Returns the positions covered when the area originates from `origin`, ignoring the bounds of any grid.
+    ///
+    /// Positions are in row-major order, except for a [`Line`](Self::Line), which runs outwards from the origin.
+    /// A line or cone with a zero `direction` covers nothing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{aoe::AreaOfEffect, prelude::*};
+    ///
+    /// let cone = AreaOfEffect::Cone { direction: EAST, length: 2 };
+    /// assert_eq!(cone.positions(v(0, 0)), vec![v(2, -2), v(1, -1), v(2, -1), v(1, 0), v(2, 0), v(1, 1), v(2, 1), v(2, 2)]);
+    ///
+    /// let line = AreaOfEffect::Line { direction: v(2, 1), length: 4 };
+    /// assert_eq!(line.positions(v(0, 0)), vec![v(1, 1), v(2, 1), v(3, 2), v(4, 2)]);
+    ///
+    /// let donut = AreaOfEffect::Donut { inner: 2, outer: 2, metric: Metric::Manhattan };
+    /// assert_eq!(donut.positions(v(5, 5)).len(), 8);
+    /// ```
+    pub fn positions(&self, origin: Vector) -> Vec<Vector> {
+        match *self {
+            Self::Line { direction, length } => {
+                if direction == Vector::new(0, 0) || length <= 0 {
+                    return Vec::new();
+                }
+                Line::new(origin, origin + direction * length)
+                    .skip(1)
+                    .take(length as usize)
+                    .collect()
+            }
+            _ => {
+                let reach = self.reach();
+                Rect::from_corners(
+                    origin - Vector::new(reach, reach),
+                    origin + Vector::new(reach + 1, reach + 1),
+                )
+                .positions()
+                .filter(|&pos| self.covers(pos - origin))
+                .collect()
+            }
+        }
+    }
+
+    /// Returns the furthest the area extends from its origin along either axis.
+    fn reach(&self) -> i64 {
+        match *self {
+            Self::Burst { radius, .. } => radius,
+            Self::Donut { outer, .. } => outer,
+            Self::Line { length, .. } | Self::Cone { length, .. } => length,
+        }
+    }
+
+    /// Returns `true` if the area covers the position at the given offset from its origin, for any area but a line.
+    fn covers(&self, offset: Vector) -> bool {
+        let distance = |metric: Metric| metric.distance(Vector::new(0, 0), offset);
+        match *self {
+            Self::Burst { radius, metric } => distance(metric) <= radius as f64,
+            Self::Donut {
+                inner,
+                outer,
+                metric,
+            } => (inner as f64..=outer as f64).contains(&distance(metric)),
+            Self::Cone { direction, length } => {
+                let along = offset.dot(direction);
+                along > 0
+                    && offset.perp_dot(direction).abs() <= along
+                    && offset.chebyshev(Vector::new(0, 0)) <= length
+            }
+            Self::Line { .. } => unreachable!(),
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns the in-bounds positions covered by the given area of effect when it originates from `origin`,
+    /// in the order given by [`AreaOfEffect::positions`].
+    ///
+    /// The origin itself need not be in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{aoe::AreaOfEffect, prelude::*};
+    ///
+    /// let grid: Grid<u8> = Grid::new(5, 5, 0);
+    ///
+    /// let burst = AreaOfEffect::Burst { radius: 1, metric: Metric::Chebyshev };
+    /// assert_eq!(grid.area_of_effect(v(0, 0), &burst), vec![v(0, 0), v(1, 0), v(0, 1), v(1, 1)]);
+    ///
+    /// let cone = AreaOfEffect::Cone { direction: NORTH, length: 3 };
+    /// assert_eq!(grid.area_of_effect(v(1, 2), &cone), vec![v(0, 0), v(1, 0), v(2, 0), v(3, 0), v(0, 1), v(1, 1), v(2, 1)]);
+    /// ```
+    pub fn area_of_effect(&self, origin: Vector, area: &AreaOfEffect) -> Vec<Vector> {
+        let mut positions = area.positions(origin);
+        positions.retain(|&pos| self.in_bounds(pos));
+        positions
+    }
+}
@@ -0,0 +1,79 @@
+//! Copying rectangular regions from one `Grid` into another.
+
+use crate::{grid::Grid, rect::Rect, vector::Vector};
+
+impl<T: Copy> Grid<T> {
+    /// Copies every value of `src` into `self`, with the top-left corner of `src` at `offset`.
+    ///
+    /// Values that would land out of bounds are skipped, so `src` can hang over any edge of `self`.
+    ///
+    /// Returns the region of `self` that was written to, or `None` if `src` lies entirely out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut map: Grid<char> = Grid::new(5, 3, '.');
+    /// let house: Grid<char> = Grid::from_nested_iter(["/\\".chars(), "[]".chars()]);
+    ///
+    /// assert_eq!(map.copy_from(&house, v(1, 0)), Some(Rect::new(v(1, 0), v(2, 2))));
+    /// assert_eq!(map.copy_from(&house, v(4, 2)), Some(Rect::new(v(4, 2), v(1, 1))));
+    /// assert_eq!(map.copy_from(&house, v(-2, 0)), None);
+    ///
+    /// assert_eq!(map, Grid::from_nested_iter([
+    ///     "./\\..".chars(),
+    ///     ".[]..".chars(),
+    ///     "..../".chars(),
+    /// ]));
+    /// ```
+    pub fn copy_from(&mut self, src: &Grid<T>, offset: Vector) -> Option<Rect> {
+        self.copy_rect_from(src, Rect::new(Vector::new(0, 0), src.dim), offset)
+    }
+
+    /// Copies the values in the region `src_rect` of `src` into `self`, with the top-left corner of the region at `offset`.
+    ///
+    /// Positions of the region that are out of bounds of `src`, or would land out of bounds of `self`, are skipped.
+    ///
+    /// Returns the region of `self` that was written to, or `None` if nothing was copied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let tiles: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 1, 2, 2],
+    ///     [1, 1, 2, 2],
+    /// ]);
+    /// let mut map: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// map.copy_rect_from(&tiles, Rect::new(v(2, 0), v(2, 2)), v(1, 1));
+    ///
+    /// assert_eq!(map, Grid::from_nested_iter([
+    ///     [0, 0, 0],
+    ///     [0, 2, 2],
+    ///     [0, 2, 2],
+    /// ]));
+    /// ```
+    pub fn copy_rect_from(
+        &mut self,
+        src: &Grid<T>,
+        src_rect: Rect,
+        offset: Vector,
+    ) -> Option<Rect> {
+        let src_rect = src_rect.intersection(&Rect::new(Vector::new(0, 0), src.dim))?;
+        let shift = offset - src_rect.pos;
+        let dest_rect = Rect::new(src_rect.pos + shift, src_rect.dim)
+            .intersection(&Rect::new(Vector::new(0, 0), self.dim))?;
+        let src_rect = Rect::new(dest_rect.pos - shift, dest_rect.dim);
+        let width = dest_rect.dim.x as usize;
+        for y in 0..dest_rect.dim.y {
+            let src_start = src.get_index(src_rect.pos + Vector::new(0, y)).unwrap();
+            let dest_start = self.get_index(dest_rect.pos + Vector::new(0, y)).unwrap();
+            self.raw[dest_start..dest_start + width]
+                .copy_from_slice(&src.raw[src_start..src_start + width]);
+        }
+        Some(dest_rect)
+    }
+}
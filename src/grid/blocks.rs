@@ -0,0 +1,248 @@
+//! Iterating over a `Grid` in fixed-size blocks with a surrounding halo, and writing processed blocks back.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{check_region, Grid},
+    rect::Rect,
+    vector::Vector,
+};
+
+impl<T: Clone> Grid<T> {
+    /// Returns an iterator over copies of the grid split into blocks of the given dimensions, in row-major order.
+    ///
+    /// Each block has a core region of at most `block_dim`, so blocks on the right and bottom edges may be smaller.
+    /// The core regions of all blocks are disjoint and together cover the entire grid.
+    ///
+    /// Each block also includes a halo of up to `halo` cells around its core region on each side,
+    /// clipped to the bounds of the grid, so that neighbourhood operations can be computed for every cell in the core.
+    ///
+    /// Panics if the block dimensions are not positive or the halo is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(5, 4, |pos| pos.x + pos.y * 5);
+    ///
+    /// let blocks: Vec<_> = grid.blocks(v(3, 3), 1).collect();
+    ///
+    /// assert_eq!(blocks.len(), 4);
+    ///
+    /// assert_eq!(blocks[0].core_pos(), v(0, 0));
+    /// assert_eq!(blocks[0].core_dim(), v(3, 3));
+    /// assert_eq!(blocks[0].grid().dim(), v(4, 4));
+    ///
+    /// assert_eq!(blocks[3].core_pos(), v(3, 3));
+    /// assert_eq!(blocks[3].core_dim(), v(2, 1));
+    /// assert_eq!(blocks[3].origin(), v(2, 2));
+    /// assert_eq!(blocks[3].grid()[v(0, 0)], grid[v(2, 2)]);
+    ///
+    /// let whole: Vec<_> = grid.blocks(v(i64::MAX, i64::MAX), i64::MAX).collect();
+    /// assert_eq!(whole.len(), 1);
+    /// assert_eq!(whole[0].grid(), &grid);
+    /// ```
+    #[track_caller]
+    pub fn blocks(&self, block_dim: Vector, halo: i64) -> Blocks<'_, T> {
+        self.try_blocks(block_dim, halo).or_panic()
+    }
+
+    /// Returns an iterator over copies of the grid split into blocks, as in [`blocks`](Self::blocks),
+    /// or an error if the block dimensions are not positive or the halo is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(4, 4, 0);
+    ///
+    /// assert_eq!(grid.try_blocks(v(2, 2), 0).map(Iterator::count), Ok(4));
+    /// assert_eq!(grid.try_blocks(v(2, 0), 0).err(), Some(GridError::InvalidDimensions { width: 2, height: 0 }));
+    /// assert!(grid.try_blocks(v(2, 2), -1).is_err());
+    /// ```
+    pub fn try_blocks(&self, block_dim: Vector, halo: i64) -> Result<Blocks<'_, T>, GridError> {
+        if block_dim.x <= 0 || block_dim.y <= 0 {
+            return Err(GridError::InvalidDimensions {
+                width: block_dim.x,
+                height: block_dim.y,
+            });
+        }
+        if halo < 0 {
+            return Err(GridError::InvalidArgument("halo must not be negative"));
+        }
+        Ok(Blocks {
+            grid: self,
+            block_dim,
+            halo,
+            pos: Vector::new(0, 0),
+        })
+    }
+
+    /// Writes the core region of a block back into the grid at the position it was taken from.
+    ///
+    /// The halo of the block is ignored, so blocks produced by [`Grid::blocks`] can be written back in any order.
+    ///
+    /// Panics if the core region of the block is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(7, 5, |pos| pos.x * pos.y);
+    ///
+    /// // sum of each value and its orthogonal neighbours, computed block by block
+    /// let mut sums = grid.clone();
+    /// for mut block in grid.blocks(v(3, 2), 1) {
+    ///     let source = block.grid().clone();
+    ///     let core = block.core_pos() - block.origin();
+    ///     for y in 0..block.core_dim().y {
+    ///         for x in 0..block.core_dim().x {
+    ///             let pos = core + v(x, y);
+    ///             let sum = ORTHOGONAL_ZERO
+    ///                 .iter()
+    ///                 .filter_map(|&offset| source.get(pos + offset))
+    ///                 .sum();
+    ///             block.grid_mut()[pos] = sum;
+    ///         }
+    ///     }
+    ///     sums.write_block(&block);
+    /// }
+    ///
+    /// assert_eq!(sums[v(3, 2)], 6 + 4 + 9 + 3 + 8);
+    /// assert_eq!(sums[v(6, 4)], 24 + 18 + 20);
+    /// ```
+    #[track_caller]
+    pub fn write_block(&mut self, block: &Block<T>) {
+        self.try_write_block(block).or_panic()
+    }
+
+    /// Writes the core region of a block back into the grid, as in [`write_block`](Self::write_block),
+    /// or returns an error without changing the grid if the core region of the block is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let big: Grid<u8> = Grid::new(4, 4, 1);
+    /// let mut small: Grid<u8> = Grid::new(2, 2, 0);
+    ///
+    /// let blocks: Vec<_> = big.blocks(v(2, 2), 0).collect();
+    ///
+    /// assert_eq!(small.try_write_block(&blocks[0]), Ok(()));
+    /// assert_eq!(
+    ///     small.try_write_block(&blocks[3]),
+    ///     Err(GridError::OutOfBounds { dim: v(2, 2), region: Rect::new(v(2, 2), v(2, 2)) }),
+    /// );
+    /// assert_eq!(small, Grid::new(2, 2, 1));
+    /// ```
+    pub fn try_write_block(&mut self, block: &Block<T>) -> Result<(), GridError> {
+        check_region(self.dim, Rect::new(block.core_pos, block.core_dim))?;
+        let offset = block.core_pos - block.origin;
+        for y in 0..block.core_dim.y {
+            for x in 0..block.core_dim.x {
+                let pos = Vector::new(x, y);
+                self[block.core_pos + pos] = block.grid[offset + pos].clone();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A copy of a rectangular block of a `Grid`, consisting of a core region surrounded by a halo.
+///
+/// Constructed by [`Grid::blocks`], and written back with [`Grid::write_block`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct Block<T> {
+    grid: Grid<T>,
+    origin: Vector,
+    core_pos: Vector,
+    core_dim: Vector,
+}
+
+impl<T> Block<T> {
+    /// Returns a reference to the values of the block, including its halo.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the values of the block, including its halo.
+    #[inline]
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    /// Returns the values of the block, including its halo, consuming the block.
+    #[inline]
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Returns the position in the source grid of the top-left cell of the block, including its halo.
+    #[inline]
+    pub fn origin(&self) -> Vector {
+        self.origin
+    }
+
+    /// Returns the position in the source grid of the top-left cell of the core region of the block.
+    #[inline]
+    pub fn core_pos(&self) -> Vector {
+        self.core_pos
+    }
+
+    /// Returns the dimensions of the core region of the block.
+    #[inline]
+    pub fn core_dim(&self) -> Vector {
+        self.core_dim
+    }
+}
+
+/// An iterator over copies of a `Grid` split into blocks with halos, in row-major order.
+///
+/// Constructed by [`Grid::blocks`].
+pub struct Blocks<'a, T> {
+    grid: &'a Grid<T>,
+    block_dim: Vector,
+    halo: i64,
+    pos: Vector,
+}
+
+impl<'a, T: Clone> Iterator for Blocks<'a, T> {
+    type Item = Block<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos.y >= self.grid.height() || self.pos.x >= self.grid.width() {
+            return None;
+        }
+        let core_pos = self.pos;
+        let core_dim = self.block_dim.min(self.grid.dim() - core_pos);
+
+        // saturating, since the block dimensions and halo may be as large as `i64::MAX`
+        self.pos.x = self.pos.x.saturating_add(self.block_dim.x);
+        if self.pos.x >= self.grid.width() {
+            self.pos.x = 0;
+            self.pos.y = self.pos.y.saturating_add(self.block_dim.y);
+        }
+
+        let halo = Vector::new(self.halo, self.halo);
+        let origin = (core_pos - halo).max(Vector::new(0, 0));
+        let core_end = core_pos + core_dim;
+        let end = Vector::new(
+            core_end.x.saturating_add(self.halo),
+            core_end.y.saturating_add(self.halo),
+        )
+        .min(self.grid.dim());
+        let dim = end - origin;
+        let grid = Grid::from_fn(dim.x, dim.y, |pos| self.grid[origin + pos].clone());
+
+        Some(Block {
+            grid,
+            origin,
+            core_pos,
+            core_dim,
+        })
+    }
+}
@@ -0,0 +1,168 @@
+//! Binary space partitioning of rectangles, the usual way of laying out the rooms of a generated level.
+
+use crate::{
+    error::{GridError, OrPanic},
+    rect::Rect,
+    rng::Rng,
+    vector::Vector,
+};
+
+/// A binary space partition of a rectangle: either a leaf, or split in two along a vertical or horizontal line
+/// into two smaller partitions.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{bsp::BspTree, dungeon::Tile, prelude::*};
+///
+/// let area = Rect::new(v(0, 0), v(40, 30));
+/// let tree = BspTree::new(area, v(6, 5), v(12, 10), 3);
+/// let leaves = tree.leaves();
+///
+/// assert!(leaves.iter().all(|leaf| (6..=12).contains(&leaf.dim.x) && (5..=10).contains(&leaf.dim.y)));
+/// assert_eq!(leaves.iter().map(Rect::area).sum::<i64>(), area.area());
+/// assert_eq!(BspTree::new(area, v(6, 5), v(12, 10), 3), tree);
+///
+/// // a room in each leaf, with a wall around it
+/// let mut tiles: Grid<Tile> = Grid::default(40, 30);
+/// for leaf in &leaves {
+///     let room = Rect::new(leaf.pos + v(1, 1), leaf.dim - v(2, 2));
+///     for pos in room.positions() {
+///         tiles[pos] = Tile::Floor;
+///     }
+/// }
+///
+/// let floors = leaves.iter().map(|leaf| (leaf.dim.x - 2) * (leaf.dim.y - 2)).sum::<i64>();
+/// assert_eq!(tiles.iter().filter(|&&tile| tile == Tile::Floor).count() as i64, floors);
+///
+/// let [first, second] = tree.children().unwrap();
+/// assert!(first.rect().intersection(&second.rect()).is_none());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BspTree {
+    rect: Rect,
+    children: Option<Box<[BspTree; 2]>>,
+}
+
+impl BspTree {
+    /// Partitions `rect` by recursively splitting it in two, at positions chosen at random from `seed`,
+    /// until each leaf is no larger than `max_size` or cannot be split without making a leaf smaller than `min_size`.
+    ///
+    /// A partition is split across whichever of its dimensions is too large, or across its longer dimension if both are.
+    /// A leaf is only larger than `max_size` in a dimension if `rect` was, and that dimension is less than twice `min_size`.
+    ///
+    /// Panics if `min_size` is not positive, or is greater than `max_size`.
+    #[track_caller]
+    pub fn new(rect: Rect, min_size: Vector, max_size: Vector, seed: u64) -> Self {
+        Self::try_new(rect, min_size, max_size, seed).or_panic()
+    }
+
+    /// Partitions `rect` as in [`new`](Self::new),
+    /// or returns an error if `min_size` is not positive, or is greater than `max_size`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{bsp::BspTree, prelude::*};
+    ///
+    /// let area = Rect::new(v(0, 0), v(40, 30));
+    ///
+    /// assert!(BspTree::try_new(area, v(6, 6), v(12, 12), 1).is_ok());
+    /// assert!(BspTree::try_new(area, v(6, 6), v(4, 12), 1).is_err());
+    /// ```
+    pub fn try_new(
+        rect: Rect,
+        min_size: Vector,
+        max_size: Vector,
+        seed: u64,
+    ) -> Result<Self, GridError> {
+        if min_size.x <= 0 || min_size.y <= 0 || min_size.x > max_size.x || min_size.y > max_size.y
+        {
+            return Err(GridError::InvalidArgument(
+                "leaf sizes must be positive, with the minimum no greater than the maximum",
+            ));
+        }
+        Ok(Self::split(rect, min_size, max_size, &mut Rng::new(seed)))
+    }
+
+    fn split(rect: Rect, min_size: Vector, max_size: Vector, rng: &mut Rng) -> Self {
+        let splittable = |len: i64, min: i64, max: i64| len > max && len >= 2 * min;
+        let x = splittable(rect.dim.x, min_size.x, max_size.x);
+        let y = splittable(rect.dim.y, min_size.y, max_size.y);
+        let vertical = match (x, y) {
+            (false, false) => {
+                return Self {
+                    rect,
+                    children: None,
+                }
+            }
+            (true, true) if rect.dim.x == rect.dim.y => rng.coin(),
+            (true, true) => rect.dim.x > rect.dim.y,
+            (vertical, _) => vertical,
+        };
+        let (first, second) = if vertical {
+            let at = rng.range(min_size.x..=rect.dim.x - min_size.x);
+            (
+                Rect::new(rect.pos, Vector::new(at, rect.dim.y)),
+                Rect::new(
+                    rect.pos + Vector::new(at, 0),
+                    Vector::new(rect.dim.x - at, rect.dim.y),
+                ),
+            )
+        } else {
+            let at = rng.range(min_size.y..=rect.dim.y - min_size.y);
+            (
+                Rect::new(rect.pos, Vector::new(rect.dim.x, at)),
+                Rect::new(
+                    rect.pos + Vector::new(0, at),
+                    Vector::new(rect.dim.x, rect.dim.y - at),
+                ),
+            )
+        };
+        Self {
+            rect,
+            children: Some(Box::new([
+                Self::split(first, min_size, max_size, rng),
+                Self::split(second, min_size, max_size, rng),
+            ])),
+        }
+    }
+
+    /// Returns the rectangle covered by this partition.
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Returns the two halves this partition is split into, left then right or top then bottom, or `None` if it is a leaf.
+    #[inline]
+    pub fn children(&self) -> Option<&[BspTree; 2]> {
+        self.children.as_deref()
+    }
+
+    /// Returns `true` if this partition is not split, or `false` otherwise.
+    #[inline]
+    pub fn is_leaf(&self) -> bool {
+        self.children.is_none()
+    }
+
+    /// Returns the number of splits from this partition down to its deepest leaf.
+    pub fn depth(&self) -> usize {
+        self.children()
+            .map_or(0, |[first, second]| 1 + first.depth().max(second.depth()))
+    }
+
+    /// Returns the rectangles of the leaves of this partition, which together cover its rectangle exactly,
+    /// in depth-first order with the left or top half first.
+    pub fn leaves(&self) -> Vec<Rect> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![self];
+        while let Some(tree) = stack.pop() {
+            match tree.children() {
+                Some([first, second]) => stack.extend([second, first]),
+                None => leaves.push(tree.rect),
+            }
+        }
+        leaves
+    }
+}
@@ -0,0 +1,131 @@
+//! Cave generation by cellular automata.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    rng::Rng,
+    trace::span,
+    vector::constants::{ADJACENT, ORTHOGONAL},
+};
+
+/// The parameters of a cave generator, using the classic 4-5 rule cellular automaton.
+///
+/// Each position starts as a wall at random, then the automaton is run for a number of iterations,
+/// where a position becomes a wall if at least 5 of the 9 positions in the 3x3 square around it are walls,
+/// and floor otherwise. Positions beyond the edge of the grid count as walls, and the outermost positions are always walls.
+/// Finally, caverns too small to be worth exploring are filled in.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{cave::CaveGenerator, prelude::*};
+///
+/// let generator = CaveGenerator::default();
+/// let walls = generator.generate(60, 40, 11);
+///
+/// let floors = walls.iter().filter(|&&wall| !wall).count();
+/// assert!(floors > 60 * 40 / 4);
+/// assert!(walls.iter_positions().all(|(pos, &wall)| wall || !walls.is_border(pos)));
+/// assert_eq!(generator.generate(60, 40, 11), walls);
+///
+/// let single = CaveGenerator { min_cavern_size: usize::MAX, ..generator }.generate(60, 40, 11);
+/// assert!(single.iter().all(|&wall| wall));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CaveGenerator {
+    /// The chance of each position starting as a wall, between `0` and `1`.
+    pub fill: f32,
+    /// The number of iterations of the automaton to run.
+    pub iterations: usize,
+    /// The number of positions in the smallest cavern that is kept, where a cavern is a region of floor
+    /// connected through orthogonal neighbours.
+    pub min_cavern_size: usize,
+}
+
+impl Default for CaveGenerator {
+    fn default() -> Self {
+        Self {
+            fill: 0.45,
+            iterations: 5,
+            min_cavern_size: 16,
+        }
+    }
+}
+
+impl CaveGenerator {
+    /// Generates a cave with the given dimensions, where `true` is wall and `false` is floor,
+    /// with the starting walls chosen at random from `seed`, so the same seed and parameters always produce the same cave.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    #[track_caller]
+    pub fn generate(&self, width: i64, height: i64, seed: u64) -> Grid<bool> {
+        self.try_generate(width, height, seed).or_panic()
+    }
+
+    /// Generates a cave with the given dimensions, as in [`generate`](Self::generate),
+    /// or returns an error if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{cave::CaveGenerator, prelude::*};
+    ///
+    /// let generator = CaveGenerator::default();
+    ///
+    /// assert_eq!(generator.try_generate(20, 10, 1).map(|cave| cave.dim()), Ok(v(20, 10)));
+    /// assert_eq!(generator.try_generate(20, 0, 1), Err(GridError::InvalidDimensions { width: 20, height: 0 }));
+    /// ```
+    pub fn try_generate(
+        &self,
+        width: i64,
+        height: i64,
+        seed: u64,
+    ) -> Result<Grid<bool>, GridError> {
+        let mut walls = Grid::try_new(width, height, true)?;
+        span!("cave_generate", width, height, iterations = self.iterations);
+        let mut rng = Rng::new(seed);
+        for (pos, wall) in walls.iter_mut_positions() {
+            if pos.x > 0 && pos.y > 0 && pos.x < width - 1 && pos.y < height - 1 {
+                *wall = rng.unit_f32() < self.fill;
+            }
+        }
+        for _ in 0..self.iterations {
+            walls = Grid::from_fn(width, height, |pos| {
+                let neighbors = ADJACENT
+                    .iter()
+                    .filter(|&&offset| walls.get(pos + offset) != Some(&false))
+                    .count();
+                walls.is_border(pos) || neighbors + walls[pos] as usize >= 5
+            });
+        }
+        self.fill_small_caverns(&mut walls);
+        Ok(walls)
+    }
+
+    fn fill_small_caverns(&self, walls: &mut Grid<bool>) {
+        let mut seen = Grid::new(walls.width(), walls.height(), false);
+        for start in walls.positions() {
+            if walls[start] || seen[start] {
+                continue;
+            }
+            seen[start] = true;
+            let mut cavern = vec![start];
+            let mut i = 0;
+            while let Some(&pos) = cavern.get(i) {
+                i += 1;
+                for offset in ORTHOGONAL {
+                    let neighbor = pos + offset;
+                    if walls.get(neighbor) == Some(&false) && !seen[neighbor] {
+                        seen[neighbor] = true;
+                        cavern.push(neighbor);
+                    }
+                }
+            }
+            if cavern.len() < self.min_cavern_size {
+                for pos in cavern {
+                    walls[pos] = true;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,445 @@
+//! Views over the columns of a `Grid`.
+
+use crate::grid::Grid;
+
+use std::{
+    iter::{FusedIterator, StepBy},
+    ops::{Index, IndexMut},
+    slice::{Iter, IterMut},
+};
+
+impl<T> Grid<T> {
+    /// Returns a view of the column of the grid at the given `x` coordinate.
+    ///
+    /// Panics if `x` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// let col = grid.col(1);
+    ///
+    /// assert_eq!(col.len(), 3);
+    /// assert_eq!(col[2], 9);
+    /// assert_eq!(col.get(3), None);
+    /// assert_eq!(col.copy_to_vec(), vec![1, 5, 9]);
+    /// ```
+    #[track_caller]
+    pub fn col(&self, x: i64) -> Column<'_, T> {
+        let width = self.check_col(x);
+        Column {
+            raw: &self.raw[x as usize..],
+            width,
+            x,
+        }
+    }
+
+    /// Returns a mutable view of the column of the grid at the given `x` coordinate.
+    ///
+    /// Panics if `x` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// let mut col = grid.col_mut(1);
+    /// col.swap(0, 2);
+    /// col[1] = 0;
+    ///
+    /// assert_eq!(grid.col(1).copy_to_vec(), vec![9, 0, 1]);
+    /// assert_eq!(grid[v(1, 0)], 9);
+    /// ```
+    #[track_caller]
+    pub fn col_mut(&mut self, x: i64) -> ColumnMut<'_, T> {
+        let width = self.check_col(x);
+        ColumnMut {
+            raw: &mut self.raw[x as usize..],
+            width,
+            x,
+        }
+    }
+
+    /// Returns a view of the column of the grid at the given `x` coordinate, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// assert_eq!(grid.get_col(3).map(|col| col.copy_to_vec()), Some(vec![3, 7, 11]));
+    /// assert!(grid.get_col(4).is_none());
+    /// assert!(grid.get_col(-1).is_none());
+    /// ```
+    pub fn get_col(&self, x: i64) -> Option<Column<'_, T>> {
+        self.has_col(x).then(|| Column {
+            raw: &self.raw[x as usize..],
+            width: self.width() as usize,
+            x,
+        })
+    }
+
+    /// Returns a mutable view of the column of the grid at the given `x` coordinate, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// if let Some(col) = grid.get_col_mut(0) {
+    ///     for value in col {
+    ///         *value = -*value;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(grid.col(0).copy_to_vec(), vec![0, -4, -8]);
+    /// assert!(grid.get_col_mut(4).is_none());
+    /// ```
+    pub fn get_col_mut(&mut self, x: i64) -> Option<ColumnMut<'_, T>> {
+        let width = self.width() as usize;
+        self.has_col(x).then(|| ColumnMut {
+            raw: &mut self.raw[x as usize..],
+            width,
+            x,
+        })
+    }
+
+    /// Returns an iterator over views of the columns of the grid, from left to right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "#.#.".chars(),
+    ///     "#..#".chars(),
+    ///     "#...".chars(),
+    /// ]);
+    ///
+    /// let solid: Vec<i64> = grid
+    ///     .columns()
+    ///     .filter(|col| col.iter().all(|&c| c == '#'))
+    ///     .map(|col| col.x())
+    ///     .collect();
+    ///
+    /// assert_eq!(solid, vec![0]);
+    /// assert_eq!(grid.columns().len(), 4);
+    /// assert_eq!(grid.columns().next_back().unwrap().copy_to_vec(), vec!['.', '#', '.']);
+    /// ```
+    #[inline]
+    pub fn columns(&self) -> Columns<'_, T> {
+        Columns {
+            grid: self,
+            front: 0,
+            back: self.width(),
+        }
+    }
+
+    fn has_col(&self, x: i64) -> bool {
+        (0..self.width()).contains(&x)
+    }
+
+    #[track_caller]
+    fn check_col(&self, x: i64) -> usize {
+        if !self.has_col(x) {
+            panic!(
+                "column out of bounds: the width is {} but the column is {x}",
+                self.width()
+            );
+        }
+        self.width() as usize
+    }
+}
+
+/// An immutable view of a single column of a `Grid`, indexed by row.
+///
+/// Constructed by [`Grid::col`].
+pub struct Column<'a, T> {
+    raw: &'a [T],
+    width: usize,
+    x: i64,
+}
+
+impl<'a, T> Column<'a, T> {
+    /// Returns the `x` coordinate of the column in the grid.
+    #[inline]
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+
+    /// Returns the number of values in the column, which is the height of the grid.
+    #[inline]
+    pub fn len(&self) -> i64 {
+        self.raw.len().div_ceil(self.width) as i64
+    }
+
+    /// Returns `true` if the column contains no values, which is never the case for a column of a non-empty grid.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns a reference to the value at the given row of the column, or `None` if out of bounds.
+    pub fn get(&self, y: i64) -> Option<&'a T> {
+        get_index(y, self.len()).map(|y| &self.raw[y * self.width])
+    }
+
+    /// Returns an iterator over the values in the column, from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x * pos.y);
+    ///
+    /// assert_eq!(grid.col(3).iter().sum::<i64>(), 9);
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> StepBy<Iter<'a, T>> {
+        self.raw.iter().step_by(self.width)
+    }
+
+    /// Returns a new `Vec<T>` containing copies of the values in the column, from top to bottom.
+    pub fn copy_to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<'a, T> Clone for Column<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for Column<'a, T> {}
+
+impl<'a, T> IntoIterator for Column<'a, T> {
+    type Item = &'a T;
+    type IntoIter = StepBy<Iter<'a, T>>;
+
+    /// Returns an iterator over the values in the column, from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// let mut total = 0;
+    /// for value in grid.col(2) {
+    ///     total += value;
+    /// }
+    ///
+    /// assert_eq!(total, 2 + 6 + 10);
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T> Index<i64> for Column<'a, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, y: i64) -> &Self::Output {
+        let Some(value) = self.get(y) else {
+            panic!(
+                "row out of bounds: the column length is {} but the row is {y}",
+                self.len()
+            )
+        };
+        value
+    }
+}
+
+/// An iterator over views of the columns of a `Grid`, from left to right.
+///
+/// Constructed by [`Grid::columns`].
+pub struct Columns<'a, T> {
+    grid: &'a Grid<T>,
+    front: i64,
+    back: i64,
+}
+
+impl<'a, T> Iterator for Columns<'a, T> {
+    type Item = Column<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.grid.get_col(self.front - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Columns<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.grid.get_col(self.back)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Columns<'a, T> {}
+
+impl<'a, T> FusedIterator for Columns<'a, T> {}
+
+impl<'a, T> Clone for Columns<'a, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+/// A mutable view of a single column of a `Grid`, indexed by row.
+///
+/// Constructed by [`Grid::col_mut`].
+pub struct ColumnMut<'a, T> {
+    raw: &'a mut [T],
+    width: usize,
+    x: i64,
+}
+
+impl<'a, T> ColumnMut<'a, T> {
+    /// Returns the `x` coordinate of the column in the grid.
+    #[inline]
+    pub fn x(&self) -> i64 {
+        self.x
+    }
+
+    /// Returns the number of values in the column, which is the height of the grid.
+    #[inline]
+    pub fn len(&self) -> i64 {
+        self.raw.len().div_ceil(self.width) as i64
+    }
+
+    /// Returns `true` if the column contains no values, which is never the case for a column of a non-empty grid.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns a reference to the value at the given row of the column, or `None` if out of bounds.
+    pub fn get(&self, y: i64) -> Option<&T> {
+        get_index(y, self.len()).map(|y| &self.raw[y * self.width])
+    }
+
+    /// Returns a mutable reference to the value at the given row of the column, or `None` if out of bounds.
+    pub fn get_mut(&mut self, y: i64) -> Option<&mut T> {
+        get_index(y, self.len()).map(|y| &mut self.raw[y * self.width])
+    }
+
+    /// Swaps the values at two rows of the column.
+    ///
+    /// Panics if either row is out of bounds.
+    #[track_caller]
+    pub fn swap(&mut self, a: i64, b: i64) {
+        let len = self.len();
+        match (get_index(a, len), get_index(b, len)) {
+            (Some(a), Some(b)) => self.raw.swap(a * self.width, b * self.width),
+            _ => panic!(
+                "rows out of bounds: the column length is {len} but the rows are {a} and {b}"
+            ),
+        }
+    }
+
+    /// Returns an iterator over the values in the column, from top to bottom.
+    #[inline]
+    pub fn iter(&self) -> StepBy<Iter<'_, T>> {
+        self.raw.iter().step_by(self.width)
+    }
+
+    /// Returns a mutable iterator over the values in the column, from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::new(4, 3, 1);
+    ///
+    /// for value in grid.col_mut(2).iter_mut() {
+    ///     *value = 7;
+    /// }
+    ///
+    /// assert_eq!(grid.col(2).copy_to_vec(), vec![7, 7, 7]);
+    /// assert_eq!(grid.col(1).copy_to_vec(), vec![1, 1, 1]);
+    /// ```
+    #[inline]
+    pub fn iter_mut(&mut self) -> StepBy<IterMut<'_, T>> {
+        self.raw.iter_mut().step_by(self.width)
+    }
+
+    /// Returns a new `Vec<T>` containing copies of the values in the column, from top to bottom.
+    pub fn copy_to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<'a, T> IntoIterator for ColumnMut<'a, T> {
+    type Item = &'a mut T;
+    type IntoIter = StepBy<IterMut<'a, T>>;
+
+    /// Returns a mutable iterator over the values in the column, from top to bottom.
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.raw.iter_mut().step_by(self.width)
+    }
+}
+
+impl<'a, T> Index<i64> for ColumnMut<'a, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, y: i64) -> &Self::Output {
+        let len = self.len();
+        let Some(value) = self.get(y) else {
+            panic!("row out of bounds: the column length is {len} but the row is {y}")
+        };
+        value
+    }
+}
+
+impl<'a, T> IndexMut<i64> for ColumnMut<'a, T> {
+    #[track_caller]
+    fn index_mut(&mut self, y: i64) -> &mut Self::Output {
+        let len = self.len();
+        let Some(value) = self.get_mut(y) else {
+            panic!("row out of bounds: the column length is {len} but the row is {y}")
+        };
+        value
+    }
+}
+
+fn get_index(y: i64, len: i64) -> Option<usize> {
+    (0..len).contains(&y).then_some(y as usize)
+}
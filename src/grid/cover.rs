@@ -0,0 +1,86 @@
+//! Exposure and cover analysis over a `Grid`, for tactics game AI.
+
+use crate::{grid::Grid, vector::Vector};
+
+impl<T> Grid<T> {
+    /// Returns the exposure of each position: the number of the given threats with [line of sight](Grid::line_of_sight) to it,
+    /// where `blocks` returns `true` for values that block sight.
+    ///
+    /// Positions whose values block sight are counted like any other, as a wall can be seen but not seen through.
+    /// A threat appearing more than once is counted once for each time it appears.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let map: Grid<char> = Grid::from_nested_iter([
+    ///     ".....".chars(),
+    ///     "..#..".chars(),
+    ///     ".....".chars(),
+    /// ]);
+    ///
+    /// let exposure = map.cover_map([v(0, 1), v(4, 0)], |&c| c == '#');
+    ///
+    /// assert_eq!(exposure[v(0, 0)], 2);
+    /// assert_eq!(exposure[v(4, 2)], 2);
+    ///
+    /// // the wall hides each side from the threat on the other
+    /// assert_eq!(exposure[v(1, 1)], 1);
+    /// assert_eq!(exposure[v(3, 1)], 1);
+    ///
+    /// let empty: Grid<char> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.cover_map([v(0, 0)], |&c| c == '#').dim(), v(0, 2));
+    /// ```
+    pub fn cover_map<I, F>(&self, threats: I, mut blocks: F) -> Grid<usize>
+    where
+        I: IntoIterator<Item = Vector>,
+        F: FnMut(&T) -> bool,
+    {
+        let mut exposure = self.map(|_| 0);
+        let transparent = self.map(|value| !blocks(value));
+        for threat in threats {
+            for (pos, count) in exposure.iter_mut_positions() {
+                if transparent.line_of_sight(threat, pos, |&transparent| transparent) {
+                    *count += 1;
+                }
+            }
+        }
+        exposure
+    }
+
+    /// Returns `true` if `pos` is in cover from `threat`: if a position orthogonally next to `pos`,
+    /// on the side facing `threat`, holds a value for which `blocks` returns `true`, or `false` otherwise.
+    ///
+    /// A threat that is diagonal to `pos` can be covered from either of the two sides facing it.
+    /// Positions out of bounds give no cover.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let map: Grid<char> = Grid::from_nested_iter([
+    ///     ".....".chars(),
+    ///     ".#...".chars(),
+    ///     ".....".chars(),
+    /// ]);
+    ///
+    /// let blocks = |&c: &char| c == '#';
+    ///
+    /// assert!(map.in_cover(v(2, 1), v(0, 1), blocks));
+    /// assert!(map.in_cover(v(2, 1), v(0, 2), blocks));
+    /// assert!(!map.in_cover(v(2, 1), v(4, 0), blocks));
+    /// assert!(map.in_cover(v(1, 2), v(1, 0), blocks));
+    /// ```
+    pub fn in_cover<F>(&self, pos: Vector, threat: Vector, mut blocks: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let facing = (threat - pos).signum();
+        [facing.horizontal(), facing.vertical()]
+            .into_iter()
+            .filter(|&side| side != Vector::new(0, 0))
+            .any(|side| self.get(pos + side).is_some_and(&mut blocks))
+    }
+}
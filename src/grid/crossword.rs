@@ -0,0 +1,176 @@
+//! Slots of a crossword: the maximal horizontal and vertical runs of open positions in a `Grid`.
+
+use crate::{
+    grid::Grid,
+    vector::{
+        constants::{EAST, SOUTH},
+        Vector,
+    },
+};
+
+/// The direction a [`Slot`] runs in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    /// From left to right.
+    Across,
+    /// From top to bottom.
+    Down,
+}
+
+impl Direction {
+    /// Returns the unit vector from each position of a slot in this direction to the next.
+    #[inline]
+    pub fn step(self) -> Vector {
+        match self {
+            Self::Across => EAST,
+            Self::Down => SOUTH,
+        }
+    }
+}
+
+/// A maximal run of open positions in a single row or column, to be filled by a single word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Slot {
+    start: Vector,
+    direction: Direction,
+    len: usize,
+}
+
+impl Slot {
+    /// Returns the first position of the slot.
+    #[inline]
+    pub fn start(&self) -> Vector {
+        self.start
+    }
+
+    /// Returns the direction the slot runs in.
+    #[inline]
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// Returns the number of positions in the slot.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the slot has no positions, which is never the case.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the position of the letter at the given index in the slot, or `None` if the index is out of range.
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<Vector> {
+        (i < self.len).then(|| self.start + self.direction.step() * i as i64)
+    }
+
+    /// Returns an iterator over the positions in the slot, in order.
+    pub fn positions(&self) -> impl Iterator<Item = Vector> {
+        let (start, step) = (self.start, self.direction.step());
+        (0..self.len as i64).map(move |i| start + step * i)
+    }
+
+    /// Returns the index in this slot and the index in `other` of the position the two slots share,
+    /// or `None` if they do not cross.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mask: Grid<char> = Grid::from_nested_iter([
+    ///     "...".chars(),
+    ///     ".#.".chars(),
+    ///     "...".chars(),
+    /// ]);
+    ///
+    /// let slots = mask.slots(2, |&c| c == '#');
+    /// let (top, right) = (slots[0], slots[2]);
+    ///
+    /// assert_eq!(top.crossing(&right), Some((2, 0)));
+    /// assert_eq!(top.crossing(&slots[3]), None);
+    /// ```
+    pub fn crossing(&self, other: &Slot) -> Option<(usize, usize)> {
+        let (across, down, swap) = match (self.direction, other.direction) {
+            (Direction::Across, Direction::Down) => (self, other, false),
+            (Direction::Down, Direction::Across) => (other, self, true),
+            _ => return None,
+        };
+        let x = down.start.x - across.start.x;
+        let y = across.start.y - down.start.y;
+        if !(0..across.len as i64).contains(&x) || !(0..down.len as i64).contains(&y) {
+            return None;
+        }
+        let (i, j) = (x as usize, y as usize);
+        Some(if swap { (j, i) } else { (i, j) })
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns every slot of at least `min_len` positions, where `blocked` returns `true` for values of blocked positions,
+    /// such as the black squares of a crossword.
+    ///
+    /// The slots are ordered by the row-major order of their first positions, so numbering them by
+    /// their distinct first positions gives the usual crossword clue numbers, with across slots before down slots
+    /// that start at the same position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{crossword::Direction, prelude::*};
+    ///
+    /// let mask: Grid<bool> = Grid::from_nested_iter([
+    ///     [false, false, false, true],
+    ///     [false, true, false, false],
+    ///     [false, false, false, false],
+    /// ]);
+    ///
+    /// let slots = mask.slots(2, |&blocked| blocked);
+    /// let summary: Vec<(Vector, Direction, usize)> = slots
+    ///     .iter()
+    ///     .map(|slot| (slot.start(), slot.direction(), slot.len()))
+    ///     .collect();
+    ///
+    /// assert_eq!(summary, vec![
+    ///     (v(0, 0), Direction::Across, 3),
+    ///     (v(0, 0), Direction::Down, 3),
+    ///     (v(2, 0), Direction::Down, 3),
+    ///     (v(2, 1), Direction::Across, 2),
+    ///     (v(3, 1), Direction::Down, 2),
+    ///     (v(0, 2), Direction::Across, 4),
+    /// ]);
+    /// ```
+    pub fn slots<F>(&self, min_len: usize, mut blocked: F) -> Vec<Slot>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let open = self.map(|value| !blocked(value));
+        let mut slots = Vec::new();
+        for start in open.positions() {
+            if !open[start] {
+                continue;
+            }
+            for direction in [Direction::Across, Direction::Down] {
+                let step = direction.step();
+                if open.get(start - step) == Some(&true) {
+                    continue;
+                }
+                let mut len = 0;
+                while open.get(start + step * len as i64) == Some(&true) {
+                    len += 1;
+                }
+                if len >= min_len.max(1) {
+                    slots.push(Slot {
+                        start,
+                        direction,
+                        len,
+                    });
+                }
+            }
+        }
+        slots
+    }
+}
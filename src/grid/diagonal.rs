@@ -0,0 +1,218 @@
+//! Iterating over the diagonals of a `Grid`, in both directions.
+
+use crate::{grid::Grid, vector::Vector};
+
+use std::iter::FusedIterator;
+
+impl<T> Grid<T> {
+    /// Returns an iterator over the diagonals of the grid running down and to the right,
+    /// from the one at the bottom-left corner to the one at the top-right corner.
+    ///
+    /// Each diagonal is itself an iterator over references to its values, from top-left to bottom-right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "abc".chars(),
+    ///     "def".chars(),
+    /// ]);
+    ///
+    /// let diagonals: Vec<String> = grid.diagonals().map(|diagonal| diagonal.collect()).collect();
+    ///
+    /// assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+    ///
+    /// let starts: Vec<Vector> = grid.diagonals().map(|diagonal| diagonal.start()).collect();
+    ///
+    /// assert_eq!(starts, vec![v(0, 1), v(0, 0), v(1, 0), v(2, 0)]);
+    /// ```
+    pub fn diagonals(&self) -> Diagonals<'_, T> {
+        Diagonals {
+            grid: self,
+            step: Vector::new(1, 1),
+            index: 0,
+            len: self.diagonal_count(),
+        }
+    }
+
+    /// Returns an iterator over the diagonals of the grid running up and to the right,
+    /// from the one at the top-left corner to the one at the bottom-right corner.
+    ///
+    /// Each diagonal is itself an iterator over references to its values, from bottom-left to top-right.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "X..S".chars(),
+    ///     ".MA.".chars(),
+    ///     ".MA.".chars(),
+    ///     "X..S".chars(),
+    /// ]);
+    ///
+    /// // search for a word in all four diagonal directions
+    /// let count = grid
+    ///     .diagonals()
+    ///     .chain(grid.anti_diagonals())
+    ///     .map(|diagonal| {
+    ///         let line: String = diagonal.collect();
+    ///         line.matches("XMAS").count() + line.matches("SAMX").count()
+    ///     })
+    ///     .sum::<usize>();
+    ///
+    /// assert_eq!(count, 2);
+    ///
+    /// let lens: Vec<usize> = grid.anti_diagonals().map(|diagonal| diagonal.len()).collect();
+    ///
+    /// assert_eq!(lens, vec![1, 2, 3, 4, 3, 2, 1]);
+    /// ```
+    pub fn anti_diagonals(&self) -> Diagonals<'_, T> {
+        Diagonals {
+            grid: self,
+            step: Vector::new(1, -1),
+            index: 0,
+            len: self.diagonal_count(),
+        }
+    }
+
+    fn diagonal_count(&self) -> usize {
+        if self.raw.is_empty() {
+            0
+        } else {
+            (self.dim.x + self.dim.y - 1) as usize
+        }
+    }
+}
+
+/// An iterator over the diagonals of a `Grid` in one direction, each as a [`Diagonal`].
+///
+/// Constructed by [`Grid::diagonals`] and [`Grid::anti_diagonals`].
+pub struct Diagonals<'a, T> {
+    grid: &'a Grid<T>,
+    step: Vector,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T> Diagonals<'a, T> {
+    /// Returns the diagonal with the given index, counting from the first diagonal.
+    fn diagonal(&self, index: usize) -> Diagonal<'a, T> {
+        let (width, height) = (self.grid.dim.x, self.grid.dim.y);
+        let i = index as i64;
+        let (start, len) = if self.step.y > 0 {
+            let start = Vector::new((i - height + 1).max(0), (height - 1 - i).max(0));
+            (start, (width - start.x).min(height - start.y))
+        } else {
+            let start = Vector::new((i - height + 1).max(0), i.min(height - 1));
+            (start, (width - start.x).min(start.y + 1))
+        };
+        Diagonal {
+            grid: self.grid,
+            start,
+            step: self.step,
+            front: 0,
+            back: len as usize,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Diagonals<'a, T> {
+    type Item = Diagonal<'a, T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        self.index += 1;
+        Some(self.diagonal(self.index - 1))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Diagonals<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        self.len -= 1;
+        Some(self.diagonal(self.len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Diagonals<'a, T> {}
+
+impl<'a, T> FusedIterator for Diagonals<'a, T> {}
+
+/// An iterator over references to the values along one diagonal of a `Grid`.
+pub struct Diagonal<'a, T> {
+    grid: &'a Grid<T>,
+    start: Vector,
+    step: Vector,
+    front: usize,
+    back: usize,
+}
+
+impl<'a, T> Diagonal<'a, T> {
+    /// Returns the position of the first value of the whole diagonal, however far it has been iterated.
+    #[inline]
+    pub fn start(&self) -> Vector {
+        self.start
+    }
+
+    /// Returns the offset between consecutive positions along the diagonal, either `(1, 1)` or `(1, -1)`.
+    #[inline]
+    pub fn step(&self) -> Vector {
+        self.step
+    }
+
+    /// Returns an iterator over the positions along the diagonal that have not yet been iterated, in the same order as the values.
+    pub fn positions(&self) -> impl Iterator<Item = Vector> {
+        let (start, step) = (self.start, self.step);
+        (self.front..self.back).map(move |i| start + step * i as i64)
+    }
+}
+
+impl<'a, T> Iterator for Diagonal<'a, T> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(&self.grid[self.start + self.step * (self.front - 1) as i64])
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Diagonal<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(&self.grid[self.start + self.step * self.back as i64])
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Diagonal<'a, T> {}
+
+impl<'a, T> FusedIterator for Diagonal<'a, T> {}
@@ -0,0 +1,219 @@
+//! Explicit diffusion steps over a `Grid<f32>`, for simulating heat, scent and similar quantities.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    trace::span,
+};
+
+use std::{num::NonZeroUsize, thread};
+
+/// What lies beyond the edges of a grid during a diffusion step.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Boundary {
+    /// Nothing flows across the edges, so the total over the grid is conserved.
+    #[default]
+    Reflect,
+    /// The grid wraps around, so the left edge neighbours the right edge and the top edge neighbours the bottom edge.
+    Wrap,
+    /// Every position beyond the edges holds the given value, acting as an infinite source or sink.
+    Fixed(f32),
+}
+
+impl Grid<f32> {
+    /// Performs one explicit diffusion step, in which each value moves towards its orthogonal neighbours
+    /// by `rate * dt` times the discrete Laplacian at its position.
+    ///
+    /// The explicit scheme is only stable when `rate * dt` is at most `0.25`; larger steps oscillate and blow up,
+    /// so split them into several smaller calls instead.
+    ///
+    /// Panics if `rate * dt` is negative, NaN, or greater than `0.25`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{diffusion::Boundary, prelude::*};
+    ///
+    /// let mut scent: Grid<f32> = Grid::new(5, 5, 0.0);
+    /// scent[v(2, 2)] = 100.0;
+    ///
+    /// scent.diffuse(1.0, 0.25, Boundary::Reflect);
+    ///
+    /// assert_eq!(scent[v(2, 2)], 0.0);
+    /// assert_eq!(scent[v(2, 1)], 25.0);
+    /// assert_eq!(scent[v(1, 1)], 0.0);
+    ///
+    /// for _ in 0..100 {
+    ///     scent.diffuse(1.0, 0.2, Boundary::Reflect);
+    /// }
+    ///
+    /// assert!((scent.iter().sum::<f32>() - 100.0).abs() < 1e-3);
+    /// assert!(scent.iter().all(|&s| (s - 4.0).abs() < 0.1));
+    /// ```
+    #[track_caller]
+    pub fn diffuse(&mut self, rate: f32, dt: f32, boundary: Boundary) {
+        self.try_diffuse(rate, dt, boundary).or_panic()
+    }
+
+    /// Performs one explicit diffusion step, as in [`diffuse`](Self::diffuse),
+    /// or returns an error without changing the grid if `rate * dt` is negative, NaN, or greater than `0.25`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{diffusion::Boundary, prelude::*};
+    ///
+    /// let mut heat: Grid<f32> = Grid::from_nested_iter([[0.0, 4.0, 0.0]]);
+    ///
+    /// assert!(heat.try_diffuse(1.0, 0.5, Boundary::Reflect).is_err());
+    /// assert_eq!(heat.try_diffuse(1.0, 0.25, Boundary::Reflect), Ok(()));
+    /// assert_eq!(heat, Grid::from_nested_iter([[1.0, 2.0, 1.0]]));
+    ///
+    /// let mut empty: Grid<f32> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.try_diffuse(1.0, 0.25, Boundary::Wrap), Ok(()));
+    /// ```
+    pub fn try_diffuse(&mut self, rate: f32, dt: f32, boundary: Boundary) -> Result<(), GridError> {
+        let k = check_step(rate, dt)?;
+        if self.raw.is_empty() {
+            return Ok(());
+        }
+        span!("diffuse", cells = self.raw.len());
+        let mut out = vec![0.0; self.raw.len()];
+        diffuse_rows(self, 0, &mut out, k, boundary);
+        self.raw = out;
+        Ok(())
+    }
+
+    /// Performs the same diffusion step as [`Grid::diffuse`], splitting the rows between up to `threads` scoped threads.
+    ///
+    /// The result is identical to [`Grid::diffuse`]. Spawning threads has a cost, so this is only worthwhile for large grids.
+    ///
+    /// Panics if `rate * dt` is negative, NaN, or greater than `0.25`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{diffusion::Boundary, prelude::*};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut a: Grid<f32> = Grid::from_fn(64, 48, |pos| (pos.x * pos.y % 7) as f32);
+    /// let mut b = a.clone();
+    ///
+    /// a.diffuse(0.5, 0.4, Boundary::Wrap);
+    /// b.diffuse_parallel(0.5, 0.4, Boundary::Wrap, NonZeroUsize::new(4).unwrap());
+    ///
+    /// assert_eq!(a, b);
+    /// ```
+    #[track_caller]
+    pub fn diffuse_parallel(
+        &mut self,
+        rate: f32,
+        dt: f32,
+        boundary: Boundary,
+        threads: NonZeroUsize,
+    ) {
+        self.try_diffuse_parallel(rate, dt, boundary, threads)
+            .or_panic()
+    }
+
+    /// Performs the same diffusion step as [`Grid::diffuse_parallel`],
+    /// or returns an error without changing the grid if `rate * dt` is negative, NaN, or greater than `0.25`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{diffusion::Boundary, prelude::*};
+    /// use std::num::NonZeroUsize;
+    ///
+    /// let mut heat: Grid<f32> = Grid::new(8, 8, 1.0);
+    /// let threads = NonZeroUsize::new(2).unwrap();
+    ///
+    /// assert!(heat.try_diffuse_parallel(-1.0, 0.1, Boundary::Wrap, threads).is_err());
+    /// assert_eq!(heat.try_diffuse_parallel(1.0, 0.1, Boundary::Wrap, threads), Ok(()));
+    ///
+    /// let mut empty: Grid<f32> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.try_diffuse_parallel(1.0, 0.1, Boundary::Wrap, threads), Ok(()));
+    /// ```
+    pub fn try_diffuse_parallel(
+        &mut self,
+        rate: f32,
+        dt: f32,
+        boundary: Boundary,
+        threads: NonZeroUsize,
+    ) -> Result<(), GridError> {
+        let k = check_step(rate, dt)?;
+        if self.raw.is_empty() {
+            return Ok(());
+        }
+        span!(
+            "diffuse_parallel",
+            cells = self.raw.len(),
+            threads = threads.get()
+        );
+        let width = self.dim.x as usize;
+        let height = self.dim.y as usize;
+        let rows_per_thread = height.div_ceil(threads.get());
+        let mut out = vec![0.0; self.raw.len()];
+        let grid = &*self;
+        thread::scope(|scope| {
+            for (i, chunk) in out.chunks_mut(rows_per_thread * width).enumerate() {
+                scope.spawn(move || diffuse_rows(grid, i * rows_per_thread, chunk, k, boundary));
+            }
+        });
+        self.raw = out;
+        Ok(())
+    }
+}
+
+/// Returns `rate * dt`, or an error if it is outside the range in which the explicit scheme is stable.
+fn check_step(rate: f32, dt: f32) -> Result<f32, GridError> {
+    let k = rate * dt;
+    if !(0.0..=0.25).contains(&k) {
+        return Err(GridError::InvalidArgument(
+            "unstable diffusion step: rate * dt must be between 0 and 0.25",
+        ));
+    }
+    Ok(k)
+}
+
+/// Writes the diffused values of the rows starting at `first_row` into `out`, which holds a whole number of rows.
+fn diffuse_rows(grid: &Grid<f32>, first_row: usize, out: &mut [f32], k: f32, boundary: Boundary) {
+    let width = grid.dim.x as usize;
+    let height = grid.dim.y as usize;
+    let raw = &grid.raw;
+    let outside = |value: f32, wrapped: f32| match boundary {
+        Boundary::Reflect => value,
+        Boundary::Wrap => wrapped,
+        Boundary::Fixed(fixed) => fixed,
+    };
+    for (row, out_row) in out.chunks_mut(width).enumerate() {
+        let y = first_row + row;
+        let above = if y > 0 { y - 1 } else { height - 1 };
+        let below = if y + 1 < height { y + 1 } else { 0 };
+        let current = &raw[y * width..][..width];
+        for (x, out_value) in out_row.iter_mut().enumerate() {
+            let value = current[x];
+            let left = if x > 0 {
+                current[x - 1]
+            } else {
+                outside(value, current[width - 1])
+            };
+            let right = if x + 1 < width {
+                current[x + 1]
+            } else {
+                outside(value, current[0])
+            };
+            let up = if y > 0 {
+                raw[above * width + x]
+            } else {
+                outside(value, raw[above * width + x])
+            };
+            let down = if y + 1 < height {
+                raw[below * width + x]
+            } else {
+                outside(value, raw[below * width + x])
+            };
+            *out_value = value + k * ((left + right) + (up + down) - 4.0 * value);
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! Addressing the edges and corners between the cells of a `Grid`, for walls, fences and flows that live on the dual grid.
+//!
+//! Corners are the points where cells meet, with the corner at `(x, y)` being the top-left corner of the cell at `(x, y)`,
+//! so a grid with dimensions `(w, h)` has corners from `(0, 0)` to `(w, h)` inclusive.
+//! Edges join two neighbouring corners, and separate two neighbouring cells.
+
+use crate::{
+    grid::{iterators::Positions, Grid},
+    vector::{Axis, Vector},
+};
+
+/// An edge between two neighbouring cells, running one unit along `axis` from the corner `start`.
+///
+/// An edge along [`Axis::X`] is the top side of the cell at `start`, and an edge along [`Axis::Y`] is its left side.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{dual::EdgeId, prelude::*};
+///
+/// let edge = EdgeId::between(v(2, 3), v(2, 2)).unwrap();
+///
+/// assert_eq!(edge, EdgeId::new(v(2, 3), Axis::X));
+/// assert_eq!(edge.cells(), [v(2, 2), v(2, 3)]);
+/// assert_eq!(edge.corners().map(|corner| corner.pos()), [v(2, 3), v(3, 3)]);
+///
+/// assert_eq!(EdgeId::between(v(2, 3), v(3, 2)), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId {
+    start: Vector,
+    axis: Axis,
+}
+
+impl EdgeId {
+    /// Constructs a new `EdgeId` for the edge running one unit along `axis` from the corner `start`.
+    #[inline]
+    pub fn new(start: Vector, axis: Axis) -> Self {
+        Self { start, axis }
+    }
+
+    /// Returns the edge separating the cells at `a` and `b`, or `None` if they are not orthogonal neighbours.
+    pub fn between(a: Vector, b: Vector) -> Option<Self> {
+        let diff = b - a;
+        let axis = match (diff.x.abs(), diff.y.abs()) {
+            (1, 0) => Axis::Y,
+            (0, 1) => Axis::X,
+            _ => return None,
+        };
+        Some(Self::new(a.max(b), axis))
+    }
+
+    /// Returns the corner the edge starts at, which is its top or left end.
+    #[inline]
+    pub fn start(self) -> Vector {
+        self.start
+    }
+
+    /// Returns the axis the edge runs along.
+    #[inline]
+    pub fn axis(self) -> Axis {
+        self.axis
+    }
+
+    /// Returns the corners at either end of the edge, top or left first.
+    #[inline]
+    pub fn corners(self) -> [CornerId; 2] {
+        [
+            CornerId::new(self.start),
+            CornerId::new(self.start + self.axis.unit()),
+        ]
+    }
+
+    /// Returns the positions of the cells on either side of the edge, top or left first,
+    /// which may be out of bounds for edges on the border of a grid.
+    #[inline]
+    pub fn cells(self) -> [Vector; 2] {
+        [self.start - self.axis.other().unit(), self.start]
+    }
+}
+
+/// A corner where up to four cells meet, with the cell at `pos` below and to the right of it.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{dual::{CornerId, EdgeId}, prelude::*};
+///
+/// let corner = CornerId::new(v(1, 1));
+///
+/// assert_eq!(corner.cells(), [v(0, 0), v(1, 0), v(0, 1), v(1, 1)]);
+/// assert_eq!(corner.edges(), [
+///     EdgeId::new(v(1, 1), Axis::X),
+///     EdgeId::new(v(1, 0), Axis::Y),
+///     EdgeId::new(v(0, 1), Axis::X),
+///     EdgeId::new(v(1, 1), Axis::Y),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CornerId {
+    pos: Vector,
+}
+
+impl CornerId {
+    /// Constructs a new `CornerId` for the top-left corner of the cell at `pos`.
+    #[inline]
+    pub fn new(pos: Vector) -> Self {
+        Self { pos }
+    }
+
+    /// Returns the position of the corner, which is also the position of the cell below and to the right of it.
+    #[inline]
+    pub fn pos(self) -> Vector {
+        self.pos
+    }
+
+    /// Returns the positions of the 4 cells around the corner, in row-major order,
+    /// which may be out of bounds for corners on the border of a grid.
+    #[inline]
+    pub fn cells(self) -> [Vector; 4] {
+        [
+            self.pos - Vector::new(1, 1),
+            self.pos - Vector::new(0, 1),
+            self.pos - Vector::new(1, 0),
+            self.pos,
+        ]
+    }
+
+    /// Returns the 4 edges meeting at the corner, going right, up, left and down from it,
+    /// as in [`ORTHOGONAL`](crate::prelude::ORTHOGONAL).
+    #[inline]
+    pub fn edges(self) -> [EdgeId; 4] {
+        [
+            EdgeId::new(self.pos, Axis::X),
+            EdgeId::new(self.pos - Vector::new(0, 1), Axis::Y),
+            EdgeId::new(self.pos - Vector::new(1, 0), Axis::X),
+            EdgeId::new(self.pos, Axis::Y),
+        ]
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns an iterator over every edge of the grid, including those on its border,
+    /// in row-major order of their starting corners, with the edge along [`Axis::X`] first at each corner.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{dual::EdgeId, prelude::*};
+    ///
+    /// let grid: Grid<u8> = Grid::new(2, 1, 0);
+    ///
+    /// let edges: Vec<EdgeId> = grid.edges().collect();
+    ///
+    /// assert_eq!(edges, vec![
+    ///     EdgeId::new(v(0, 0), Axis::X),
+    ///     EdgeId::new(v(0, 0), Axis::Y),
+    ///     EdgeId::new(v(1, 0), Axis::X),
+    ///     EdgeId::new(v(1, 0), Axis::Y),
+    ///     EdgeId::new(v(2, 0), Axis::Y),
+    ///     EdgeId::new(v(0, 1), Axis::X),
+    ///     EdgeId::new(v(1, 1), Axis::X),
+    /// ]);
+    /// ```
+    pub fn edges(&self) -> impl Iterator<Item = EdgeId> {
+        let dim = self.dim;
+        self.corners().flat_map(move |corner| {
+            let pos = corner.pos;
+            [
+                (pos.x < dim.x).then(|| EdgeId::new(pos, Axis::X)),
+                (pos.y < dim.y).then(|| EdgeId::new(pos, Axis::Y)),
+            ]
+            .into_iter()
+            .flatten()
+        })
+    }
+
+    /// Returns an iterator over every edge separating two cells of the grid, leaving out those on its border,
+    /// in the order of [`edges`](Self::edges).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter(["ab".chars(), "ab".chars()]);
+    ///
+    /// // walls go between cells with different values
+    /// let walls = grid
+    ///     .interior_edges()
+    ///     .filter(|edge| {
+    ///         let [a, b] = edge.cells();
+    ///         grid[a] != grid[b]
+    ///     })
+    ///     .count();
+    ///
+    /// assert_eq!(grid.interior_edges().count(), 4);
+    /// assert_eq!(walls, 2);
+    /// ```
+    pub fn interior_edges(&self) -> impl Iterator<Item = EdgeId> {
+        let dim = self.dim;
+        self.edges().filter(move |edge| {
+            let before = edge.start - edge.axis.other().unit();
+            before.x >= 0 && before.y >= 0 && edge.start.x < dim.x && edge.start.y < dim.y
+        })
+    }
+
+    /// Returns an iterator over every corner of the grid, including those on its border, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 2, 0);
+    ///
+    /// assert_eq!(grid.corners().count(), 4 * 3);
+    /// assert_eq!(grid.corners().last().unwrap().pos(), v(3, 2));
+    /// ```
+    pub fn corners(&self) -> impl Iterator<Item = CornerId> {
+        Positions::new(Vector::new(0, 0), self.dim + Vector::new(1, 1)).map(CornerId::new)
+    }
+}
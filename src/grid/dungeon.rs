@@ -0,0 +1,293 @@
+//! Roguelike dungeon generation: rectangular rooms connected by corridors.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{pathfinding::astar, tile::MapTile, Grid},
+    rect::Rect,
+    rng::Rng,
+    vector::Vector,
+};
+
+use std::fmt;
+
+/// A tile of a generated [`Dungeon`].
+///
+/// Tiles are displayed as `#` for walls, `.` for room floors and `:` for corridors.
+/// To use a tile type of your own, [map](Grid::map) the generated grid.
+///
+/// Walls are opaque and cannot be walked on, so a generated dungeon can be used as a [`TileMap`](crate::tile::TileMap) as it is.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{dungeon::DungeonGenerator, prelude::*, tile::TileMap};
+///
+/// let dungeon = DungeonGenerator::default().generate(48, 32, 7);
+/// let start = dungeon.rooms()[0].pos;
+/// let goal = dungeon.rooms()[1].pos;
+///
+/// let map = TileMap::new(dungeon.into_tiles());
+///
+/// assert!(map.path(start, goal).is_some());
+/// assert_eq!(map.to_string().lines().next(), Some("#".repeat(48).as_str()));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Tile {
+    /// Solid rock.
+    #[default]
+    Wall,
+    /// The floor of a room.
+    Floor,
+    /// The floor of a corridor.
+    Corridor,
+}
+
+impl Tile {
+    /// Returns `true` if the tile can be walked on, or `false` if it is a wall.
+    #[inline]
+    pub fn is_passable(self) -> bool {
+        self != Self::Wall
+    }
+}
+
+impl MapTile for Tile {
+    #[inline]
+    fn walkable(&self) -> bool {
+        self.is_passable()
+    }
+
+    #[inline]
+    fn opacity(&self) -> u8 {
+        if self.is_passable() {
+            0
+        } else {
+            u8::MAX
+        }
+    }
+
+    #[inline]
+    fn glyph(&self) -> char {
+        match self {
+            Self::Wall => '#',
+            Self::Floor => '.',
+            Self::Corridor => ':',
+        }
+    }
+}
+
+impl fmt::Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.glyph())
+    }
+}
+
+/// How the rooms of a dungeon are connected to each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Corridors {
+    /// A straight corridor out of one room that turns once towards the other,
+    /// horizontal or vertical first at random.
+    #[default]
+    LShaped,
+    /// The cheapest route found by A*, where walls cost more to dig through than existing floors,
+    /// so that corridors wind around and merge into the rooms and corridors already dug.
+    AStar,
+}
+
+/// The parameters of a dungeon generator, which places non-overlapping rooms at random and connects them with corridors.
+///
+/// Each room is connected to the nearest room placed before it, so every room can be reached from every other.
+/// The outermost positions of the grid are always walls.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{dungeon::{Corridors, DungeonGenerator, Tile}, pathfinding::astar, prelude::*};
+///
+/// let mut generator = DungeonGenerator::default();
+///
+/// for corridors in [Corridors::LShaped, Corridors::AStar] {
+///     generator.corridors = corridors;
+///     let dungeon = generator.generate(48, 32, 7);
+///     let tiles = dungeon.tiles();
+///
+///     assert!(dungeon.rooms().len() > 1);
+///     assert!(dungeon.rooms()[0].positions().all(|pos| tiles[pos] == Tile::Floor));
+///     assert!(tiles.iter_positions().all(|(pos, &tile)| !tiles.is_border(pos) || tile == Tile::Wall));
+///
+///     let start = dungeon.rooms()[0].pos;
+///     for room in dungeon.rooms() {
+///         let path = astar(tiles.dim(), start, room.pos, |pos| tiles[pos].is_passable().then_some(1));
+///         assert!(path.is_some());
+///     }
+///
+///     assert_eq!(generator.generate(48, 32, 7), dungeon);
+/// }
+///
+/// // map to tiles of your own
+/// let solid: Grid<bool> = generator.generate(48, 32, 7).into_tiles().map(|&tile| tile == Tile::Wall);
+/// assert!(solid[v(0, 0)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DungeonGenerator {
+    /// The number of times to try placing a room, each with a random size and position.
+    pub room_attempts: usize,
+    /// The smallest width and height of a room.
+    pub min_room_size: Vector,
+    /// The largest width and height of a room.
+    pub max_room_size: Vector,
+    /// The least number of walls between any two rooms.
+    pub spacing: i64,
+    /// How the rooms are connected.
+    pub corridors: Corridors,
+}
+
+impl Default for DungeonGenerator {
+    fn default() -> Self {
+        Self {
+            room_attempts: 60,
+            min_room_size: Vector::new(4, 3),
+            max_room_size: Vector::new(10, 7),
+            spacing: 1,
+            corridors: Corridors::LShaped,
+        }
+    }
+}
+
+/// A dungeon made by a [`DungeonGenerator`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Dungeon {
+    tiles: Grid<Tile>,
+    rooms: Vec<Rect>,
+}
+
+impl Dungeon {
+    /// Returns a reference to the grid of tiles.
+    #[inline]
+    pub fn tiles(&self) -> &Grid<Tile> {
+        &self.tiles
+    }
+
+    /// Returns the rooms, in the order they were placed.
+    #[inline]
+    pub fn rooms(&self) -> &[Rect] {
+        &self.rooms
+    }
+
+    /// Returns the grid of tiles, consuming the dungeon.
+    #[inline]
+    pub fn into_tiles(self) -> Grid<Tile> {
+        self.tiles
+    }
+}
+
+impl DungeonGenerator {
+    /// Generates a dungeon with the given dimensions, with its rooms and corridors chosen at random from `seed`,
+    /// so the same seed and parameters always produce the same dungeon.
+    ///
+    /// Rooms that do not fit are skipped, so the dungeon may have no rooms at all if it is too small.
+    ///
+    /// Panics if the dimensions are not positive or too large, or if the room sizes are not positive
+    /// or the minimum room size is greater than the maximum.
+    #[track_caller]
+    pub fn generate(&self, width: i64, height: i64, seed: u64) -> Dungeon {
+        self.try_generate(width, height, seed).or_panic()
+    }
+
+    /// Generates a dungeon with the given dimensions, as in [`generate`](Self::generate),
+    /// or returns an error if the dimensions are not positive or too large, or if the room sizes are invalid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{dungeon::DungeonGenerator, prelude::*};
+    ///
+    /// let mut generator = DungeonGenerator::default();
+    ///
+    /// assert!(generator.try_generate(40, 30, 1).is_ok());
+    /// assert!(generator.try_generate(0, 30, 1).is_err());
+    ///
+    /// generator.min_room_size = v(0, 3);
+    /// assert!(generator.try_generate(40, 30, 1).is_err());
+    /// ```
+    pub fn try_generate(&self, width: i64, height: i64, seed: u64) -> Result<Dungeon, GridError> {
+        let (min, max) = (self.min_room_size, self.max_room_size);
+        if min.x <= 0 || min.y <= 0 || min.x > max.x || min.y > max.y {
+            return Err(GridError::InvalidArgument(
+                "room sizes must be positive, with the minimum no greater than the maximum",
+            ));
+        }
+        let mut tiles = Grid::try_new(width, height, Tile::Wall)?;
+        let mut rng = Rng::new(seed);
+        let mut rooms: Vec<Rect> = Vec::new();
+        for _ in 0..self.room_attempts {
+            let dim = Vector::new(rng.range(min.x..=max.x), rng.range(min.y..=max.y));
+            if dim.x > width - 2 || dim.y > height - 2 {
+                continue;
+            }
+            let pos = Vector::new(
+                rng.range(1..=width - 1 - dim.x),
+                rng.range(1..=height - 1 - dim.y),
+            );
+            let room = Rect::new(pos, dim);
+            let spaced = Rect::new(
+                pos - Vector::new(self.spacing, self.spacing),
+                dim + Vector::new(self.spacing, self.spacing) * 2,
+            );
+            if rooms
+                .iter()
+                .any(|other| spaced.intersection(other).is_some())
+            {
+                continue;
+            }
+            for pos in room.positions() {
+                tiles[pos] = Tile::Floor;
+            }
+            if let Some(nearest) = rooms
+                .iter()
+                .min_by_key(|other| center(other).manhattan(center(&room)))
+            {
+                self.connect(&mut tiles, center(nearest), center(&room), &mut rng);
+            }
+            rooms.push(room);
+        }
+        Ok(Dungeon { tiles, rooms })
+    }
+
+    /// Digs a corridor between `from` and `to`, leaving room floors as they are.
+    fn connect(&self, tiles: &mut Grid<Tile>, from: Vector, to: Vector, rng: &mut Rng) {
+        let route = match self.corridors {
+            Corridors::LShaped => {
+                let corner = if rng.coin() {
+                    Vector::new(to.x, from.y)
+                } else {
+                    Vector::new(from.x, to.y)
+                };
+                line(from, corner).chain(line(corner, to)).collect()
+            }
+            Corridors::AStar => astar(tiles.dim(), from, to, |pos| match tiles[pos] {
+                _ if tiles.is_border(pos) => None,
+                Tile::Wall => Some(3),
+                Tile::Floor | Tile::Corridor => Some(1),
+            })
+            .map(|path| path.into_positions())
+            .unwrap_or_default(),
+        };
+        for pos in route {
+            if tiles[pos] == Tile::Wall {
+                tiles[pos] = Tile::Corridor;
+            }
+        }
+    }
+}
+
+/// Returns the position at the middle of the room.
+fn center(room: &Rect) -> Vector {
+    room.pos + room.dim / 2
+}
+
+/// Returns the positions in a horizontal or vertical line from `from` to `to`, inclusive.
+fn line(from: Vector, to: Vector) -> impl Iterator<Item = Vector> {
+    let step = (to - from).signum();
+    let len = (to - from).abs();
+    (0..=len.x + len.y).map(move |i| from + step * i)
+}
@@ -0,0 +1,335 @@
+//! A grid that stores its values in a compact encoding, such as half-precision or fixed-point, converting on access.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+use std::{marker::PhantomData, slice::Iter};
+
+/// A lossy conversion between logical values and a more compact stored representation.
+pub trait Encoding {
+    /// The type of the values as seen by users of an [`EncodedGrid`].
+    type Value;
+    /// The type in which values are stored.
+    type Stored: Copy;
+
+    /// Converts a value into its stored representation.
+    fn encode(value: Self::Value) -> Self::Stored;
+
+    /// Converts a stored representation back into a value.
+    fn decode(stored: Self::Stored) -> Self::Value;
+}
+
+/// Stores `f32` values as IEEE 754 half-precision floats in a `u16`, rounding to the nearest representable value.
+///
+/// Values too large for half precision become infinite, and the precision is about three significant decimal digits.
+///
+/// # Examples
+///
+/// ```
+/// use grid::encoded::{Encoding, F16};
+///
+/// assert_eq!(F16::decode(F16::encode(1.5)), 1.5);
+/// assert_eq!(F16::decode(F16::encode(-0.1)), -0.099975586);
+/// assert_eq!(F16::decode(F16::encode(1e6)), f32::INFINITY);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct F16;
+
+impl Encoding for F16 {
+    type Value = f32;
+    type Stored = u16;
+
+    fn encode(value: f32) -> u16 {
+        let bits = value.to_bits();
+        let sign = ((bits >> 16) & 0x8000) as u16;
+        let exponent = ((bits >> 23) & 0xff) as i32;
+        let mantissa = bits & 0x007f_ffff;
+
+        if exponent == 0xff {
+            let nan = if mantissa != 0 { 0x0200 } else { 0 };
+            return sign | 0x7c00 | nan;
+        }
+        let exponent = exponent - 127 + 15;
+        if exponent >= 0x1f {
+            return sign | 0x7c00;
+        }
+        if exponent <= 0 {
+            if exponent < -10 {
+                return sign;
+            }
+            let shift = (14 - exponent) as u32;
+            return sign | round_shift(mantissa | 0x0080_0000, shift) as u16;
+        }
+        sign | round_shift(((exponent as u32) << 23) | mantissa, 13) as u16
+    }
+
+    fn decode(stored: u16) -> f32 {
+        let sign = ((stored & 0x8000) as u32) << 16;
+        let exponent = ((stored >> 10) & 0x1f) as u32;
+        let mantissa = (stored & 0x03ff) as u32;
+        let bits = match exponent {
+            0 => (mantissa as f32 * f32::from_bits(0x3380_0000)).to_bits(),
+            0x1f => 0x7f80_0000 | (mantissa << 13),
+            _ => ((exponent + 112) << 23) | (mantissa << 13),
+        };
+        f32::from_bits(sign | bits)
+    }
+}
+
+/// Shifts `value` right by `shift` bits, rounding to nearest with ties to even.
+fn round_shift(value: u32, shift: u32) -> u32 {
+    let truncated = value >> shift;
+    let remainder = value & ((1 << shift) - 1);
+    let halfway = 1 << (shift - 1);
+    if remainder > halfway || (remainder == halfway && truncated & 1 == 1) {
+        truncated + 1
+    } else {
+        truncated
+    }
+}
+
+/// Stores `f32` values as signed 16-bit fixed-point numbers with `FRAC` fractional bits, rounding to the nearest representable value.
+///
+/// Values outside the representable range saturate. `FRAC` must be less than 32, which is checked at compile time:
+///
+/// ```compile_fail
+/// use grid::encoded::{Encoding, Fixed16};
+///
+/// Fixed16::<32>::encode(1.0);
+/// ```
+///
+/// # Examples
+///
+/// ```
+/// use grid::encoded::{Encoding, Fixed16};
+///
+/// type Q8 = Fixed16<8>;
+///
+/// assert_eq!(Q8::encode(1.5), 384);
+/// assert_eq!(Q8::decode(Q8::encode(-2.25)), -2.25);
+/// assert_eq!(Q8::decode(Q8::encode(1000.0)), 127.99609);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Fixed16<const FRAC: u32>;
+
+impl<const FRAC: u32> Fixed16<FRAC> {
+    const SCALE: f32 = {
+        assert!(FRAC < 32, "FRAC must be less than 32");
+        (1u32 << FRAC) as f32
+    };
+}
+
+impl<const FRAC: u32> Encoding for Fixed16<FRAC> {
+    type Value = f32;
+    type Stored = i16;
+
+    fn encode(value: f32) -> i16 {
+        (value * Self::SCALE).round() as i16
+    }
+
+    fn decode(stored: i16) -> f32 {
+        stored as f32 / Self::SCALE
+    }
+}
+
+/// Stores `f32` values as signed 8-bit fixed-point numbers with `FRAC` fractional bits, rounding to the nearest representable value.
+///
+/// Values outside the representable range saturate. `FRAC` must be less than 32, which is checked at compile time.
+///
+/// # Examples
+///
+/// ```
+/// use grid::encoded::{Encoding, Fixed8};
+///
+/// type Q4 = Fixed8<4>;
+///
+/// assert_eq!(Q4::encode(1.5), 24);
+/// assert_eq!(Q4::decode(Q4::encode(0.3)), 0.3125);
+/// assert_eq!(Q4::decode(Q4::encode(-100.0)), -8.0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Fixed8<const FRAC: u32>;
+
+impl<const FRAC: u32> Fixed8<FRAC> {
+    const SCALE: f32 = {
+        assert!(FRAC < 32, "FRAC must be less than 32");
+        (1u32 << FRAC) as f32
+    };
+}
+
+impl<const FRAC: u32> Encoding for Fixed8<FRAC> {
+    type Value = f32;
+    type Stored = i8;
+
+    fn encode(value: f32) -> i8 {
+        (value * Self::SCALE).round() as i8
+    }
+
+    fn decode(stored: i8) -> f32 {
+        stored as f32 / Self::SCALE
+    }
+}
+
+/// A grid of logical values of type `E::Value`, stored compactly as values of type `E::Stored`.
+///
+/// Values are encoded when written and decoded when read, so reads return values rather than references.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{encoded::{EncodedGrid, F16}, prelude::*};
+///
+/// let heights: Grid<f32> = Grid::from_fn(8, 10, |pos| pos.x as f32 * 0.5);
+///
+/// let mut compact: EncodedGrid<F16> = EncodedGrid::from_grid(&heights);
+///
+/// assert_eq!(compact.get(v(3, 2)), Some(1.5));
+/// assert_eq!(compact.set(v(3, 2), 2.25), Some(1.5));
+/// assert_eq!(compact.get(v(3, 2)), Some(2.25));
+/// assert_eq!(compact.get(v(8, 2)), None);
+///
+/// assert_eq!(compact.to_grid()[v(7, 0)], 3.5);
+/// ```
+pub struct EncodedGrid<E: Encoding> {
+    grid: Grid<E::Stored>,
+    encoding: PhantomData<E>,
+}
+
+impl<E: Encoding> EncodedGrid<E> {
+    /// Constructs a new `EncodedGrid<E>` with the given dimensions, initialising all values to `value`.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    #[track_caller]
+    pub fn new(width: i64, height: i64, value: E::Value) -> Self {
+        Self::try_new(width, height, value).or_panic()
+    }
+
+    /// Constructs a new `EncodedGrid<E>` with the given dimensions, initialising all values to `value`,
+    /// or returns an error if the dimensions are not positive or too large.
+    pub fn try_new(width: i64, height: i64, value: E::Value) -> Result<Self, GridError> {
+        Grid::try_new(width, height, E::encode(value)).map(Self::from_stored)
+    }
+
+    /// Constructs a new `EncodedGrid<E>` by encoding every value of `grid`.
+    pub fn from_grid(grid: &Grid<E::Value>) -> Self
+    where
+        E::Value: Clone,
+    {
+        Self::from_stored(grid.map(|value| E::encode(value.clone())))
+    }
+
+    /// Constructs a new `EncodedGrid<E>` from a grid of already encoded values.
+    #[inline]
+    pub fn from_stored(grid: Grid<E::Stored>) -> Self {
+        Self {
+            grid,
+            encoding: PhantomData,
+        }
+    }
+
+    /// Returns a new grid containing every decoded value.
+    pub fn to_grid(&self) -> Grid<E::Value> {
+        self.grid.map(|&stored| E::decode(stored))
+    }
+
+    /// Returns a reference to the grid of encoded values.
+    #[inline]
+    pub fn stored(&self) -> &Grid<E::Stored> {
+        &self.grid
+    }
+
+    /// Returns the grid of encoded values, consuming `self`.
+    #[inline]
+    pub fn into_stored(self) -> Grid<E::Stored> {
+        self.grid
+    }
+
+    /// Returns the width of the grid.
+    #[inline]
+    pub fn width(&self) -> i64 {
+        self.grid.width()
+    }
+
+    /// Returns the height of the grid.
+    #[inline]
+    pub fn height(&self) -> i64 {
+        self.grid.height()
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.grid.dim()
+    }
+
+    /// Returns `true` if the given position is within the bounds of the grid, or `false` otherwise.
+    #[inline]
+    pub fn in_bounds(&self, pos: Vector) -> bool {
+        self.grid.in_bounds(pos)
+    }
+
+    /// Returns the decoded value at the given position of the grid, or `None` if out of bounds.
+    pub fn get(&self, pos: Vector) -> Option<E::Value> {
+        self.grid.get(pos).map(|&stored| E::decode(stored))
+    }
+
+    /// Encodes and sets the value at the given position of the grid.
+    ///
+    /// Returns the old decoded value at that position, or `None` if out of bounds.
+    pub fn set(&mut self, pos: Vector, value: E::Value) -> Option<E::Value> {
+        self.grid.set(pos, E::encode(value)).map(E::decode)
+    }
+
+    /// Returns an iterator over the decoded values in the grid, in row-major order.
+    pub fn iter(&self) -> EncodedIter<'_, E> {
+        EncodedIter {
+            iter: self.grid.iter(),
+            encoding: PhantomData,
+        }
+    }
+}
+
+impl<E: Encoding> Clone for EncodedGrid<E> {
+    fn clone(&self) -> Self {
+        Self::from_stored(self.grid.clone())
+    }
+}
+
+impl<E: Encoding> PartialEq for EncodedGrid<E>
+where
+    E::Stored: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.grid == other.grid
+    }
+}
+
+/// An iterator over the decoded values in an [`EncodedGrid`], in row-major order.
+pub struct EncodedIter<'a, E: Encoding> {
+    iter: Iter<'a, E::Stored>,
+    encoding: PhantomData<E>,
+}
+
+impl<'a, E: Encoding> Iterator for EncodedIter<'a, E> {
+    type Item = E::Value;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|&stored| E::decode(stored))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.iter.nth(n).map(|&stored| E::decode(stored))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, E: Encoding> ExactSizeIterator for EncodedIter<'a, E> {}
@@ -0,0 +1,122 @@
+//! An entry type for conditionally accessing a single position of a `Grid`.
+
+use crate::{grid::Grid, vector::Vector};
+
+impl<T> Grid<T> {
+    /// Returns an entry for the given position of the grid, which is either in bounds or out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(4, 3, 0);
+    ///
+    /// grid.entry(v(1, 2)).and_modify(|value| *value += 1);
+    /// grid.entry(v(5, 2)).and_modify(|value| *value += 1);
+    ///
+    /// assert_eq!(grid[v(1, 2)], 1);
+    /// assert_eq!(grid.iter().sum::<u8>(), 1);
+    /// ```
+    pub fn entry(&mut self, pos: Vector) -> Entry<'_, T> {
+        match self.get_mut(pos) {
+            Some(value) => Entry::InBounds(value),
+            None => Entry::OutOfBounds,
+        }
+    }
+}
+
+/// A single position of a `Grid`, which is either in bounds or out of bounds.
+///
+/// Constructed with [`Grid::entry`].
+///
+/// # Examples
+///
+/// ```
+/// use grid::{entry::Entry, prelude::*};
+///
+/// let mut grid: Grid<u8> = Grid::new(4, 3, 2);
+///
+/// if let Entry::InBounds(value) = grid.entry(v(3, 0)) {
+///     *value = 5;
+/// }
+///
+/// assert_eq!(grid[v(3, 0)], 5);
+/// assert!(grid.entry(v(4, 0)).is_out_of_bounds());
+/// ```
+#[derive(Debug)]
+pub enum Entry<'a, T> {
+    InBounds(&'a mut T),
+    OutOfBounds,
+}
+
+impl<'a, T> Entry<'a, T> {
+    /// Returns `true` if the entry is in bounds, or `false` otherwise.
+    #[inline]
+    pub fn is_in_bounds(&self) -> bool {
+        matches!(self, Self::InBounds(_))
+    }
+
+    /// Returns `true` if the entry is out of bounds, or `false` otherwise.
+    #[inline]
+    pub fn is_out_of_bounds(&self) -> bool {
+        matches!(self, Self::OutOfBounds)
+    }
+
+    /// Calls `f` on the value if the entry is in bounds, then returns the entry for further chaining.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(4, 3, 1);
+    ///
+    /// grid.entry(v(0, 1))
+    ///     .and_modify(|value| *value *= 3)
+    ///     .and_modify(|value| *value += 1);
+    ///
+    /// assert_eq!(grid[v(0, 1)], 4);
+    /// ```
+    pub fn and_modify<F>(self, f: F) -> Self
+    where
+        F: FnOnce(&mut T),
+    {
+        match self {
+            Self::InBounds(value) => {
+                f(value);
+                Self::InBounds(value)
+            }
+            Self::OutOfBounds => Self::OutOfBounds,
+        }
+    }
+
+    /// Replaces the value if the entry is in bounds.
+    ///
+    /// Returns the old value, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(4, 3, 1);
+    ///
+    /// assert_eq!(grid.entry(v(2, 2)).replace(6), Some(1));
+    /// assert_eq!(grid.entry(v(2, 3)).replace(6), None);
+    ///
+    /// assert_eq!(grid[v(2, 2)], 6);
+    /// ```
+    pub fn replace(self, value: T) -> Option<T> {
+        Some(std::mem::replace(self.into_mut()?, value))
+    }
+
+    /// Converts the entry into a mutable reference to the value, or `None` if out of bounds.
+    #[inline]
+    pub fn into_mut(self) -> Option<&'a mut T> {
+        match self {
+            Self::InBounds(value) => Some(value),
+            Self::OutOfBounds => None,
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Hydraulic and thermal erosion of `Grid<f32>` heightmaps.
+
+use crate::{
+    grid::Grid,
+    rng::Rng,
+    trace::span,
+    vector::{constants::ADJACENT, Vector},
+};
+
+/// The parameters of [`Grid::erode_hydraulic`], which simulates individual raindrops carving the terrain.
+///
+/// The [`Default`] values suit heightmaps with heights roughly between `0` and `1` per unit of distance.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HydraulicErosion {
+    /// The number of droplets to simulate.
+    pub droplets: usize,
+    /// The maximum number of steps each droplet takes before it evaporates.
+    pub max_lifetime: usize,
+    /// How much each droplet keeps moving in its previous direction rather than straight downhill, between `0` and `1`.
+    pub inertia: f32,
+    /// How much sediment a droplet can carry, relative to its speed, volume of water, and the slope.
+    pub capacity: f32,
+    /// The least sediment capacity a droplet has, so that it keeps eroding on gentle slopes.
+    pub min_capacity: f32,
+    /// The fraction of its free capacity that a droplet erodes at each step, between `0` and `1`.
+    pub erosion: f32,
+    /// The fraction of its excess sediment that a droplet deposits at each step, between `0` and `1`.
+    pub deposition: f32,
+    /// The fraction of its water that a droplet loses at each step, between `0` and `1`.
+    pub evaporation: f32,
+    /// How strongly the slope accelerates a droplet.
+    pub gravity: f32,
+}
+
+impl Default for HydraulicErosion {
+    fn default() -> Self {
+        Self {
+            droplets: 10_000,
+            max_lifetime: 30,
+            inertia: 0.05,
+            capacity: 4.0,
+            min_capacity: 0.01,
+            erosion: 0.3,
+            deposition: 0.3,
+            evaporation: 0.01,
+            gravity: 4.0,
+        }
+    }
+}
+
+/// The parameters of [`Grid::erode_thermal`], which simulates material crumbling down slopes that are too steep.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ThermalErosion {
+    /// The largest height difference between adjacent positions that is stable.
+    pub talus: f32,
+    /// The fraction of the excess height difference moved at each iteration, between `0` and `0.5`.
+    pub rate: f32,
+    /// The number of iterations to run.
+    pub iterations: usize,
+}
+
+impl Default for ThermalErosion {
+    fn default() -> Self {
+        Self {
+            talus: 0.01,
+            rate: 0.5,
+            iterations: 50,
+        }
+    }
+}
+
+impl Grid<f32> {
+    /// Erodes the heightmap by simulating raindrops that run downhill, picking up sediment where they speed up
+    /// and dropping it where they slow down, carving channels and filling hollows.
+    ///
+    /// Droplets start at random positions chosen from `seed`, so the same seed and parameters always produce the same terrain.
+    /// Sediment still carried by a droplet when it evaporates is deposited where it stops,
+    /// but sediment carried off the edge of the grid is lost.
+    ///
+    /// Does nothing if the grid is less than 2 positions wide or high.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{erosion::HydraulicErosion, prelude::*};
+    ///
+    /// let hill: Grid<f32> = Grid::from_fn(32, 32, |pos| {
+    ///     let d = pos - v(16, 16);
+    ///     (1.0 - (d.dot(d) as f32).sqrt() / 16.0).max(0.0) * 8.0
+    /// });
+    ///
+    /// let params = HydraulicErosion { droplets: 2_000, ..Default::default() };
+    ///
+    /// let mut a = hill.clone();
+    /// a.erode_hydraulic(&params, 7);
+    /// let mut b = hill.clone();
+    /// b.erode_hydraulic(&params, 7);
+    /// let mut c = hill.clone();
+    /// c.erode_hydraulic(&params, 8);
+    ///
+    /// assert_eq!(a, b);
+    /// assert_ne!(a, c);
+    /// assert_ne!(a, hill);
+    /// assert!(a.iter().sum::<f32>() <= hill.iter().sum::<f32>() + 1e-2);
+    /// ```
+    pub fn erode_hydraulic(&mut self, params: &HydraulicErosion, seed: u64) {
+        if self.dim.x < 2 || self.dim.y < 2 {
+            return;
+        }
+        span!("erode_hydraulic", droplets = params.droplets);
+        let mut rng = Rng::new(seed);
+        let max = ((self.dim.x - 1) as f32, (self.dim.y - 1) as f32);
+        for _ in 0..params.droplets {
+            let mut pos = (rng.unit_f32() * max.0, rng.unit_f32() * max.1);
+            let mut dir = (0.0, 0.0);
+            let mut speed = 1.0;
+            let mut water = 1.0;
+            let mut sediment = 0.0;
+            for _ in 0..params.max_lifetime {
+                let (height, gradient) = self.sample(pos);
+                dir = (
+                    dir.0 * params.inertia - gradient.0 * (1.0 - params.inertia),
+                    dir.1 * params.inertia - gradient.1 * (1.0 - params.inertia),
+                );
+                let len = (dir.0 * dir.0 + dir.1 * dir.1).sqrt();
+                if len == 0.0 {
+                    break;
+                }
+                dir = (dir.0 / len, dir.1 / len);
+                let next = (pos.0 + dir.0, pos.1 + dir.1);
+                if !(0.0..max.0).contains(&next.0) || !(0.0..max.1).contains(&next.1) {
+                    sediment = 0.0;
+                    break;
+                }
+                let delta = self.sample(next).0 - height;
+                let capacity = (-delta * speed * water * params.capacity).max(params.min_capacity);
+                if delta > 0.0 || sediment > capacity {
+                    let amount = if delta > 0.0 {
+                        delta.min(sediment)
+                    } else {
+                        (sediment - capacity) * params.deposition
+                    };
+                    sediment -= amount;
+                    self.spread(pos, amount);
+                } else {
+                    let amount = ((capacity - sediment) * params.erosion).min(-delta);
+                    sediment += amount;
+                    self.spread(pos, -amount);
+                }
+                speed = (speed * speed - delta * params.gravity).max(0.0).sqrt();
+                water *= 1.0 - params.evaporation;
+                pos = next;
+            }
+            self.spread(pos, sediment);
+        }
+    }
+
+    /// Erodes the heightmap by moving material from each position to its lower adjacent neighbours
+    /// wherever the height difference is greater than the talus, smoothing cliffs into slopes.
+    ///
+    /// The total height of the grid is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{erosion::ThermalErosion, prelude::*};
+    ///
+    /// let mut spike: Grid<f32> = Grid::new(9, 9, 0.0);
+    /// spike[v(4, 4)] = 10.0;
+    ///
+    /// spike.erode_thermal(&ThermalErosion { talus: 0.5, rate: 0.5, iterations: 200 });
+    ///
+    /// assert!((spike.iter().sum::<f32>() - 10.0).abs() < 1e-3);
+    /// assert!(spike[v(4, 4)] < 2.0);
+    /// assert!(spike[v(4, 4)] > spike[v(4, 2)]);
+    ///
+    /// let mut empty: Grid<f32> = Grid::from_nested_iter([[], []]);
+    /// empty.erode_thermal(&ThermalErosion { talus: 0.5, rate: 0.5, iterations: 10 });
+    /// assert_eq!(empty.dim(), v(0, 2));
+    /// ```
+    pub fn erode_thermal(&mut self, params: &ThermalErosion) {
+        span!(
+            "erode_thermal",
+            cells = self.raw.len(),
+            iterations = params.iterations
+        );
+        let mut delta = self.map(|_| 0.0);
+        for _ in 0..params.iterations {
+            for (pos, &height) in self.iter_positions() {
+                let mut max_drop = 0.0f32;
+                let mut total_excess = 0.0;
+                for offset in ADJACENT {
+                    if let Some(&neighbor) = self.get(pos + offset) {
+                        let drop = height - neighbor;
+                        if drop > params.talus {
+                            max_drop = max_drop.max(drop);
+                            total_excess += drop - params.talus;
+                        }
+                    }
+                }
+                if total_excess == 0.0 {
+                    continue;
+                }
+                let moved = params.rate * (max_drop - params.talus);
+                delta[pos] -= moved;
+                for offset in ADJACENT {
+                    if let Some(&neighbor) = self.get(pos + offset) {
+                        let drop = height - neighbor;
+                        if drop > params.talus {
+                            delta[pos + offset] += moved * (drop - params.talus) / total_excess;
+                        }
+                    }
+                }
+            }
+            for (height, change) in self.raw.iter_mut().zip(&mut delta.raw) {
+                *height += *change;
+                *change = 0.0;
+            }
+        }
+    }
+
+    /// Returns the bilinearly interpolated height at a point, and the gradient of the height there.
+    fn sample(&self, (x, y): (f32, f32)) -> (f32, (f32, f32)) {
+        let cell = Vector::new(x as i64, y as i64);
+        let (u, v) = (x - cell.x as f32, y - cell.y as f32);
+        let nw = self[cell];
+        let ne = self[cell + Vector::new(1, 0)];
+        let sw = self[cell + Vector::new(0, 1)];
+        let se = self[cell + Vector::new(1, 1)];
+        let height =
+            nw * (1.0 - u) * (1.0 - v) + ne * u * (1.0 - v) + sw * (1.0 - u) * v + se * u * v;
+        let gradient = (
+            (ne - nw) * (1.0 - v) + (se - sw) * v,
+            (sw - nw) * (1.0 - u) + (se - ne) * u,
+        );
+        (height, gradient)
+    }
+
+    /// Adds `amount` to the heights around a point, split between the 4 surrounding positions by bilinear weights.
+    fn spread(&mut self, (x, y): (f32, f32), amount: f32) {
+        let cell = Vector::new(x as i64, y as i64);
+        let (u, v) = (x - cell.x as f32, y - cell.y as f32);
+        self[cell] += amount * (1.0 - u) * (1.0 - v);
+        self[cell + Vector::new(1, 0)] += amount * u * (1.0 - v);
+        self[cell + Vector::new(0, 1)] += amount * (1.0 - u) * v;
+        self[cell + Vector::new(1, 1)] += amount * u * v;
+    }
+}
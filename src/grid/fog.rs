@@ -0,0 +1,214 @@
+//! A fog of war layer over a world `Grid`, tracking which positions are currently visible and which have been explored.
+
+use crate::{grid::Grid, vector::Vector};
+
+use std::ops::{Index, IndexMut};
+
+/// What can be seen of a position under a [`FogOfWar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fog<T> {
+    /// The position has never been seen.
+    Hidden,
+    /// The position has been seen before, but is not visible now.
+    Explored(T),
+    /// The position is visible now.
+    Visible(T),
+}
+
+impl<T> Fog<T> {
+    /// Returns the value if the position has been seen, or `None` if it is hidden.
+    #[inline]
+    pub fn seen(self) -> Option<T> {
+        match self {
+            Self::Hidden => None,
+            Self::Explored(value) | Self::Visible(value) => Some(value),
+        }
+    }
+}
+
+/// A world grid paired with the visibility of each of its positions.
+///
+/// A position is visible if it was revealed since the visible positions were last hidden,
+/// and explored if it has ever been revealed. The usual pattern is to hide everything at the start of each turn,
+/// then reveal whatever can be seen from where the player is now.
+///
+/// The values of the world can be changed through [`get_mut`](Self::get_mut) or by indexing,
+/// but the world cannot be resized while paired with the fog, so every position always has a visibility.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{fog::{Fog, FogOfWar}, prelude::*};
+///
+/// let world: Grid<char> = Grid::from_nested_iter([
+///     "..#".chars(),
+///     "...".chars(),
+/// ]);
+///
+/// let mut fog = FogOfWar::new(world);
+///
+/// fog.reveal([v(0, 0), v(1, 0), v(2, 0)]);
+/// fog.hide_all();
+/// fog.reveal([v(0, 1)]);
+///
+/// assert!(fog.is_visible(v(0, 1)));
+/// assert!(!fog.is_visible(v(2, 0)) && fog.is_explored(v(2, 0)));
+/// assert!(!fog.is_explored(v(2, 1)));
+///
+/// let view = fog.masked();
+///
+/// assert_eq!(view[v(0, 1)], Fog::Visible(&'.'));
+/// assert_eq!(view[v(2, 0)], Fog::Explored(&'#'));
+/// assert_eq!(view[v(2, 1)], Fog::Hidden);
+///
+/// // a door opens
+/// fog[v(2, 0)] = '.';
+/// assert_eq!(fog.get(v(2, 0)), Some(Fog::Explored(&'.')));
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct FogOfWar<T> {
+    world: Grid<T>,
+    visible: Grid<bool>,
+    explored: Grid<bool>,
+}
+
+impl<T> FogOfWar<T> {
+    /// Constructs a new `FogOfWar` over the given world, with every position hidden.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{fog::FogOfWar, prelude::*};
+    ///
+    /// let fog = FogOfWar::new(Grid::<u8>::from_nested_iter([[], []]));
+    ///
+    /// assert_eq!(fog.masked().dim(), v(0, 2));
+    /// ```
+    pub fn new(world: Grid<T>) -> Self {
+        Self {
+            visible: world.map(|_| false),
+            explored: world.map(|_| false),
+            world,
+        }
+    }
+
+    /// Returns a reference to the world grid.
+    #[inline]
+    pub fn world(&self) -> &Grid<T> {
+        &self.world
+    }
+
+    /// Returns the world grid, consuming the fog.
+    #[inline]
+    pub fn into_world(self) -> Grid<T> {
+        self.world
+    }
+
+    /// Returns the dimensions of the world grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.world.dim()
+    }
+
+    /// Makes the given positions visible and explored, ignoring any that are out of bounds.
+    pub fn reveal<I>(&mut self, positions: I)
+    where
+        I: IntoIterator<Item = Vector>,
+    {
+        for pos in positions {
+            if self.world.in_bounds(pos) {
+                self.visible[pos] = true;
+                self.explored[pos] = true;
+            }
+        }
+    }
+
+    /// Makes every position not visible, leaving explored positions explored.
+    pub fn hide_all(&mut self) {
+        for visible in self.visible.iter_mut() {
+            *visible = false;
+        }
+    }
+
+    /// Makes every position hidden and unexplored.
+    pub fn forget_all(&mut self) {
+        self.hide_all();
+        for explored in self.explored.iter_mut() {
+            *explored = false;
+        }
+    }
+
+    /// Returns `true` if the given position is visible, or `false` otherwise or if out of bounds.
+    #[inline]
+    pub fn is_visible(&self, pos: Vector) -> bool {
+        self.visible.get(pos) == Some(&true)
+    }
+
+    /// Returns `true` if the given position has been explored, or `false` otherwise or if out of bounds.
+    #[inline]
+    pub fn is_explored(&self, pos: Vector) -> bool {
+        self.explored.get(pos) == Some(&true)
+    }
+
+    /// Returns a reference to the grid of visible positions.
+    #[inline]
+    pub fn visible(&self) -> &Grid<bool> {
+        &self.visible
+    }
+
+    /// Returns a reference to the grid of explored positions.
+    #[inline]
+    pub fn explored(&self) -> &Grid<bool> {
+        &self.explored
+    }
+
+    /// Returns what can be seen of the given position, or `None` if out of bounds.
+    pub fn get(&self, pos: Vector) -> Option<Fog<&T>> {
+        self.world.get(pos).map(|value| self.fog(pos, value))
+    }
+
+    /// Returns a mutable reference to the value of the world at the given position, whether or not it can be seen,
+    /// or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, pos: Vector) -> Option<&mut T> {
+        self.world.get_mut(pos)
+    }
+
+    /// Returns a grid of what can be seen of each position, for rendering.
+    pub fn masked(&self) -> Grid<Fog<&T>> {
+        Grid {
+            raw: self
+                .world
+                .iter_positions()
+                .map(|(pos, value)| self.fog(pos, value))
+                .collect(),
+            dim: self.world.dim,
+        }
+    }
+
+    fn fog<'a>(&self, pos: Vector, value: &'a T) -> Fog<&'a T> {
+        if self.visible[pos] {
+            Fog::Visible(value)
+        } else if self.explored[pos] {
+            Fog::Explored(value)
+        } else {
+            Fog::Hidden
+        }
+    }
+}
+
+impl<T> Index<Vector> for FogOfWar<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, pos: Vector) -> &Self::Output {
+        &self.world[pos]
+    }
+}
+
+impl<T> IndexMut<Vector> for FogOfWar<T> {
+    #[track_caller]
+    fn index_mut(&mut self, pos: Vector) -> &mut Self::Output {
+        &mut self.world[pos]
+    }
+}
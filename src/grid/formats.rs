@@ -0,0 +1,7 @@
+//! Reading and writing grids in common text-based file formats.
+
+pub mod life;
+pub mod text;
+
+#[cfg(feature = "esri")]
+pub mod esri;
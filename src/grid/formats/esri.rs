@@ -0,0 +1,248 @@
+//! Reading and writing raster data in the ESRI ASCII grid (`.asc`) format.
+//!
+//! Requires the `esri` feature.
+
+use crate::{grid::Grid, vector::Vector};
+
+use std::{error::Error, fmt, str::FromStr};
+
+/// A raster in the ESRI ASCII grid format: a `Grid<f64>` of cell values along with its georeferencing header.
+///
+/// The first row of values in the file is the northernmost row of the raster, and is stored at `y = 0` in the grid.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{formats::esri::{EsriGrid, ParseEsriError}, prelude::*};
+///
+/// let input = "\
+/// ncols 3
+/// nrows 2
+/// xllcorner 100.0
+/// yllcorner 200.0
+/// cellsize 25.0
+/// NODATA_value -9999
+/// 1.5 2 -9999
+/// 4 5 6
+/// ";
+///
+/// let raster: EsriGrid = input.parse().unwrap();
+///
+/// assert_eq!(raster.grid.dim(), v(3, 2));
+/// assert_eq!(raster.grid[v(1, 0)], 2.0);
+/// assert_eq!(raster.get(v(2, 0)), None);
+/// assert_eq!(raster.get(v(2, 1)), Some(6.0));
+/// assert_eq!(raster.cell_size, 25.0);
+///
+/// assert_eq!(raster.to_string().parse::<EsriGrid>().unwrap(), raster);
+///
+/// // the header alone does not decide how much memory is used
+/// let header = "ncols 100000 nrows 100000 xllcorner 0 yllcorner 0 cellsize 1";
+/// assert_eq!(
+///     format!("{header} 1.5").parse::<EsriGrid>(),
+///     Err(ParseEsriError::WrongValueCount { expected: 10_000_000_000, found: 1 }),
+/// );
+/// assert!(format!("{header} 1.5").replace("100000", "9223372036854775807").parse::<EsriGrid>().is_err());
+///
+/// // a row of values may start with `nan` or `inf`, and a centre coordinate mixed with a corner one is moved to the corner
+/// let raster: EsriGrid = "ncols 2 nrows 1 xllcenter 5 yllcorner 0 cellsize 2 nan inf".parse().unwrap();
+/// assert!(raster.grid[v(0, 0)].is_nan() && raster.grid[v(1, 0)] == f64::INFINITY);
+/// assert_eq!((raster.x_lower_left, raster.y_lower_left, raster.centered), (4.0, 0.0, false));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct EsriGrid {
+    /// The cell values, including any nodata values.
+    pub grid: Grid<f64>,
+    /// The x coordinate of the lower-left corner (or centre, if `centered`) of the raster.
+    pub x_lower_left: f64,
+    /// The y coordinate of the lower-left corner (or centre, if `centered`) of the raster.
+    pub y_lower_left: f64,
+    /// Whether the lower-left coordinates refer to the centre of the lower-left cell rather than its corner.
+    ///
+    /// When parsing, this is only `true` if both coordinates are given for the centre;
+    /// if only one is, it is converted to the corner by subtracting half of the cell size.
+    pub centered: bool,
+    /// The side length of each cell.
+    pub cell_size: f64,
+    /// The value used to mark cells with no data, if any.
+    pub nodata: Option<f64>,
+}
+
+impl EsriGrid {
+    /// Returns `true` if the value at the given position is the nodata value, or `false` otherwise.
+    ///
+    /// Panics if the position is out of bounds.
+    #[track_caller]
+    pub fn is_nodata(&self, pos: Vector) -> bool {
+        Some(self.grid[pos]) == self.nodata
+    }
+
+    /// Returns the value at the given position, or `None` if it is out of bounds or the nodata value.
+    pub fn get(&self, pos: Vector) -> Option<f64> {
+        let value = *self.grid.get(pos)?;
+        (Some(value) != self.nodata).then_some(value)
+    }
+
+    /// Returns a grid containing the value of each cell, or `None` for cells with the nodata value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{formats::esri::EsriGrid, prelude::*};
+    ///
+    /// let input = "ncols 2\nnrows 1\nxllcenter 0\nyllcenter 0\ncellsize 1\nnodata_value -1\n-1 3\n";
+    ///
+    /// let raster: EsriGrid = input.parse().unwrap();
+    /// let masked = raster.masked();
+    ///
+    /// assert_eq!(masked[v(0, 0)], None);
+    /// assert_eq!(masked[v(1, 0)], Some(3.0));
+    /// ```
+    pub fn masked(&self) -> Grid<Option<f64>> {
+        self.grid
+            .map(|&value| (Some(value) != self.nodata).then_some(value))
+    }
+}
+
+impl FromStr for EsriGrid {
+    type Err = ParseEsriError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split_whitespace().peekable();
+
+        let (mut width, mut height, mut cell_size, mut nodata) = (None, None, None, None);
+        let (mut x_lower_left, mut y_lower_left) = (None, None);
+        // only known keys start the header, so that values such as `nan` and `inf` are read as cell values
+        while let Some(key) = tokens.next_if(|token| is_header_key(token)) {
+            let key = key.to_ascii_lowercase();
+            let value = tokens
+                .next()
+                .ok_or_else(|| ParseEsriError::InvalidHeader(key.clone()))?;
+            let invalid = || ParseEsriError::InvalidHeader(key.clone());
+            let parsed = value.parse::<f64>().map_err(|_| invalid());
+            match key.as_str() {
+                "ncols" => width = Some(value.parse::<i64>().map_err(|_| invalid())?),
+                "nrows" => height = Some(value.parse::<i64>().map_err(|_| invalid())?),
+                "cellsize" => cell_size = Some(parsed?),
+                "nodata_value" => nodata = Some(parsed?),
+                "xllcorner" | "xllcenter" => x_lower_left = Some((parsed?, key == "xllcenter")),
+                "yllcorner" | "yllcenter" => y_lower_left = Some((parsed?, key == "yllcenter")),
+                _ => unreachable!("not a header key"),
+            }
+        }
+
+        let width = width.ok_or(ParseEsriError::MissingHeader("ncols"))?;
+        let height = height.ok_or(ParseEsriError::MissingHeader("nrows"))?;
+        let expected = (width > 0 && height > 0)
+            .then(|| (width as usize).checked_mul(height as usize))
+            .flatten()
+            .ok_or_else(|| ParseEsriError::InvalidHeader(String::from("ncols/nrows")))?;
+
+        // the header is not trusted to size the allocation, only the values actually read
+        let mut values = Vec::new();
+        for token in tokens {
+            values.push(
+                token
+                    .parse::<f64>()
+                    .map_err(|_| ParseEsriError::InvalidValue(token.to_string()))?,
+            );
+        }
+        if values.len() != expected {
+            return Err(ParseEsriError::WrongValueCount {
+                expected,
+                found: values.len(),
+            });
+        }
+
+        let cell_size = cell_size.ok_or(ParseEsriError::MissingHeader("cellsize"))?;
+        let (x_lower_left, x_centered) =
+            x_lower_left.ok_or(ParseEsriError::MissingHeader("xllcorner"))?;
+        let (y_lower_left, y_centered) =
+            y_lower_left.ok_or(ParseEsriError::MissingHeader("yllcorner"))?;
+        // if one coordinate is given for the centre and the other for the corner, both are converted to the corner
+        let centered = x_centered && y_centered;
+        let to_corner = |value: f64, was_centered: bool| {
+            if was_centered && !centered {
+                value - cell_size / 2.0
+            } else {
+                value
+            }
+        };
+
+        Ok(Self {
+            grid: Grid::from_iter(width, height, values),
+            x_lower_left: to_corner(x_lower_left, x_centered),
+            y_lower_left: to_corner(y_lower_left, y_centered),
+            centered,
+            cell_size,
+            nodata,
+        })
+    }
+}
+
+impl fmt::Display for EsriGrid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let suffix = if self.centered { "center" } else { "corner" };
+        writeln!(f, "ncols {}", self.grid.width())?;
+        writeln!(f, "nrows {}", self.grid.height())?;
+        writeln!(f, "xll{suffix} {}", self.x_lower_left)?;
+        writeln!(f, "yll{suffix} {}", self.y_lower_left)?;
+        writeln!(f, "cellsize {}", self.cell_size)?;
+        if let Some(nodata) = self.nodata {
+            writeln!(f, "NODATA_value {nodata}")?;
+        }
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
+                if x != 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{}", self.grid[Vector::new(x, y)])?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if `token` is one of the header keys, in any case.
+fn is_header_key(token: &str) -> bool {
+    const KEYS: [&str; 8] = [
+        "ncols",
+        "nrows",
+        "xllcorner",
+        "xllcenter",
+        "yllcorner",
+        "yllcenter",
+        "cellsize",
+        "nodata_value",
+    ];
+    KEYS.iter().any(|key| token.eq_ignore_ascii_case(key))
+}
+
+/// An error returned when parsing an ESRI ASCII grid fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseEsriError {
+    /// A required header field is missing.
+    MissingHeader(&'static str),
+    /// A header field is unrecognised or has an invalid value.
+    InvalidHeader(String),
+    /// A cell value could not be parsed as a number.
+    InvalidValue(String),
+    /// The number of cell values does not match the dimensions given in the header.
+    WrongValueCount { expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseEsriError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader(key) => write!(f, "missing header field {key}"),
+            Self::InvalidHeader(key) => write!(f, "invalid header field {key}"),
+            Self::InvalidValue(value) => write!(f, "invalid cell value {value:?}"),
+            Self::WrongValueCount { expected, found } => {
+                write!(f, "expected {expected} cell values but found {found}")
+            }
+        }
+    }
+}
+
+impl Error for ParseEsriError {}
@@ -0,0 +1,296 @@
+//! Reading and writing `Grid<bool>` in the Game of Life RLE (`.rle`) and plaintext (`.cells`) pattern formats.
+//!
+//! Live cells are `true` and dead cells are `false`.
+
+use crate::{error::GridError, grid::Grid, vector::Vector};
+
+use std::{error::Error, fmt};
+
+/// The maximum length of a line of RLE pattern data written by [`Grid::to_rle`].
+const RLE_LINE_LENGTH: usize = 70;
+
+impl Grid<bool> {
+    /// Parses a pattern in the Life RLE format.
+    ///
+    /// Comment lines starting with `#` are ignored, and the header line determines the dimensions of the grid.
+    /// Any cell state other than `b` is treated as live.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{formats::life::ParseLifeError, prelude::*};
+    ///
+    /// let glider = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbob$2bo$3o!";
+    ///
+    /// let grid = Grid::from_rle(glider).unwrap();
+    ///
+    /// assert_eq!(grid.dim(), v(3, 3));
+    /// assert_eq!(grid[v(1, 0)], true);
+    /// assert_eq!(grid[v(0, 1)], false);
+    /// assert_eq!(grid.iter().filter(|&&alive| alive).count(), 5);
+    ///
+    /// let huge = "x = 9223372036854775807, y = 9223372036854775807\n!";
+    /// assert_eq!(Grid::from_rle(huge), Err(ParseLifeError::TooLarge { line: 1 }));
+    ///
+    /// let long_run = "x = 3, y = 3\n99999999999999999999b!";
+    /// assert_eq!(Grid::from_rle(long_run), Err(ParseLifeError::OutOfBounds { line: 2 }));
+    /// ```
+    pub fn from_rle(s: &str) -> Result<Self, ParseLifeError> {
+        let mut lines = s
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+        let (line_number, header) = lines.next().ok_or(ParseLifeError::MissingHeader)?;
+        let dim =
+            parse_rle_header(header).ok_or(ParseLifeError::InvalidHeader { line: line_number })?;
+        let mut grid = Grid::try_new(dim.x, dim.y, false).map_err(|error| match error {
+            GridError::TooLarge { .. } => ParseLifeError::TooLarge { line: line_number },
+            _ => ParseLifeError::InvalidHeader { line: line_number },
+        })?;
+
+        let mut pos = Vector::new(0, 0);
+        for (line_number, line) in lines {
+            let out_of_bounds = ParseLifeError::OutOfBounds { line: line_number };
+            let mut count = None;
+            for c in line.chars() {
+                if let Some(digit) = c.to_digit(10) {
+                    count = Some(
+                        count
+                            .unwrap_or(0i64)
+                            .checked_mul(10)
+                            .and_then(|count| count.checked_add(digit as i64))
+                            .ok_or(out_of_bounds.clone())?,
+                    );
+                    continue;
+                }
+                let run = count.take().unwrap_or(1);
+                match c {
+                    '!' => return Ok(grid),
+                    '$' => {
+                        pos.x = 0;
+                        pos.y = pos.y.checked_add(run).ok_or(out_of_bounds.clone())?;
+                    }
+                    'b' | '.' => pos.x = pos.x.checked_add(run).ok_or(out_of_bounds.clone())?,
+                    c if c.is_ascii_alphabetic() => {
+                        for _ in 0..run {
+                            *grid.get_mut(pos).ok_or(out_of_bounds.clone())? = true;
+                            pos.x += 1;
+                        }
+                    }
+                    c if c.is_whitespace() => {}
+                    c => {
+                        return Err(ParseLifeError::UnexpectedChar {
+                            line: line_number,
+                            char: c,
+                        })
+                    }
+                }
+            }
+        }
+
+        Ok(grid)
+    }
+
+    /// Writes the grid as a pattern in the Life RLE format, with a header using the standard Life rule `B3/S23`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid = Grid::from_nested_iter([
+    ///     [false, true, false],
+    ///     [false, false, true],
+    ///     [true, true, true],
+    /// ]);
+    ///
+    /// assert_eq!(grid.to_rle(), "x = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n");
+    /// assert_eq!(Grid::from_rle(&grid.to_rle()).unwrap(), grid);
+    /// ```
+    pub fn to_rle(&self) -> String {
+        let mut tokens = Vec::new();
+        let mut newlines = 0;
+        for row in 0..self.height() {
+            let mut runs = Vec::new();
+            let mut x = 0;
+            while x < self.width() {
+                let alive = self[Vector::new(x, row)];
+                let start = x;
+                while x < self.width() && self[Vector::new(x, row)] == alive {
+                    x += 1;
+                }
+                runs.push((x - start, if alive { 'o' } else { 'b' }));
+            }
+            if let Some((_, 'b')) = runs.last() {
+                runs.pop();
+            }
+            if !runs.is_empty() {
+                if newlines > 0 {
+                    tokens.push(rle_token(newlines, '$'));
+                    newlines = 0;
+                }
+                tokens.extend(runs.into_iter().map(|(run, tag)| rle_token(run, tag)));
+            }
+            newlines += 1;
+        }
+        tokens.push(String::from("!"));
+
+        let mut s = format!(
+            "x = {}, y = {}, rule = B3/S23\n",
+            self.width(),
+            self.height()
+        );
+        let mut line_length = 0;
+        for token in tokens {
+            if line_length + token.len() > RLE_LINE_LENGTH {
+                s.push('\n');
+                line_length = 0;
+            }
+            line_length += token.len();
+            s.push_str(&token);
+        }
+        s.push('\n');
+        s
+    }
+
+    /// Parses a pattern in the plaintext `.cells` format.
+    ///
+    /// Comment lines starting with `!` are ignored. Live cells are `O` (or `*`) and dead cells are `.`,
+    /// and rows shorter than the longest row are padded with dead cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let blinker = "!Name: Blinker\n.O\n.O\n.O";
+    ///
+    /// let grid = Grid::from_cells(blinker).unwrap();
+    ///
+    /// assert_eq!(grid.dim(), v(2, 3));
+    /// assert_eq!(grid[v(1, 2)], true);
+    /// assert_eq!(grid[v(0, 2)], false);
+    /// ```
+    pub fn from_cells(s: &str) -> Result<Self, ParseLifeError> {
+        let mut rows = Vec::new();
+        for (i, line) in s.lines().enumerate() {
+            let line = line.trim_end();
+            if line.starts_with('!') {
+                continue;
+            }
+            let mut row = Vec::new();
+            for c in line.chars() {
+                row.push(match c {
+                    'O' | '*' => true,
+                    '.' => false,
+                    c => {
+                        return Err(ParseLifeError::UnexpectedChar {
+                            line: i + 1,
+                            char: c,
+                        })
+                    }
+                });
+            }
+            rows.push(row);
+        }
+        while rows.last().is_some_and(Vec::is_empty) {
+            rows.pop();
+        }
+
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0) as i64;
+        if width == 0 {
+            return Err(ParseLifeError::Empty);
+        }
+        Ok(Grid::from_fn(width, rows.len() as i64, |pos| {
+            rows[pos.y as usize]
+                .get(pos.x as usize)
+                .copied()
+                .unwrap_or(false)
+        }))
+    }
+
+    /// Writes the grid as a pattern in the plaintext `.cells` format, using `O` for live cells and `.` for dead cells.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid = Grid::from_nested_iter([[false, true], [true, false]]);
+    ///
+    /// assert_eq!(grid.to_cells(), ".O\nO.\n");
+    /// assert_eq!(Grid::from_cells(&grid.to_cells()).unwrap(), grid);
+    /// ```
+    pub fn to_cells(&self) -> String {
+        let mut s = String::with_capacity(self.raw.len() + self.height() as usize);
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                s.push(if self[Vector::new(x, y)] { 'O' } else { '.' });
+            }
+            s.push('\n');
+        }
+        s
+    }
+}
+
+fn parse_rle_header(header: &str) -> Option<Vector> {
+    let (mut width, mut height) = (None, None);
+    for field in header.split(',') {
+        let (key, value) = field.split_once('=')?;
+        match key.trim() {
+            "x" => width = Some(value.trim().parse().ok()?),
+            "y" => height = Some(value.trim().parse().ok()?),
+            _ => {}
+        }
+    }
+    let dim = Vector::new(width?, height?);
+    (dim.x > 0 && dim.y > 0).then_some(dim)
+}
+
+fn rle_token(run: i64, tag: char) -> String {
+    if run == 1 {
+        tag.to_string()
+    } else {
+        format!("{run}{tag}")
+    }
+}
+
+/// An error returned when parsing a Life pattern fails.
+///
+/// Line numbers start at 1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseLifeError {
+    /// The RLE pattern has no header line.
+    MissingHeader,
+    /// The RLE header line is malformed or declares non-positive dimensions.
+    InvalidHeader { line: usize },
+    /// The RLE header declares dimensions too large for the grid to be stored.
+    TooLarge { line: usize },
+    /// A live cell lies outside the dimensions declared in the RLE header, or a run is too long to be represented.
+    OutOfBounds { line: usize },
+    /// The pattern contains a character with no meaning in the format.
+    UnexpectedChar { line: usize, char: char },
+    /// The plaintext pattern contains no cells.
+    Empty,
+}
+
+impl fmt::Display for ParseLifeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingHeader => write!(f, "missing RLE header line"),
+            Self::InvalidHeader { line } => write!(f, "invalid RLE header on line {line}"),
+            Self::TooLarge { line } => write!(f, "RLE header dimensions too large on line {line}"),
+            Self::OutOfBounds { line } => {
+                write!(f, "cell outside the declared dimensions on line {line}")
+            }
+            Self::UnexpectedChar { line, char } => {
+                write!(f, "unexpected character {char:?} on line {line}")
+            }
+            Self::Empty => write!(f, "pattern contains no cells"),
+        }
+    }
+}
+
+impl Error for ParseLifeError {}
@@ -0,0 +1,216 @@
+//! Reading grids of characters from plain text, one row per line, as in most puzzle inputs.
+
+use crate::{grid::Grid, vector::Vector};
+
+use std::{error::Error, fmt};
+
+impl Grid<char> {
+    /// Parses a grid of characters from text with one row per line.
+    ///
+    /// Blank lines before the first row and after the last row are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid = Grid::parse("#..\n.#.\n..#\n").unwrap();
+    ///
+    /// assert_eq!(grid.dim(), v(3, 3));
+    /// assert_eq!(grid[v(1, 1)], '#');
+    ///
+    /// assert!(Grid::parse("#..\n.#\n").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, ParseGridError> {
+        Self::parse_with(s, Some)
+    }
+
+    /// Parses every grid of characters in text containing any number of grids separated by blank lines.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let input = "\
+    /// ooooo
+    /// .oooo
+    ///
+    /// .....
+    /// o....
+    /// o.o..
+    /// ";
+    ///
+    /// let grids = Grid::parse_many(input).unwrap();
+    ///
+    /// assert_eq!(grids.len(), 2);
+    /// assert_eq!(grids[0].dim(), v(5, 2));
+    /// assert_eq!(grids[1].dim(), v(5, 3));
+    /// ```
+    pub fn parse_many(s: &str) -> Result<Vec<Self>, ParseGridError> {
+        Self::parse_many_with(s, Some)
+    }
+}
+
+impl<T> Grid<T> {
+    /// Parses a grid from text with one row per line, converting each character with `f`,
+    /// which returns `None` for characters that are not allowed.
+    ///
+    /// Blank lines before the first row and after the last row are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{formats::text::ParseGridError, prelude::*};
+    ///
+    /// let digits = |c: char| c.to_digit(10);
+    ///
+    /// let grid = Grid::parse_with("123\n456", digits).unwrap();
+    /// assert_eq!(grid[v(2, 1)], 6);
+    ///
+    /// assert_eq!(
+    ///     Grid::parse_with("123\n4x6", digits),
+    ///     Err(ParseGridError::UnexpectedChar { line: 2, column: 2, char: 'x' }),
+    /// );
+    /// ```
+    pub fn parse_with<F>(s: &str, f: F) -> Result<Self, ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let lines: Vec<(usize, &str)> = numbered_lines(s)
+            .skip_while(|(_, line)| is_blank(line))
+            .collect();
+        let end = lines
+            .iter()
+            .rposition(|(_, line)| !is_blank(line))
+            .map_or(0, |i| i + 1);
+        parse_block(&lines[..end], f)
+    }
+
+    /// Parses every grid in text containing any number of grids separated by blank lines,
+    /// converting each character with `f`, which returns `None` for characters that are not allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{formats::text::ParseGridError, prelude::*};
+    ///
+    /// let input = "#.\n.#\n\n\n..\n##\n";
+    ///
+    /// let grids = Grid::parse_many_with(input, |c| match c {
+    ///     '#' => Some(true),
+    ///     '.' => Some(false),
+    ///     _ => None,
+    /// })
+    /// .unwrap();
+    ///
+    /// assert_eq!(grids, vec![
+    ///     Grid::from_nested_iter([[true, false], [false, true]]),
+    ///     Grid::from_nested_iter([[false, false], [true, true]]),
+    /// ]);
+    ///
+    /// assert_eq!(
+    ///     Grid::parse_many_with("#.\n\n#.\n.", |c| Some(c)),
+    ///     Err(ParseGridError::Ragged { line: 4, expected: 2, found: 1 }),
+    /// );
+    /// ```
+    pub fn parse_many_with<F>(s: &str, mut f: F) -> Result<Vec<Self>, ParseGridError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let lines: Vec<(usize, &str)> = numbered_lines(s).collect();
+        lines
+            .split(|(_, line)| is_blank(line))
+            .filter(|block| !block.is_empty())
+            .map(|block| parse_block(block, &mut f))
+            .collect()
+    }
+}
+
+/// Returns the lines of `s` along with their line numbers, starting at 1.
+fn numbered_lines(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    s.lines().enumerate().map(|(i, line)| (i + 1, line))
+}
+
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Parses a grid from consecutive numbered lines, one row per line.
+fn parse_block<T, F>(lines: &[(usize, &str)], mut f: F) -> Result<Grid<T>, ParseGridError>
+where
+    F: FnMut(char) -> Option<T>,
+{
+    let Some(&(_, first)) = lines.first() else {
+        return Err(ParseGridError::Empty);
+    };
+    let width = first.chars().count();
+    let mut raw = Vec::with_capacity(width * lines.len());
+    for &(line, s) in lines {
+        let start = raw.len();
+        for (i, char) in s.chars().enumerate() {
+            raw.push(f(char).ok_or(ParseGridError::UnexpectedChar {
+                line,
+                column: i + 1,
+                char,
+            })?);
+        }
+        let found = raw.len() - start;
+        if found != width {
+            return Err(ParseGridError::Ragged {
+                line,
+                expected: width,
+                found,
+            });
+        }
+    }
+    Ok(Grid {
+        raw,
+        dim: Vector::new(width as i64, lines.len() as i64),
+    })
+}
+
+/// An error returned when parsing a grid from plain text fails.
+///
+/// Line and column numbers start at 1, and count from the start of the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// The input contains no rows.
+    Empty,
+    /// A row has a different number of characters to the first row of its grid.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The input contains a character that is not allowed.
+    UnexpectedChar {
+        line: usize,
+        column: usize,
+        char: char,
+    },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "input contains no rows"),
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row on line {line} has {found} characters but {expected} were expected"
+            ),
+            Self::UnexpectedChar { line, column, char } => {
+                write!(
+                    f,
+                    "unexpected character {char:?} on line {line}, column {column}"
+                )
+            }
+        }
+    }
+}
+
+impl Error for ParseGridError {}
@@ -0,0 +1,92 @@
+//! Frontiers between explored and unexplored regions of a `Grid`, for exploration and fog of war reveal.
+
+use crate::{
+    grid::{set::PositionSet, Grid},
+    vector::constants::{ADJACENT, ORTHOGONAL},
+};
+
+impl<T> Grid<T> {
+    /// Returns the frontier: the positions whose values satisfy `known`, with an orthogonal neighbour whose value satisfies `unknown`.
+    ///
+    /// Typically `known` accepts explored, passable positions and `unknown` accepts unexplored ones,
+    /// so the frontier is where an explorer should head to see more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let map: Grid<char> = Grid::from_nested_iter([
+    ///     "..#??".chars(),
+    ///     "...??".chars(),
+    ///     "??.??".chars(),
+    /// ]);
+    ///
+    /// let frontier = map.frontier(|&c| c == '.', |&c| c == '?');
+    ///
+    /// assert_eq!(frontier.iter().collect::<Vec<_>>(), vec![v(0, 1), v(1, 1), v(2, 1), v(2, 2)]);
+    /// ```
+    pub fn frontier<K, U>(&self, mut known: K, mut unknown: U) -> PositionSet
+    where
+        K: FnMut(&T) -> bool,
+        U: FnMut(&T) -> bool,
+    {
+        self.iter_positions()
+            .filter(|&(pos, value)| {
+                known(value)
+                    && ORTHOGONAL
+                        .iter()
+                        .any(|&offset| self.get(pos + offset).is_some_and(&mut unknown))
+            })
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Returns the frontier, as in [`Grid::frontier`], split into clusters of positions connected to each other
+    /// through their 8 adjacent neighbours, ordered by the first position of each cluster in row-major order.
+    ///
+    /// Each cluster is a separate opening onto unexplored space, and its size is a rough measure of how much could be seen through it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let map: Grid<char> = Grid::from_nested_iter([
+    ///     "?...?".chars(),
+    ///     "#...#".chars(),
+    ///     "#...#".chars(),
+    ///     "#.?.#".chars(),
+    /// ]);
+    ///
+    /// let clusters = map.frontier_clusters(|&c| c == '.', |&c| c == '?');
+    ///
+    /// assert_eq!(clusters.len(), 3);
+    /// assert_eq!(clusters[0].iter().collect::<Vec<_>>(), vec![v(1, 0)]);
+    /// assert_eq!(clusters[1].iter().collect::<Vec<_>>(), vec![v(3, 0)]);
+    /// assert_eq!(clusters[2].iter().collect::<Vec<_>>(), vec![v(2, 2), v(1, 3), v(3, 3)]);
+    /// ```
+    pub fn frontier_clusters<K, U>(&self, known: K, unknown: U) -> Vec<PositionSet>
+    where
+        K: FnMut(&T) -> bool,
+        U: FnMut(&T) -> bool,
+    {
+        let mut remaining = self.frontier(known, unknown);
+        let mut clusters = Vec::new();
+        while let Some(start) = remaining.iter().next() {
+            remaining.remove(start);
+            let mut cluster = PositionSet::new();
+            let mut stack = vec![start];
+            while let Some(pos) = stack.pop() {
+                cluster.insert(pos);
+                for offset in ADJACENT {
+                    if remaining.remove(pos + offset) {
+                        stack.push(pos + offset);
+                    }
+                }
+            }
+            clusters.push(cluster);
+        }
+        clusters
+    }
+}
@@ -0,0 +1,273 @@
+//! Groups of orthogonally connected equal values and their liberties, kept up to date as the board changes,
+//! for Go and other capture games.
+
+use crate::{
+    grid::{set::PositionSet, Grid},
+    vector::{constants::ORTHOGONAL, Vector},
+};
+
+/// A maximal set of orthogonally connected positions holding equal values, other than the empty value.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Group {
+    stones: PositionSet,
+    liberties: PositionSet,
+}
+
+impl Group {
+    /// Returns the positions in the group.
+    #[inline]
+    pub fn stones(&self) -> &PositionSet {
+        &self.stones
+    }
+
+    /// Returns the liberties of the group: the empty positions orthogonally next to any of its positions.
+    #[inline]
+    pub fn liberties(&self) -> &PositionSet {
+        &self.liberties
+    }
+
+    /// Returns the number of positions in the group.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.stones.len()
+    }
+
+    /// Returns `true` if the group has no positions, which is never the case for a group of a [`GroupMap`].
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.stones.is_empty()
+    }
+}
+
+/// A board, together with its groups of orthogonally connected equal values and their liberties.
+///
+/// Changing a single position only revisits the groups next to it, and the group it was part of,
+/// so the groups stay cheap to keep up to date during play.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{groups::GroupMap, prelude::*};
+///
+/// let board: Grid<char> = Grid::from_nested_iter([
+///     ".xo..".chars(),
+///     "xo...".chars(),
+///     ".xo..".chars(),
+///     ".....".chars(),
+/// ]);
+///
+/// let mut groups = GroupMap::new(board, '.');
+///
+/// assert_eq!(groups.group(v(1, 1)).unwrap().liberties().len(), 1);
+/// assert_eq!(groups.group(v(1, 0)).unwrap().len(), 1);
+/// assert_eq!(groups.groups().count(), 6);
+///
+/// // x plays at the last liberty, capturing o
+/// groups.set(v(2, 1), 'x');
+/// let surrounded: Vec<Vector> = ORTHOGONAL
+///     .iter()
+///     .map(|&offset| v(2, 1) + offset)
+///     .filter(|&pos| groups.get(pos) == Some(&'o'))
+///     .filter(|&pos| groups.group(pos).unwrap().liberties().is_empty())
+///     .collect();
+///
+/// assert_eq!(surrounded, vec![v(1, 1)]);
+/// assert_eq!(groups.capture(v(1, 1)).len(), 1);
+/// assert_eq!(groups.get(v(1, 1)), Some(&'.'));
+///
+/// let liberties: Vec<Vector> = groups.group(v(2, 1)).unwrap().liberties().iter().collect();
+/// assert_eq!(liberties, vec![v(1, 1), v(3, 1)]);
+/// ```
+#[derive(Clone)]
+pub struct GroupMap<T> {
+    board: Grid<T>,
+    empty: T,
+    ids: Grid<Option<usize>>,
+    groups: Vec<Option<Group>>,
+    free: Vec<usize>,
+}
+
+impl<T: PartialEq> GroupMap<T> {
+    /// Constructs a new `GroupMap` over the given board, where positions holding `empty` are empty,
+    /// and every other value forms groups.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{groups::GroupMap, prelude::*};
+    ///
+    /// let board: Grid<char> = Grid::from_nested_iter([[], []]);
+    /// let map = GroupMap::new(board, '.');
+    ///
+    /// assert_eq!(map.groups().count(), 0);
+    /// ```
+    pub fn new(board: Grid<T>, empty: T) -> Self {
+        let mut map = Self {
+            ids: board.map(|_| None),
+            board,
+            empty,
+            groups: Vec::new(),
+            free: Vec::new(),
+        };
+        for pos in map.board.positions() {
+            if map.board[pos] != map.empty && map.ids[pos].is_none() {
+                map.flood(pos);
+            }
+        }
+        map
+    }
+
+    /// Returns a reference to the board.
+    #[inline]
+    pub fn board(&self) -> &Grid<T> {
+        &self.board
+    }
+
+    /// Returns the board, consuming the map.
+    #[inline]
+    pub fn into_board(self) -> Grid<T> {
+        self.board
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.board.get(pos)
+    }
+
+    /// Returns the group containing the given position, or `None` if it is empty or out of bounds.
+    pub fn group(&self, pos: Vector) -> Option<&Group> {
+        let id = (*self.ids.get(pos)?)?;
+        self.groups[id].as_ref()
+    }
+
+    /// Returns an iterator over every group on the board, in no particular order.
+    pub fn groups(&self) -> impl Iterator<Item = &Group> {
+        self.groups.iter().flatten()
+    }
+
+    /// Sets the value at the given position, updating the groups and liberties it affects.
+    ///
+    /// Nothing is captured: positions left without liberties keep their values until they are [captured](Self::capture).
+    ///
+    /// Returns the old value, or `None` if `pos` is out of bounds, in which case nothing changes.
+    pub fn set(&mut self, pos: Vector, value: T) -> Option<T> {
+        if *self.board.get(pos)? == value {
+            return Some(value);
+        }
+        let old = std::mem::replace(&mut self.board[pos], value);
+        if let Some(id) = self.ids[pos].take() {
+            let group = self.take(id);
+            for stone in group.stones.iter() {
+                self.ids[stone] = None;
+            }
+            for stone in group.stones.iter() {
+                if stone != pos && self.ids[stone].is_none() {
+                    self.flood(stone);
+                }
+            }
+        }
+        let empty = self.board[pos] == self.empty;
+        for offset in ORTHOGONAL {
+            if let Some(&Some(id)) = self.ids.get(pos + offset) {
+                let liberties = &mut self.groups[id].as_mut().unwrap().liberties;
+                if empty {
+                    liberties.insert(pos);
+                } else {
+                    liberties.remove(pos);
+                }
+            }
+        }
+        if !empty {
+            let mut id = self.flood(pos);
+            for offset in ORTHOGONAL {
+                if let Some(&Some(other)) = self.ids.get(pos + offset) {
+                    if other != id && self.board[pos + offset] == self.board[pos] {
+                        id = self.merge(id, other);
+                    }
+                }
+            }
+        }
+        Some(old)
+    }
+
+    /// Empties every position of the group containing the given position, giving liberties to the groups around it.
+    ///
+    /// Returns the positions that were emptied, which is an empty set if `pos` is empty or out of bounds.
+    pub fn capture(&mut self, pos: Vector) -> PositionSet
+    where
+        T: Clone,
+    {
+        let Some(&Some(id)) = self.ids.get(pos) else {
+            return PositionSet::new();
+        };
+        let group = self.take(id);
+        for stone in group.stones.iter() {
+            self.ids[stone] = None;
+            self.board[stone] = self.empty.clone();
+        }
+        for stone in group.stones.iter() {
+            for offset in ORTHOGONAL {
+                if let Some(&Some(other)) = self.ids.get(stone + offset) {
+                    self.groups[other].as_mut().unwrap().liberties.insert(stone);
+                }
+            }
+        }
+        group.stones
+    }
+
+    /// Finds the group containing `start`, which must not be empty or in a group, and gives it a new id.
+    fn flood(&mut self, start: Vector) -> usize {
+        let id = self.free.pop().unwrap_or_else(|| {
+            self.groups.push(None);
+            self.groups.len() - 1
+        });
+        let mut group = Group {
+            stones: PositionSet::new(),
+            liberties: PositionSet::new(),
+        };
+        self.ids[start] = Some(id);
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            group.stones.insert(pos);
+            for offset in ORTHOGONAL {
+                let neighbor = pos + offset;
+                let Some(value) = self.board.get(neighbor) else {
+                    continue;
+                };
+                if *value == self.empty {
+                    group.liberties.insert(neighbor);
+                } else if *value == self.board[start] && self.ids[neighbor].is_none() {
+                    self.ids[neighbor] = Some(id);
+                    stack.push(neighbor);
+                }
+            }
+        }
+        self.groups[id] = Some(group);
+        id
+    }
+
+    /// Merges two groups with the same value into whichever is larger, returning the id of the merged group.
+    fn merge(&mut self, a: usize, b: usize) -> usize {
+        let (a, b) =
+            if self.groups[a].as_ref().unwrap().len() >= self.groups[b].as_ref().unwrap().len() {
+                (a, b)
+            } else {
+                (b, a)
+            };
+        let smaller = self.take(b);
+        for stone in smaller.stones.iter() {
+            self.ids[stone] = Some(a);
+        }
+        let larger = self.groups[a].as_mut().unwrap();
+        larger.stones.extend(smaller.stones.iter());
+        larger.liberties.extend(smaller.liberties.iter());
+        a
+    }
+
+    /// Removes the group with the given id, freeing the id for reuse.
+    fn take(&mut self, id: usize) -> Group {
+        self.free.push(id);
+        self.groups[id].take().unwrap()
+    }
+}
@@ -0,0 +1,274 @@
+//! A priority queue of positions with decrease-key, backed by an index grid.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+/// The index grid value for a position that is not in the heap.
+const ABSENT: usize = usize::MAX;
+
+/// A binary min-heap of positions within the bounds of a grid, each with a priority of type `P`.
+///
+/// Each position is in the heap at most once. A companion grid records where each position is in the heap,
+/// so the priority of a position can be looked up, decreased, or removed in place,
+/// rather than pushing duplicates and skipping stale entries as with [`std::collections::BinaryHeap`].
+///
+/// # Examples
+///
+/// Dijkstra's algorithm over a grid of costs:
+///
+/// ```
+/// use grid::{heap::GridHeap, prelude::*};
+///
+/// let costs: Grid<u32> = Grid::from_nested_iter([
+///     [1, 1, 9, 1],
+///     [9, 1, 9, 1],
+///     [1, 1, 1, 1],
+/// ]);
+///
+/// let mut dist: Grid<Option<u32>> = Grid::new(costs.width(), costs.height(), None);
+/// let mut heap = GridHeap::new(costs.width(), costs.height());
+/// heap.push(v(0, 0), 0);
+///
+/// while let Some((pos, d)) = heap.pop() {
+///     dist[pos] = Some(d);
+///     for offset in ORTHOGONAL {
+///         let next = pos + offset;
+///         if costs.in_bounds(next) && dist[next].is_none() {
+///             heap.push(next, d + costs[next]);
+///         }
+///     }
+/// }
+///
+/// assert_eq!(dist[v(3, 0)], Some(7));
+/// ```
+#[derive(Clone)]
+pub struct GridHeap<P> {
+    heap: Vec<(P, Vector)>,
+    index: Grid<usize>,
+}
+
+impl<P: Ord> GridHeap<P> {
+    /// Constructs a new, empty `GridHeap<P>` for positions within a grid of the given dimensions.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    #[track_caller]
+    pub fn new(width: i64, height: i64) -> Self {
+        Self::try_new(width, height).or_panic()
+    }
+
+    /// Constructs a new, empty `GridHeap<P>` for positions within a grid of the given dimensions,
+    /// or returns an error if the dimensions are not positive or too large.
+    pub fn try_new(width: i64, height: i64) -> Result<Self, GridError> {
+        Ok(Self {
+            heap: Vec::new(),
+            index: Grid::try_new(width, height, ABSENT)?,
+        })
+    }
+
+    /// Returns the dimensions of the grid of positions the heap can hold.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.index.dim()
+    }
+
+    /// Returns the number of positions in the heap.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the heap contains no positions, or `false` otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Returns `true` if the heap contains the given position, or `false` otherwise.
+    #[inline]
+    pub fn contains(&self, pos: Vector) -> bool {
+        self.index.get(pos).is_some_and(|&i| i != ABSENT)
+    }
+
+    /// Returns a reference to the priority of the given position, or `None` if it is not in the heap.
+    pub fn priority(&self, pos: Vector) -> Option<&P> {
+        match self.index.get(pos) {
+            Some(&i) if i != ABSENT => Some(&self.heap[i].0),
+            _ => None,
+        }
+    }
+
+    /// Returns the position with the lowest priority, and a reference to its priority, or `None` if the heap is empty.
+    #[inline]
+    pub fn peek(&self) -> Option<(Vector, &P)> {
+        self.heap.first().map(|(priority, pos)| (*pos, priority))
+    }
+
+    /// Adds a position to the heap with the given priority, or lowers its priority if it is already in the heap with a higher one.
+    ///
+    /// Returns `true` if the position was added or its priority was lowered, or `false` otherwise.
+    ///
+    /// Panics if `pos` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{heap::GridHeap, prelude::*};
+    ///
+    /// let mut heap = GridHeap::new(4, 4);
+    ///
+    /// assert!(heap.push(v(1, 1), 10));
+    /// assert!(heap.push(v(2, 2), 5));
+    /// assert!(!heap.push(v(1, 1), 12));
+    /// assert!(heap.push(v(1, 1), 3));
+    ///
+    /// assert_eq!(heap.len(), 2);
+    /// assert_eq!(heap.pop(), Some((v(1, 1), 3)));
+    /// assert_eq!(heap.pop(), Some((v(2, 2), 5)));
+    /// assert_eq!(heap.pop(), None);
+    /// ```
+    #[track_caller]
+    pub fn push(&mut self, pos: Vector, priority: P) -> bool {
+        self.try_push(pos, priority).or_panic()
+    }
+
+    /// Adds a position to the heap or lowers its priority, as in [`push`](Self::push),
+    /// or returns an error if `pos` is out of bounds, in which case the heap is unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{heap::GridHeap, prelude::*};
+    ///
+    /// let mut heap = GridHeap::new(4, 4);
+    ///
+    /// assert_eq!(heap.try_push(v(1, 1), 10), Ok(true));
+    /// assert_eq!(
+    ///     heap.try_push(v(4, 1), 10),
+    ///     Err(GridError::OutOfBounds { dim: v(4, 4), region: Rect::new(v(4, 1), v(1, 1)) }),
+    /// );
+    /// assert_eq!(heap.len(), 1);
+    /// ```
+    pub fn try_push(&mut self, pos: Vector, priority: P) -> Result<bool, GridError> {
+        let i = *self
+            .index
+            .get(pos)
+            .ok_or(GridError::out_of_bounds(self.dim(), pos))?;
+        Ok(if i == ABSENT {
+            self.heap.push((priority, pos));
+            self.index[pos] = self.heap.len() - 1;
+            self.sift_up(self.heap.len() - 1);
+            true
+        } else if priority < self.heap[i].0 {
+            self.heap[i].0 = priority;
+            self.sift_up(i);
+            true
+        } else {
+            false
+        })
+    }
+
+    /// Sets the priority of a position that is already in the heap, whether higher or lower than its current priority.
+    ///
+    /// Returns the old priority, or `None` if the position is not in the heap, in which case it is not added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{heap::GridHeap, prelude::*};
+    ///
+    /// let mut heap = GridHeap::new(4, 4);
+    /// heap.push(v(0, 0), 1);
+    /// heap.push(v(3, 3), 2);
+    ///
+    /// assert_eq!(heap.change_priority(v(0, 0), 7), Some(1));
+    /// assert_eq!(heap.change_priority(v(1, 0), 7), None);
+    /// assert_eq!(heap.peek(), Some((v(3, 3), &2)));
+    /// ```
+    pub fn change_priority(&mut self, pos: Vector, priority: P) -> Option<P> {
+        let i = *self.index.get(pos).filter(|&&i| i != ABSENT)?;
+        let old = std::mem::replace(&mut self.heap[i].0, priority);
+        self.sift_up(i);
+        self.sift_down(self.index[pos]);
+        Some(old)
+    }
+
+    /// Removes and returns the position with the lowest priority, along with its priority, or `None` if the heap is empty.
+    pub fn pop(&mut self) -> Option<(Vector, P)> {
+        let (pos, _) = self.peek()?;
+        self.remove(pos).map(|priority| (pos, priority))
+    }
+
+    /// Removes a position from the heap.
+    ///
+    /// Returns its priority, or `None` if it was not in the heap.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{heap::GridHeap, prelude::*};
+    ///
+    /// let mut heap = GridHeap::new(4, 4);
+    /// heap.push(v(0, 0), 1);
+    /// heap.push(v(3, 3), 2);
+    ///
+    /// assert_eq!(heap.remove(v(0, 0)), Some(1));
+    /// assert_eq!(heap.remove(v(0, 0)), None);
+    /// assert_eq!(heap.pop(), Some((v(3, 3), 2)));
+    /// ```
+    pub fn remove(&mut self, pos: Vector) -> Option<P> {
+        let i = *self.index.get(pos).filter(|&&i| i != ABSENT)?;
+        let last = self.heap.len() - 1;
+        self.swap(i, last);
+        let (priority, _) = self.heap.pop()?;
+        self.index[pos] = ABSENT;
+        if i < last {
+            let moved = self.heap[i].1;
+            self.sift_up(i);
+            self.sift_down(self.index[moved]);
+        }
+        Some(priority)
+    }
+
+    /// Removes every position from the heap.
+    pub fn clear(&mut self) {
+        for (_, pos) in self.heap.drain(..) {
+            self.index[pos] = ABSENT;
+        }
+    }
+
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.index[self.heap[a].1] = a;
+        self.index[self.heap[b].1] = b;
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if self.heap[i].0 >= self.heap[parent].0 {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let mut min = i;
+            for child in [2 * i + 1, 2 * i + 2] {
+                if child < self.heap.len() && self.heap[child].0 < self.heap[min].0 {
+                    min = child;
+                }
+            }
+            if min == i {
+                break;
+            }
+            self.swap(i, min);
+            i = min;
+        }
+    }
+}
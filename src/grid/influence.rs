@@ -0,0 +1,266 @@
+//! Influence maps for game AI, built on a `Grid<f32>`.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    rect::Rect,
+    trace::span,
+    vector::{constants::ORTHOGONAL, Vector},
+};
+
+/// How the influence of a source falls off with the Euclidean distance from it.
+///
+/// Every falloff has a radius, and positions further than that from the source are unaffected.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Falloff {
+    /// The full strength applies everywhere within the radius.
+    Constant(f32),
+    /// The strength decreases linearly, from the full strength at the source to zero at the radius.
+    Linear(f32),
+    /// The strength is multiplied by `rate` for every unit of distance from the source, up to `radius`.
+    Exponential {
+        /// The factor by which the strength decreases per unit of distance, usually between `0` and `1`.
+        rate: f32,
+        /// The distance beyond which the strength is zero.
+        radius: f32,
+    },
+}
+
+impl Falloff {
+    /// Returns the radius beyond which the falloff is zero.
+    #[inline]
+    pub fn radius(&self) -> f32 {
+        match *self {
+            Self::Constant(radius) | Self::Linear(radius) => radius,
+            Self::Exponential { radius, .. } => radius,
+        }
+    }
+
+    /// Returns the factor applied to the strength of a source at the given distance from it.
+    ///
+    /// The factor at the source itself is always `1`, even for a falloff with a radius of `0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::influence::Falloff;
+    ///
+    /// assert_eq!(Falloff::Constant(2.0).factor(1.5), 1.0);
+    /// assert_eq!(Falloff::Linear(4.0).factor(1.0), 0.75);
+    /// assert_eq!(Falloff::Exponential { rate: 0.5, radius: 10.0 }.factor(2.0), 0.25);
+    /// assert_eq!(Falloff::Linear(4.0).factor(5.0), 0.0);
+    /// assert_eq!(Falloff::Linear(0.0).factor(0.0), 1.0);
+    /// ```
+    pub fn factor(&self, distance: f32) -> f32 {
+        if distance > self.radius() {
+            return 0.0;
+        }
+        match *self {
+            Self::Constant(_) => 1.0,
+            // at the source, so that a radius of 0 does not divide 0 by 0
+            Self::Linear(_) if distance == 0.0 => 1.0,
+            Self::Linear(radius) => 1.0 - distance / radius,
+            Self::Exponential { rate, .. } => rate.powf(distance),
+        }
+    }
+}
+
+/// A map of influence values over a grid, such as the threat posed by enemy units or the value of resources.
+///
+/// Sources add influence around their positions with a [`Falloff`],
+/// and the map can be decayed and blurred between updates so that influence lingers and spreads.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{influence::{Falloff, InfluenceMap}, prelude::*};
+///
+/// let mut threat = InfluenceMap::new(20, 20);
+///
+/// threat.add_source(v(5, 5), 10.0, Falloff::Linear(4.0));
+/// threat.add_source(v(7, 5), 10.0, Falloff::Linear(4.0));
+///
+/// assert_eq!(threat.get(v(5, 5)), Some(10.0 + 5.0));
+/// assert_eq!(threat.get(v(15, 15)), Some(0.0));
+/// assert_eq!(threat.get(v(6, 5)), Some(15.0));
+/// assert_eq!(threat.argmax(), Some((v(5, 5), 15.0)));
+///
+/// threat.decay(0.5);
+///
+/// assert_eq!(threat.get(v(5, 5)), Some(7.5));
+/// ```
+#[derive(Clone, PartialEq)]
+pub struct InfluenceMap {
+    grid: Grid<f32>,
+}
+
+impl InfluenceMap {
+    /// Constructs a new `InfluenceMap` with the given dimensions, with no influence anywhere.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    #[track_caller]
+    pub fn new(width: i64, height: i64) -> Self {
+        Self::try_new(width, height).or_panic()
+    }
+
+    /// Constructs a new `InfluenceMap` with the given dimensions, with no influence anywhere,
+    /// or returns an error if the dimensions are not positive or too large.
+    pub fn try_new(width: i64, height: i64) -> Result<Self, GridError> {
+        Grid::try_new(width, height, 0.0).map(Self::from_grid)
+    }
+
+    /// Constructs a new `InfluenceMap` with the given influence values.
+    #[inline]
+    pub fn from_grid(grid: Grid<f32>) -> Self {
+        Self { grid }
+    }
+
+    /// Returns a reference to the grid of influence values.
+    #[inline]
+    pub fn grid(&self) -> &Grid<f32> {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the grid of influence values.
+    #[inline]
+    pub fn grid_mut(&mut self) -> &mut Grid<f32> {
+        &mut self.grid
+    }
+
+    /// Returns the grid of influence values, consuming the map.
+    #[inline]
+    pub fn into_grid(self) -> Grid<f32> {
+        self.grid
+    }
+
+    /// Returns the influence at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<f32> {
+        self.grid.get(pos).copied()
+    }
+
+    /// Sets the influence everywhere to zero.
+    pub fn clear(&mut self) {
+        for value in self.grid.iter_mut() {
+            *value = 0.0;
+        }
+    }
+
+    /// Adds the influence of a source at `pos` with the given strength to every position within the radius of the falloff.
+    ///
+    /// The source itself may be out of bounds, in which case only the in bounds positions within its radius are affected.
+    /// A negative strength subtracts influence.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{influence::{Falloff, InfluenceMap}, prelude::*};
+    ///
+    /// let mut map = InfluenceMap::new(4, 4);
+    /// map.add_source(v(-1, 0), 2.0, Falloff::Linear(2.0));
+    /// map.add_source(v(1_000_000_000, 0), 1.0, Falloff::Constant(f32::INFINITY));
+    ///
+    /// assert_eq!(map.get(v(0, 0)), Some(1.0 + 1.0));
+    /// assert_eq!(map.get(v(3, 3)), Some(1.0));
+    /// ```
+    pub fn add_source(&mut self, pos: Vector, strength: f32, falloff: Falloff) {
+        // clamped to the grid, so that a huge or infinite radius cannot overflow
+        let reach = falloff.radius().max(0.0).floor() as i64;
+        let dim = self.grid.dim();
+        let min = Vector::new(
+            pos.x.saturating_sub(reach).max(0),
+            pos.y.saturating_sub(reach).max(0),
+        );
+        let max = Vector::new(
+            pos.x.saturating_add(reach).min(dim.x - 1),
+            pos.y.saturating_add(reach).min(dim.y - 1),
+        );
+        if min.x > max.x || min.y > max.y {
+            return;
+        }
+        for target in Rect::new(min, max - min + Vector::new(1, 1)).positions() {
+            let (dx, dy) = (
+                target.x as f64 - pos.x as f64,
+                target.y as f64 - pos.y as f64,
+            );
+            let distance = dx.hypot(dy) as f32;
+            self.grid[target] += strength * falloff.factor(distance);
+        }
+    }
+
+    /// Multiplies the influence everywhere by `factor`, so that old influence fades over time.
+    pub fn decay(&mut self, factor: f32) {
+        for value in self.grid.iter_mut() {
+            *value *= factor;
+        }
+    }
+
+    /// Spreads influence to neighbouring positions, by moving the influence at each position `weight` of the way
+    /// towards the average of its orthogonal neighbours that are in bounds.
+    ///
+    /// A `weight` of `0` leaves the map unchanged, and a `weight` of `1` replaces each value with the average of its neighbours.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{influence::{Falloff, InfluenceMap}, prelude::*};
+    ///
+    /// let mut map = InfluenceMap::new(5, 5);
+    /// map.add_source(v(2, 2), 8.0, Falloff::Constant(0.0));
+    ///
+    /// map.blur(0.5);
+    ///
+    /// assert_eq!(map.get(v(2, 2)), Some(4.0));
+    /// assert_eq!(map.get(v(2, 1)), Some(1.0));
+    /// assert_eq!(map.get(v(1, 1)), Some(0.0));
+    ///
+    /// let mut single = InfluenceMap::new(1, 1);
+    /// single.add_source(v(0, 0), 3.0, Falloff::Constant(0.0));
+    /// single.blur(0.5);
+    ///
+    /// assert_eq!(single.get(v(0, 0)), Some(3.0));
+    /// ```
+    pub fn blur(&mut self, weight: f32) {
+        span!("influence_blur", cells = self.grid.raw.len());
+        let grid = &self.grid;
+        self.grid = grid.pos_map(|pos, &value| {
+            let (sum, count) = ORTHOGONAL
+                .iter()
+                .filter_map(|&offset| grid.get(pos + offset))
+                .fold((0.0, 0.0), |(sum, count), &neighbor| {
+                    (sum + neighbor, count + 1.0)
+                });
+            if count == 0.0 {
+                // a 1x1 map has no neighbours to move towards
+                return value;
+            }
+            value + (sum / count - value) * weight
+        });
+    }
+
+    /// Returns the position with the highest influence, along with that influence, or `None` if every value is NaN.
+    ///
+    /// If several positions share the highest influence, the first in row-major order is returned.
+    pub fn argmax(&self) -> Option<(Vector, f32)> {
+        self.grid
+            .iter_positions()
+            .filter(|(_, value)| !value.is_nan())
+            .fold(None, |best, (pos, &value)| match best {
+                Some((_, max)) if max >= value => best,
+                _ => Some((pos, value)),
+            })
+    }
+
+    /// Returns the position with the lowest influence, along with that influence, or `None` if every value is NaN.
+    ///
+    /// If several positions share the lowest influence, the first in row-major order is returned.
+    pub fn argmin(&self) -> Option<(Vector, f32)> {
+        self.grid
+            .iter_positions()
+            .filter(|(_, value)| !value.is_nan())
+            .fold(None, |best, (pos, &value)| match best {
+                Some((_, min)) if min <= value => best,
+                _ => Some((pos, value)),
+            })
+    }
+}
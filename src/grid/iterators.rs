@@ -1,9 +1,16 @@
 //! Iterator types for iterating over a `Grid` and its positions.
 
-use crate::{grid::Grid, vector::Vector};
+use crate::{
+    grid::{row::RowsMut, Grid},
+    vector::{
+        constants::{ADJACENT, ORTHOGONAL},
+        Vector,
+    },
+};
 
 use std::{
-    iter::Zip,
+    iter::{FusedIterator, Skip, Take},
+    ops::Range,
     slice::{Iter, IterMut},
     vec::IntoIter,
 };
@@ -33,7 +40,7 @@ impl<T> Grid<T> {
     /// assert_eq!(grid.iter().sum::<u8>(), 51);
     /// ```
     #[inline(always)]
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.raw.iter()
     }
 
@@ -60,7 +67,7 @@ impl<T> Grid<T> {
     /// assert_eq!(grid[v(3, 5)], 1);
     /// ```
     #[inline(always)]
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
         self.raw.iter_mut()
     }
 
@@ -90,10 +97,7 @@ impl<T> Grid<T> {
     /// }
     /// ```
     pub fn positions(&self) -> Positions {
-        Positions {
-            pos: Vector::new(0, 0),
-            dim: self.dim,
-        }
+        Positions::new(Vector::new(0, 0), self.dim)
     }
 
     /// Returns an iterator over every position and value in the grid, in row-major order.
@@ -112,9 +116,15 @@ impl<T> Grid<T> {
     ///     assert_eq!(grid[pos], *value);
     ///     assert_eq!(*value, pos.x * 2 + pos.y);
     /// }
+    ///
+    /// // skipping is constant time
+    /// let mut iter = grid.iter_positions().skip(19);
+    ///
+    /// assert_eq!(iter.next(), Some((v(3, 2), &8)));
+    /// assert_eq!(iter.len(), 60);
     /// ```
-    pub fn iter_positions(&self) -> PositionIter<T> {
-        PositionIter::new(self.positions().zip(self.iter()))
+    pub fn iter_positions(&self) -> PositionIter<'_, T> {
+        PositionIter::new(self.positions(), self.iter())
     }
 
     /// Returns an iterator over every position and value in the grid, in row-major order.
@@ -136,8 +146,8 @@ impl<T> Grid<T> {
     /// assert_eq!(grid[v(2, 3)], 6);
     /// assert_eq!(grid[v(7, 9)], 63);
     /// ```
-    pub fn iter_mut_positions(&mut self) -> PositionIterMut<T> {
-        PositionIterMut::new(self.positions().zip(self.iter_mut()))
+    pub fn iter_mut_positions(&mut self) -> PositionIterMut<'_, T> {
+        PositionIterMut::new(self.positions(), self.iter_mut())
     }
 
     /// Returns an iterator over every position and value in the grid, in row-major order, consuming the grid.
@@ -158,7 +168,237 @@ impl<T> Grid<T> {
     /// }
     /// ```
     pub fn into_iter_positions(self) -> PositionIntoIter<T> {
-        PositionIntoIter::new(self.positions().zip(self.into_iter()))
+        PositionIntoIter::new(self.positions(), self.into_iter())
+    }
+
+    /// Returns an iterator over every position and value in the grid that is not on the border, in row-major order.
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y);
+    ///
+    /// let mut iter = grid.iter_interior();
+    ///
+    /// assert_eq!(iter.next(), Some((v(1, 1), &2)));
+    /// assert_eq!(iter.next(), Some((v(2, 1), &3)));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// for (pos, _) in grid.iter_interior() {
+    ///     assert!(!grid.is_border(pos));
+    /// }
+    /// ```
+    pub fn iter_interior(&self) -> RectIter<'_, T> {
+        RectIter::new(self, Vector::new(1, 1), self.dim - Vector::new(1, 1))
+    }
+
+    /// Returns an iterator over every position and value on the border of the grid, each exactly once,
+    /// clockwise from the top-left corner: along the top row, down the right column, back along the bottom row and up the left column.
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// let border: Vec<Vector> = grid.iter_border().map(|(pos, _)| pos).collect();
+    ///
+    /// assert_eq!(border, vec![
+    ///     v(0, 0), v(1, 0), v(2, 0),
+    ///     v(2, 1), v(2, 2),
+    ///     v(1, 2), v(0, 2),
+    ///     v(0, 1),
+    /// ]);
+    ///
+    /// for (pos, _) in grid.iter_border() {
+    ///     assert!(grid.is_border(pos));
+    /// }
+    ///
+    /// assert_eq!(Grid::new(1, 4, 0).iter_border().count(), 4);
+    /// ```
+    pub fn iter_border(&self) -> Border<'_, T> {
+        Border {
+            grid: self,
+            index: 0,
+            len: border_len(self.dim),
+        }
+    }
+
+    /// Returns an iterator over every position and value in the rectangular region with the given top-left corner and dimensions,
+    /// clipped to the bounds of the grid, in row-major order.
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// let values: Vec<i64> = grid.iter_rect(v(1, 1), v(2, 2)).map(|(_, &value)| value).collect();
+    /// assert_eq!(values, vec![5, 6, 9, 10]);
+    ///
+    /// let mut iter = grid.iter_rect(v(-1, 2), v(2, 5));
+    /// assert_eq!(iter.next(), Some((v(0, 2), &8)));
+    /// assert_eq!(iter.next(), None);
+    ///
+    /// assert_eq!(grid.iter_rect(v(4, 0), v(2, 2)).count(), 0);
+    /// ```
+    pub fn iter_rect(&self, top_left: Vector, dim: Vector) -> RectIter<'_, T> {
+        let (min, max) = self.clip(top_left, dim);
+        RectIter::new(self, min, max)
+    }
+
+    /// Returns an iterator over every position and a mutable reference to its value in the rectangular region
+    /// with the given top-left corner and dimensions, clipped to the bounds of the grid, in row-major order.
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a mutable reference to the value:
+    /// `(Vector, &mut T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(4, 3, 0);
+    ///
+    /// for (pos, value) in grid.iter_rect_mut(v(2, 1), v(5, 5)) {
+    ///     *value = pos.x as u8;
+    /// }
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[0, 0, 0, 0], [0, 0, 2, 3], [0, 0, 2, 3]]));
+    ///
+    /// let mut empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.iter_rect_mut(v(0, 0), v(2, 2)).count(), 0);
+    /// ```
+    pub fn iter_rect_mut(&mut self, top_left: Vector, dim: Vector) -> RectIterMut<'_, T> {
+        let (min, max) = self.clip(top_left, dim);
+        let (rows, columns) = if min.x < max.x && min.y < max.y {
+            ((max.y - min.y) as usize, min.x as usize..max.x as usize)
+        } else {
+            (0, 0..0)
+        };
+        RectIterMut {
+            positions: Positions::new(min, max),
+            rows: self.rows_mut().skip(min.y as usize).take(rows),
+            row: [].iter_mut(),
+            columns,
+        }
+    }
+
+    /// Returns the corners of the given rectangle clipped to the bounds of the grid.
+    fn clip(&self, top_left: Vector, dim: Vector) -> (Vector, Vector) {
+        (
+            top_left.max(Vector::new(0, 0)),
+            (top_left + dim).min(self.dim),
+        )
+    }
+
+    /// Returns an iterator over the positions and values of the 4 orthogonal neighbours of `pos` that are in bounds,
+    /// in the order of [`ORTHOGONAL`].
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// let mut iter = grid.iter_neighbors(v(0, 1));
+    ///
+    /// assert_eq!(iter.next(), Some((v(1, 1), &5)));
+    /// assert_eq!(iter.next(), Some((v(0, 0), &0)));
+    /// assert_eq!(iter.next(), Some((v(0, 2), &8)));
+    /// assert_eq!(iter.next(), None);
+    /// ```
+    pub fn iter_neighbors(&self, pos: Vector) -> Neighbors<'_, T> {
+        Neighbors::new(self, pos, &ORTHOGONAL)
+    }
+
+    /// Returns an iterator over the positions and values of the 8 adjacent neighbours of `pos` that are in bounds,
+    /// including diagonals, in the order of [`ADJACENT`].
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "*..".chars(),
+    ///     "...".chars(),
+    ///     ".**".chars(),
+    /// ]);
+    ///
+    /// let mines = grid.iter_neighbors_diag(v(1, 1)).filter(|&(_, &c)| c == '*').count();
+    /// assert_eq!(mines, 3);
+    ///
+    /// assert_eq!(grid.iter_neighbors_diag(v(0, 0)).count(), 3);
+    /// ```
+    pub fn iter_neighbors_diag(&self, pos: Vector) -> Neighbors<'_, T> {
+        Neighbors::new(self, pos, &ADJACENT)
+    }
+
+    /// Returns an iterator over the positions of the 4 orthogonal neighbours of `pos` that are in bounds,
+    /// in the order of [`ORTHOGONAL`].
+    ///
+    /// The iterator does not borrow the grid, so it can be used while changing the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(3, 2, 0);
+    ///
+    /// for neighbor in grid.orthogonal(v(0, 0)) {
+    ///     grid[neighbor] += 1;
+    /// }
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[0, 1, 0], [1, 0, 0]]));
+    /// ```
+    pub fn orthogonal(&self, pos: Vector) -> NeighborPositions {
+        NeighborPositions::new(self.dim, pos, &ORTHOGONAL)
+    }
+
+    /// Returns an iterator over the positions of the 8 adjacent neighbours of `pos` that are in bounds,
+    /// including diagonals, in the order of [`ADJACENT`].
+    ///
+    /// The iterator does not borrow the grid, so it can be used while changing the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// for pos in [v(0, 0), v(1, 1)] {
+    ///     for neighbor in grid.adjacent(pos) {
+    ///         grid[neighbor] += 1;
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2, 1], [2, 1, 1], [1, 1, 1]]));
+    /// ```
+    pub fn adjacent(&self, pos: Vector) -> NeighborPositions {
+        NeighborPositions::new(self.dim, pos, &ADJACENT)
     }
 }
 
@@ -250,6 +490,9 @@ impl<'a, T> IntoIterator for &'a mut Grid<T> {
 
 /// An iterator over every position that can be used to index into the grid, in row-major order.
 ///
+/// Positions are produced by incrementing `x` and `y` counters, so iterating never divides a flat index by the width.
+/// This also holds for the position iterators built on top of it, such as [`PositionIter`].
+///
 /// # Examples
 ///
 /// ```
@@ -276,26 +519,86 @@ impl<'a, T> IntoIterator for &'a mut Grid<T> {
 #[derive(Clone, Copy)]
 pub struct Positions {
     pos: Vector,
-    dim: Vector,
+    min_x: i64,
+    max: Vector,
+}
+
+impl Positions {
+    /// Positions from `min` (inclusive) to `max` (exclusive).
+    pub(crate) fn new(min: Vector, max: Vector) -> Self {
+        let pos = if min.x < max.x && min.y < max.y {
+            min
+        } else {
+            max
+        };
+        Self {
+            pos,
+            min_x: min.x,
+            max,
+        }
+    }
 }
 
 impl Iterator for Positions {
     type Item = Vector;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos.y != self.dim.y {
+        if self.pos.y != self.max.y {
             let pos = self.pos;
             self.pos.x += 1;
-            if self.pos.x == self.dim.x {
-                self.pos.x = 0;
+            if self.pos.x == self.max.x {
+                self.pos.x = self.min_x;
                 self.pos.y += 1;
             }
             return Some(pos);
         }
         None
     }
+
+    /// Visits the remaining positions row by row with a nested loop, which optimises better than repeated calls to `next`.
+    fn fold<B, F>(mut self, init: B, mut f: F) -> B
+    where
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        while self.pos.y != self.max.y {
+            for x in self.pos.x..self.max.x {
+                acc = f(acc, Vector::new(x, self.pos.y));
+            }
+            self.pos.x = self.min_x;
+            self.pos.y += 1;
+        }
+        acc
+    }
+
+    /// Skips `n` positions in constant time.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len() {
+            self.pos = Vector::new(self.min_x, self.max.y);
+            return None;
+        }
+        let width = self.max.x - self.min_x;
+        let offset = self.pos.x - self.min_x + n as i64;
+        self.pos = Vector::new(self.min_x + offset % width, self.pos.y + offset / width);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.pos.y == self.max.y {
+            0
+        } else {
+            ((self.max.y - self.pos.y) * (self.max.x - self.min_x) - (self.pos.x - self.min_x))
+                as usize
+        };
+        (len, Some(len))
+    }
 }
 
+impl ExactSizeIterator for Positions {}
+
+impl FusedIterator for Positions {}
+
 /// An iterator over every position and value in the grid, in row-major order.
 ///
 /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
@@ -314,13 +617,14 @@ impl Iterator for Positions {
 /// }
 /// ```
 pub struct PositionIter<'a, T> {
-    iter: Zip<Positions, Iter<'a, T>>,
+    positions: Positions,
+    iter: Iter<'a, T>,
 }
 
 impl<'a, T> PositionIter<'a, T> {
     #[inline(always)]
-    fn new(iter: Zip<Positions, Iter<'a, T>>) -> Self {
-        Self { iter }
+    fn new(positions: Positions, iter: Iter<'a, T>) -> Self {
+        Self { positions, iter }
     }
 }
 
@@ -329,10 +633,26 @@ impl<'a, T> Iterator for PositionIter<'a, T> {
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        Some((self.positions.next()?, self.iter.next()?))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let pos = self.positions.nth(n);
+        let value = self.iter.nth(n);
+        Some((pos?, value?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
+impl<'a, T> ExactSizeIterator for PositionIter<'a, T> {}
+
+impl<'a, T> FusedIterator for PositionIter<'a, T> {}
+
 /// An iterator over every position and value in the grid, in row-major order.
 ///
 /// Values from this iterator come in the form of a tuple containing the position and a mutable reference to the value:
@@ -353,13 +673,14 @@ impl<'a, T> Iterator for PositionIter<'a, T> {
 /// assert_eq!(grid[v(7, 9)], 63);
 /// ```
 pub struct PositionIterMut<'a, T> {
-    iter: Zip<Positions, IterMut<'a, T>>,
+    positions: Positions,
+    iter: IterMut<'a, T>,
 }
 
 impl<'a, T> PositionIterMut<'a, T> {
     #[inline(always)]
-    fn new(iter: Zip<Positions, IterMut<'a, T>>) -> Self {
-        Self { iter }
+    fn new(positions: Positions, iter: IterMut<'a, T>) -> Self {
+        Self { positions, iter }
     }
 }
 
@@ -368,10 +689,26 @@ impl<'a, T> Iterator for PositionIterMut<'a, T> {
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        Some((self.positions.next()?, self.iter.next()?))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let pos = self.positions.nth(n);
+        let value = self.iter.nth(n);
+        Some((pos?, value?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
     }
 }
 
+impl<'a, T> ExactSizeIterator for PositionIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for PositionIterMut<'a, T> {}
+
 /// An iterator over every position and value in the grid, in row-major order.
 ///
 /// Values from this iterator come in the form of a tuple containing the position and the value:
@@ -390,13 +727,14 @@ impl<'a, T> Iterator for PositionIterMut<'a, T> {
 /// }
 /// ```
 pub struct PositionIntoIter<T> {
-    iter: Zip<Positions, IntoIter<T>>,
+    positions: Positions,
+    iter: IntoIter<T>,
 }
 
 impl<T> PositionIntoIter<T> {
     #[inline(always)]
-    fn new(iter: Zip<Positions, IntoIter<T>>) -> Self {
-        Self { iter }
+    fn new(positions: Positions, iter: IntoIter<T>) -> Self {
+        Self { positions, iter }
     }
 }
 
@@ -405,6 +743,272 @@ impl<T> Iterator for PositionIntoIter<T> {
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next()
+        Some((self.positions.next()?, self.iter.next()?))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let pos = self.positions.nth(n);
+        let value = self.iter.nth(n);
+        Some((pos?, value?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<T> ExactSizeIterator for PositionIntoIter<T> {}
+
+impl<T> FusedIterator for PositionIntoIter<T> {}
+
+/// An iterator over every position and value in a rectangular region of the grid, in row-major order.
+///
+/// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+/// `(Vector, &T)`
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let grid: Grid<i64> = Grid::from_fn(8, 10, |pos| pos.x * 2 + pos.y);
+///
+/// for (pos, value) in grid.iter_interior() {
+///     assert_eq!(grid[pos], *value);
+///     assert_eq!(*value, pos.x * 2 + pos.y);
+/// }
+/// ```
+pub struct RectIter<'a, T> {
+    grid: &'a Grid<T>,
+    positions: Positions,
+}
+
+impl<'a, T> RectIter<'a, T> {
+    #[inline(always)]
+    fn new(grid: &'a Grid<T>, min: Vector, max: Vector) -> Self {
+        Self {
+            grid,
+            positions: Positions::new(min, max),
+        }
+    }
+}
+
+impl<'a, T> Iterator for RectIter<'a, T> {
+    type Item = (Vector, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        Some((pos, &self.grid[pos]))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let pos = self.positions.nth(n)?;
+        Some((pos, &self.grid[pos]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RectIter<'a, T> {}
+
+impl<'a, T> FusedIterator for RectIter<'a, T> {}
+
+/// An iterator over every position and value on the border of the grid, clockwise from the top-left corner.
+///
+/// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+/// `(Vector, &T)`
+///
+/// Constructed by [`Grid::iter_border`].
+pub struct Border<'a, T> {
+    grid: &'a Grid<T>,
+    index: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Border<'a, T> {
+    type Item = (Vector, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.len {
+            return None;
+        }
+        let pos = border_pos(self.grid.dim, self.index);
+        self.index += 1;
+        Some((pos, &self.grid[pos]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Border<'a, T> {}
+
+impl<'a, T> FusedIterator for Border<'a, T> {}
+
+/// Returns the number of positions on the border of a rectangle with the given dimensions.
+pub(crate) fn border_len(dim: Vector) -> usize {
+    let (width, height) = (dim.x as usize, dim.y as usize);
+    if width <= 1 || height <= 1 {
+        width * height
+    } else {
+        2 * (width + height) - 4
     }
 }
+
+/// Returns the position `index` steps clockwise around the border of a rectangle with the given dimensions,
+/// relative to its top-left corner.
+pub(crate) fn border_pos(dim: Vector, index: usize) -> Vector {
+    let (width, height) = (dim.x, dim.y);
+    let mut i = index as i64;
+    if i < width {
+        return Vector::new(i, 0);
+    }
+    i -= width;
+    if i < height - 1 {
+        return Vector::new(width - 1, i + 1);
+    }
+    i -= height - 1;
+    if i < width - 1 {
+        return Vector::new(width - 2 - i, height - 1);
+    }
+    i -= width - 1;
+    Vector::new(0, height - 2 - i)
+}
+
+/// An iterator over every position and mutable value in a rectangular region of the grid, in row-major order.
+///
+/// Values from this iterator come in the form of a tuple containing the position and a mutable reference to the value:
+/// `(Vector, &mut T)`
+///
+/// Constructed by [`Grid::iter_rect_mut`].
+pub struct RectIterMut<'a, T> {
+    positions: Positions,
+    rows: Take<Skip<RowsMut<'a, T>>>,
+    row: IterMut<'a, T>,
+    columns: Range<usize>,
+}
+
+impl<'a, T> Iterator for RectIterMut<'a, T> {
+    type Item = (Vector, &'a mut T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.row.next() {
+                return Some((self.positions.next()?, value));
+            }
+            self.row = self.rows.next()?[self.columns.clone()].iter_mut();
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RectIterMut<'a, T> {}
+
+impl<'a, T> FusedIterator for RectIterMut<'a, T> {}
+
+/// An iterator over the positions and values of the neighbours of a position that are in bounds.
+///
+/// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+/// `(Vector, &T)`
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let grid: Grid<i64> = Grid::from_fn(8, 10, |pos| pos.x * 2 + pos.y);
+///
+/// for (neighbor, value) in grid.iter_neighbors(v(3, 4)) {
+///     assert_eq!(neighbor.manhattan(v(3, 4)), 1);
+///     assert_eq!(grid[neighbor], *value);
+/// }
+/// ```
+pub struct Neighbors<'a, T> {
+    grid: &'a Grid<T>,
+    positions: NeighborPositions,
+}
+
+impl<'a, T> Neighbors<'a, T> {
+    #[inline(always)]
+    fn new(grid: &'a Grid<T>, pos: Vector, offsets: &'static [Vector]) -> Self {
+        Self {
+            grid,
+            positions: NeighborPositions::new(grid.dim, pos, offsets),
+        }
+    }
+}
+
+impl<'a, T> Iterator for Neighbors<'a, T> {
+    type Item = (Vector, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        Some((pos, &self.grid[pos]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> FusedIterator for Neighbors<'a, T> {}
+
+/// An iterator over the positions of the neighbours of a position that are in bounds.
+///
+/// The iterator only holds the dimensions of the grid, not a borrow of it.
+#[derive(Clone)]
+pub struct NeighborPositions {
+    dim: Vector,
+    pos: Vector,
+    offsets: Iter<'static, Vector>,
+}
+
+impl NeighborPositions {
+    #[inline(always)]
+    fn new(dim: Vector, pos: Vector, offsets: &'static [Vector]) -> Self {
+        Self {
+            dim,
+            pos,
+            offsets: offsets.iter(),
+        }
+    }
+}
+
+impl Iterator for NeighborPositions {
+    type Item = Vector;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets.find_map(|&offset| {
+            let neighbor = self.pos + offset;
+            let in_bounds =
+                (0..self.dim.x).contains(&neighbor.x) && (0..self.dim.y).contains(&neighbor.y);
+            in_bounds.then_some(neighbor)
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.offsets.len()))
+    }
+}
+
+impl FusedIterator for NeighborPositions {}
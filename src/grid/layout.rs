@@ -0,0 +1,184 @@
+//! Adjacency rules for tilings that are stored in a rectangular `Grid` but are not made of squares:
+//! triangles, and isometric diamonds in a staggered layout.
+//!
+//! The offsets returned here depend on the position they are taken from, so they can be passed to
+//! [`Grid::gather`](crate::prelude::Grid::gather) one position at a time.
+
+use crate::vector::Vector;
+
+/// A tiling of alternating up and down pointing triangles, stored one triangle per position.
+///
+/// The triangle at `(x, y)` points up if `x + y` is even and down otherwise,
+/// so each row alternates between the two and neighbouring rows start with opposite orientations.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{layout::Triangular, prelude::*};
+///
+/// let grid: Grid<i64> = Grid::from_fn(5, 2, |pos| pos.x + pos.y * 5);
+///
+/// assert!(Triangular::is_up(v(2, 0)));
+/// assert_eq!(grid.gather(v(2, 0), Triangular::offsets(v(2, 0))), [Some(&3), Some(&1), Some(&7)]);
+///
+/// assert!(!Triangular::is_up(v(2, 1)));
+/// assert_eq!(Triangular::neighbors(v(2, 1)), [v(3, 1), v(2, 0), v(1, 1)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Triangular;
+
+impl Triangular {
+    const UP: [Vector; 3] = [Vector::new(1, 0), Vector::new(-1, 0), Vector::new(0, 1)];
+    const DOWN: [Vector; 3] = [Vector::new(1, 0), Vector::new(0, -1), Vector::new(-1, 0)];
+
+    /// Returns `true` if the triangle at `pos` points up, or `false` if it points down.
+    #[inline]
+    pub fn is_up(pos: Vector) -> bool {
+        (pos.x + pos.y).rem_euclid(2) == 0
+    }
+
+    /// Returns the offsets from `pos` to the 3 triangles sharing an edge with it,
+    /// in the order of [`ORTHOGONAL`](crate::prelude::ORTHOGONAL) with the direction it has no neighbour in left out.
+    #[inline]
+    pub fn offsets(pos: Vector) -> &'static [Vector; 3] {
+        if Self::is_up(pos) {
+            &Self::UP
+        } else {
+            &Self::DOWN
+        }
+    }
+
+    /// Returns the positions of the 3 triangles sharing an edge with the triangle at `pos`, in the order of [`offsets`](Self::offsets).
+    #[inline]
+    pub fn neighbors(pos: Vector) -> [Vector; 3] {
+        Self::offsets(pos).map(|offset| pos + offset)
+    }
+}
+
+/// An isometric map of diamond tiles in a staggered layout, stored one tile per position,
+/// where each row is half a tile lower than the last and odd rows are shifted right by half a tile.
+///
+/// Tiles are also addressed by isometric coordinates, where the `x` axis runs down and to the right
+/// on screen and the `y` axis runs down and to the left, as in a diamond shaped map.
+/// The tile at position `(0, 0)` has isometric coordinates `(0, 0)`.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{layout::Staggered, prelude::*};
+///
+/// assert_eq!(Staggered::to_iso(v(1, 2)), v(2, 0));
+/// assert_eq!(Staggered::from_iso(v(3, 0)), v(1, 3));
+///
+/// for pos in [v(0, 0), v(3, 1), v(-2, 5)] {
+///     assert_eq!(Staggered::from_iso(Staggered::to_iso(pos)), pos);
+/// }
+///
+/// let map: Grid<char> = Grid::from_nested_iter([
+///     "....".chars(),
+///     ".#..".chars(),
+///     "....".chars(),
+/// ]);
+///
+/// let neighbors = map.gather(v(1, 2), Staggered::offsets(v(1, 2)));
+///
+/// assert_eq!(neighbors, [Some(&'#'), Some(&'.'), None, None]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Staggered;
+
+impl Staggered {
+    const EVEN: [Vector; 4] = [
+        Vector::new(0, -1),
+        Vector::new(-1, -1),
+        Vector::new(-1, 1),
+        Vector::new(0, 1),
+    ];
+    const ODD: [Vector; 4] = [
+        Vector::new(1, -1),
+        Vector::new(0, -1),
+        Vector::new(0, 1),
+        Vector::new(1, 1),
+    ];
+    const EVEN_ADJACENT: [Vector; 8] = [
+        Vector::new(1, 0),
+        Vector::new(0, -1),
+        Vector::new(0, -2),
+        Vector::new(-1, -1),
+        Vector::new(-1, 0),
+        Vector::new(-1, 1),
+        Vector::new(0, 2),
+        Vector::new(0, 1),
+    ];
+    const ODD_ADJACENT: [Vector; 8] = [
+        Vector::new(1, 0),
+        Vector::new(1, -1),
+        Vector::new(0, -2),
+        Vector::new(0, -1),
+        Vector::new(-1, 0),
+        Vector::new(0, 1),
+        Vector::new(0, 2),
+        Vector::new(1, 1),
+    ];
+
+    /// Returns the isometric coordinates of the tile stored at `pos`.
+    #[inline]
+    pub fn to_iso(pos: Vector) -> Vector {
+        let odd = pos.y.rem_euclid(2);
+        Vector::new(pos.x + (pos.y + odd) / 2, (pos.y - odd) / 2 - pos.x)
+    }
+
+    /// Returns the position the tile with the given isometric coordinates is stored at.
+    #[inline]
+    pub fn from_iso(iso: Vector) -> Vector {
+        let y = iso.x + iso.y;
+        Vector::new((iso.x - iso.y - y.rem_euclid(2)) / 2, y)
+    }
+
+    /// Returns the offsets from `pos` to the 4 tiles sharing an edge with it,
+    /// towards the top-right, top-left, bottom-left and bottom-right on screen, as in [`DIAGONAL`](crate::prelude::DIAGONAL).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{layout::Staggered, prelude::*};
+    ///
+    /// for pos in [v(2, 2), v(2, 3)] {
+    ///     let iso = Staggered::to_iso(pos);
+    ///     let neighbors = Staggered::offsets(pos).map(|offset| Staggered::to_iso(pos + offset) - iso);
+    ///     assert_eq!(neighbors, [v(0, -1), v(-1, 0), v(0, 1), v(1, 0)]);
+    /// }
+    /// ```
+    #[inline]
+    pub fn offsets(pos: Vector) -> &'static [Vector; 4] {
+        if pos.y.rem_euclid(2) == 0 {
+            &Self::EVEN
+        } else {
+            &Self::ODD
+        }
+    }
+
+    /// Returns the offsets from `pos` to the 8 tiles sharing an edge or a corner with it,
+    /// in the screen directions of [`ADJACENT`](crate::prelude::ADJACENT).
+    #[inline]
+    pub fn adjacent_offsets(pos: Vector) -> &'static [Vector; 8] {
+        if pos.y.rem_euclid(2) == 0 {
+            &Self::EVEN_ADJACENT
+        } else {
+            &Self::ODD_ADJACENT
+        }
+    }
+
+    /// Returns the positions of the 4 tiles sharing an edge with the tile at `pos`, in the order of [`offsets`](Self::offsets).
+    #[inline]
+    pub fn neighbors(pos: Vector) -> [Vector; 4] {
+        Self::offsets(pos).map(|offset| pos + offset)
+    }
+
+    /// Returns the positions of the 8 tiles sharing an edge or a corner with the tile at `pos`,
+    /// in the order of [`adjacent_offsets`](Self::adjacent_offsets).
+    #[inline]
+    pub fn adjacent(pos: Vector) -> [Vector; 8] {
+        Self::adjacent_offsets(pos).map(|offset| pos + offset)
+    }
+}
@@ -0,0 +1,237 @@
+//! Tile-game style light propagation, with incremental updates when light sources or opacities change.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    trace::span,
+    vector::{constants::ORTHOGONAL, Vector},
+};
+
+use std::collections::VecDeque;
+
+/// Light levels over a grid, spread orthogonally from light sources and dimmed by opaque positions,
+/// in the style of block light in sandbox games.
+///
+/// Each position has an emission level and an opacity. Light loses `1 + opacity` levels on entering a position,
+/// so the light level of a position is the greatest of its own emission and the levels of its neighbours,
+/// each reduced by `1 + opacity` of the position, saturating at `0`.
+///
+/// When the emission or opacity of a position changes, only the light it affects is recomputed.
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let world: Grid<char> = Grid::from_nested_iter([
+///     ".....".chars(),
+///     "###..".chars(),
+///     ".....".chars(),
+/// ]);
+///
+/// let mut light = world.propagate_light([(v(0, 0), 6)], |&c| if c == '#' { 15 } else { 0 });
+///
+/// assert_eq!(light.level(v(0, 0)), Some(6));
+/// assert_eq!(light.level(v(4, 0)), Some(2));
+/// assert_eq!(light.level(v(2, 1)), Some(0));
+/// assert_eq!(light.level(v(3, 2)), Some(1));
+/// assert_eq!(light.level(v(0, 2)), Some(0));
+///
+/// // knock a hole in the wall
+/// light.set_opacity(v(0, 1), 0);
+///
+/// assert_eq!(light.level(v(0, 1)), Some(5));
+/// assert_eq!(light.level(v(0, 2)), Some(4));
+/// assert_eq!(light.level(v(2, 2)), Some(2));
+///
+/// // put out the light
+/// light.set_emission(v(0, 0), 0);
+///
+/// assert!(light.levels().iter().all(|&level| level == 0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LightMap {
+    levels: Grid<u8>,
+    emission: Grid<u8>,
+    opacity: Grid<u8>,
+}
+
+impl<T> Grid<T> {
+    /// Returns the light levels over `self`, spread from the given sources, each a position and the level it emits,
+    /// with the opacity of each position given by `opacity`.
+    ///
+    /// If a position appears more than once in `sources`, the last emission level given for it is used.
+    ///
+    /// Panics if any source is out of bounds.
+    #[track_caller]
+    pub fn propagate_light<I, F>(&self, sources: I, opacity: F) -> LightMap
+    where
+        I: IntoIterator<Item = (Vector, u8)>,
+        F: FnMut(&T) -> u8,
+    {
+        self.try_propagate_light(sources, opacity).or_panic()
+    }
+
+    /// Returns the light levels over `self`, as in [`propagate_light`](Self::propagate_light),
+    /// or an error if any source is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let world: Grid<u8> = Grid::new(4, 1, 0);
+    ///
+    /// let light = world.try_propagate_light([(v(0, 0), 3)], |&opacity| opacity).unwrap();
+    /// assert_eq!(light.level(v(2, 0)), Some(1));
+    ///
+    /// assert_eq!(
+    ///     world.try_propagate_light([(v(0, 0), 3), (v(0, 1), 3)], |&opacity| opacity).err(),
+    ///     Some(GridError::OutOfBounds { dim: v(4, 1), region: Rect::new(v(0, 1), v(1, 1)) }),
+    /// );
+    ///
+    /// let empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.try_propagate_light([], |&opacity| opacity).unwrap().dim(), v(0, 2));
+    /// ```
+    pub fn try_propagate_light<I, F>(&self, sources: I, opacity: F) -> Result<LightMap, GridError>
+    where
+        I: IntoIterator<Item = (Vector, u8)>,
+        F: FnMut(&T) -> u8,
+    {
+        span!("propagate_light", cells = self.raw.len());
+        let mut emission = self.map(|_| 0);
+        for (pos, level) in sources {
+            *emission
+                .get_mut(pos)
+                .ok_or(GridError::out_of_bounds(self.dim, pos))? = level;
+        }
+        let mut light = LightMap {
+            levels: self.map(|_| 0),
+            emission,
+            opacity: self.map(opacity),
+        };
+        let mut queue = VecDeque::new();
+        for (pos, &level) in light.emission.iter_positions() {
+            if level > 0 {
+                light.levels[pos] = level;
+                queue.push_back(pos);
+            }
+        }
+        light.spread(queue);
+        Ok(light)
+    }
+}
+
+impl LightMap {
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.levels.dim()
+    }
+
+    /// Returns a reference to the grid of light levels.
+    #[inline]
+    pub fn levels(&self) -> &Grid<u8> {
+        &self.levels
+    }
+
+    /// Returns the light level at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn level(&self, pos: Vector) -> Option<u8> {
+        self.levels.get(pos).copied()
+    }
+
+    /// Returns the level of light emitted at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn emission(&self, pos: Vector) -> Option<u8> {
+        self.emission.get(pos).copied()
+    }
+
+    /// Returns the opacity of the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn opacity(&self, pos: Vector) -> Option<u8> {
+        self.opacity.get(pos).copied()
+    }
+
+    /// Sets the level of light emitted at the given position, updating the light levels it affects.
+    ///
+    /// Returns the old emission level, or `None` if `pos` is out of bounds, in which case nothing changes.
+    pub fn set_emission(&mut self, pos: Vector, level: u8) -> Option<u8> {
+        let old = self.emission.set(pos, level)?;
+        if old != level {
+            self.update(pos);
+        }
+        Some(old)
+    }
+
+    /// Sets the opacity of the given position, updating the light levels it affects.
+    ///
+    /// Returns the old opacity, or `None` if `pos` is out of bounds, in which case nothing changes.
+    pub fn set_opacity(&mut self, pos: Vector, opacity: u8) -> Option<u8> {
+        let old = self.opacity.set(pos, opacity)?;
+        if old != opacity {
+            self.update(pos);
+        }
+        Some(old)
+    }
+
+    /// Recomputes the light levels after the emission or opacity at `pos` has changed.
+    ///
+    /// First the light at `pos` is removed, along with all light that could have spread through it,
+    /// which is any light dimmer than the light that was removed from its neighbour.
+    /// Brighter light bordering the removed region, and emission within it, is then spread back in.
+    fn update(&mut self, pos: Vector) {
+        let mut relight = VecDeque::new();
+        let mut removal = VecDeque::from([(pos, self.levels[pos])]);
+        self.levels[pos] = 0;
+        while let Some((pos, old)) = removal.pop_front() {
+            if self.emission[pos] > 0 {
+                self.levels[pos] = self.emission[pos];
+                relight.push_back(pos);
+            }
+            for offset in ORTHOGONAL {
+                let neighbor = pos + offset;
+                let Some(&level) = self.levels.get(neighbor) else {
+                    continue;
+                };
+                if level == 0 {
+                    continue;
+                }
+                if level < old {
+                    self.levels[neighbor] = 0;
+                    removal.push_back((neighbor, level));
+                } else {
+                    relight.push_back(neighbor);
+                }
+            }
+        }
+        for offset in ORTHOGONAL {
+            if self
+                .levels
+                .get(pos + offset)
+                .is_some_and(|&level| level > 0)
+            {
+                relight.push_back(pos + offset);
+            }
+        }
+        self.spread(relight);
+    }
+
+    /// Spreads light outwards from the positions in `queue`.
+    fn spread(&mut self, mut queue: VecDeque<Vector>) {
+        while let Some(pos) = queue.pop_front() {
+            let level = self.levels[pos];
+            for offset in ORTHOGONAL {
+                let neighbor = pos + offset;
+                let Some(&current) = self.levels.get(neighbor) else {
+                    continue;
+                };
+                let spread = level.saturating_sub(self.opacity[neighbor].saturating_add(1));
+                if spread > current {
+                    self.levels[neighbor] = spread;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+    }
+}
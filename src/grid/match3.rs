@@ -0,0 +1,202 @@
+//! Finding runs of equal values and collapsing the gaps they leave, the core loop of match-3 games.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{set::PositionSet, Grid},
+    vector::Vector,
+};
+
+impl<T: PartialEq> Grid<T> {
+    /// Returns every position that is part of a horizontal or vertical run of at least `min_len` equal values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let board: Grid<char> = Grid::from_nested_iter([
+    ///     "RRRG".chars(),
+    ///     "BGBG".chars(),
+    ///     "BRRG".chars(),
+    ///     "BGRR".chars(),
+    /// ]);
+    ///
+    /// let matches = board.find_matches(3);
+    ///
+    /// assert_eq!(matches.iter().collect::<Vec<_>>(), vec![
+    ///     v(0, 0), v(1, 0), v(2, 0), v(3, 0),
+    ///     v(0, 1), v(3, 1),
+    ///     v(0, 2), v(3, 2),
+    ///     v(0, 3),
+    /// ]);
+    ///
+    /// let empty: Grid<char> = Grid::from_nested_iter([[], []]);
+    /// assert!(empty.find_matches(3).is_empty());
+    /// ```
+    pub fn find_matches(&self, min_len: usize) -> PositionSet {
+        let mut matches = PositionSet::new();
+        if self.raw.is_empty() {
+            return matches;
+        }
+        for (lanes, step) in [
+            (self.dim.y, Vector::new(1, 0)),
+            (self.dim.x, Vector::new(0, 1)),
+        ] {
+            for lane in 0..lanes {
+                let mut run_start = step.perp().abs() * lane;
+                let mut len = 0;
+                let mut pos = run_start;
+                loop {
+                    let value = self.get(pos);
+                    if value != Some(&self[run_start]) {
+                        if len >= min_len {
+                            matches.extend((0..len as i64).map(|i| run_start + step * i));
+                        }
+                        if value.is_none() {
+                            break;
+                        }
+                        run_start = pos;
+                        len = 0;
+                    }
+                    len += 1;
+                    pos += step;
+                }
+            }
+        }
+        matches
+    }
+}
+
+impl<T> Grid<T> {
+    /// Removes the values at the given positions, lets the remaining values fall in the direction `gravity` to fill the gaps,
+    /// then fills the positions left empty at the far side with values taken from `refill`, in row-major order.
+    ///
+    /// Positions in `cleared` that are out of bounds are ignored.
+    ///
+    /// Panics if `gravity` is not one of the four orthogonal unit vectors, or if `refill` runs out of values,
+    /// in which case the grid is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut board: Grid<char> = Grid::from_nested_iter([
+    ///     "ABC".chars(),
+    ///     "DEF".chars(),
+    ///     "GGG".chars(),
+    /// ]);
+    ///
+    /// let matches = board.find_matches(3);
+    /// board.collapse(&matches, SOUTH, "xyz".chars());
+    ///
+    /// assert_eq!(board, Grid::from_nested_iter([
+    ///     "xyz".chars(),
+    ///     "ABC".chars(),
+    ///     "DEF".chars(),
+    /// ]));
+    ///
+    /// let cleared = [v(0, 0), v(0, 2)].into_iter().collect();
+    /// board.collapse(&cleared, EAST, "12".chars());
+    ///
+    /// assert_eq!(board, Grid::from_nested_iter([
+    ///     "1yz".chars(),
+    ///     "ABC".chars(),
+    ///     "2EF".chars(),
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn collapse<I>(&mut self, cleared: &PositionSet, gravity: Vector, refill: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_collapse(cleared, gravity, refill).or_panic()
+    }
+
+    /// Removes the values at the given positions and collapses the gaps they leave, as in [`collapse`](Self::collapse),
+    /// or returns an error without changing the grid if `gravity` is not one of the four orthogonal unit vectors,
+    /// or if `refill` runs out of values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut board: Grid<char> = Grid::from_nested_iter(["AB".chars(), "CC".chars()]);
+    ///
+    /// let matches = board.find_matches(2);
+    ///
+    /// assert!(board.try_collapse(&matches, v(1, 1), "xy".chars()).is_err());
+    /// assert_eq!(
+    ///     board.try_collapse(&matches, SOUTH, "x".chars()),
+    ///     Err(GridError::TooFewValues { expected: 2, found: 1 }),
+    /// );
+    /// assert_eq!(board.try_collapse(&matches, SOUTH, "xy".chars()), Ok(()));
+    /// assert_eq!(board, Grid::from_nested_iter(["xy".chars(), "AB".chars()]));
+    /// ```
+    pub fn try_collapse<I>(
+        &mut self,
+        cleared: &PositionSet,
+        gravity: Vector,
+        refill: I,
+    ) -> Result<(), GridError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if gravity.abs().x + gravity.abs().y != 1 {
+            return Err(GridError::InvalidArgument(
+                "gravity must be an orthogonal unit vector",
+            ));
+        }
+        let cleared_count = cleared.iter().filter(|&pos| self.in_bounds(pos)).count();
+        let mut refill: Vec<T> = refill.into_iter().take(cleared_count).collect();
+        if refill.len() < cleared_count {
+            return Err(GridError::TooFewValues {
+                expected: cleared_count,
+                found: refill.len(),
+            });
+        }
+        refill.reverse();
+        let mut cells: Vec<Option<T>> = std::mem::take(&mut self.raw)
+            .into_iter()
+            .map(Some)
+            .collect();
+        let width = self.dim.x as usize;
+        let index = |pos: Vector| pos.x as usize + pos.y as usize * width;
+        for pos in cleared.iter().filter(|&pos| self.in_bounds(pos)) {
+            cells[index(pos)] = None;
+        }
+        // walk each lane upstream from its downstream end, packing the remaining values downstream
+        let (lanes, along) = if gravity.x == 0 {
+            (self.dim.x, self.dim.y)
+        } else {
+            (self.dim.y, self.dim.x)
+        };
+        for lane in 0..lanes {
+            let at = |i: i64| {
+                let i = if gravity.x + gravity.y > 0 {
+                    along - 1 - i
+                } else {
+                    i
+                };
+                if gravity.x == 0 {
+                    Vector::new(lane, i)
+                } else {
+                    Vector::new(i, lane)
+                }
+            };
+            let mut filled = 0;
+            for i in 0..along {
+                if let Some(value) = cells[index(at(i))].take() {
+                    cells[index(at(filled))] = Some(value);
+                    filled += 1;
+                }
+            }
+        }
+        self.raw = cells
+            .into_iter()
+            .map(|cell| cell.unwrap_or_else(|| refill.pop().unwrap()))
+            .collect();
+        Ok(())
+    }
+}
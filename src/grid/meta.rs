@@ -0,0 +1,264 @@
+//! A `Grid` paired with sparse per-position metadata, for annotating some positions without widening every value.
+
+use std::{
+    collections::{btree_map::Entry, BTreeMap},
+    ops::{Index, IndexMut},
+};
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+impl<T> Grid<T> {
+    /// Pairs the grid with an empty table of metadata of type `M`, consuming the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let map: Grid<char> = Grid::new(4, 4, '.');
+    /// let mut map = map.with_meta::<&str>();
+    ///
+    /// map.set_meta(v(2, 1), "treasure");
+    /// map[v(2, 1)] = '$';
+    ///
+    /// assert_eq!(map.meta(v(2, 1)), Some(&"treasure"));
+    /// assert_eq!(map.meta(v(0, 0)), None);
+    /// assert_eq!(map.into_grid()[v(2, 1)], '$');
+    /// ```
+    #[inline]
+    pub fn with_meta<M>(self) -> WithMeta<T, M> {
+        WithMeta {
+            grid: self,
+            meta: BTreeMap::new(),
+        }
+    }
+}
+
+/// A grid, together with metadata for any number of its positions.
+///
+/// The metadata is stored separately from the values, in a sparse table that only holds the positions given metadata,
+/// and the grid cannot be resized while paired with it, so every position with metadata is always in bounds.
+///
+/// Constructed by [`Grid::with_meta`].
+#[derive(PartialEq, Eq, Clone, Hash)]
+pub struct WithMeta<T, M> {
+    grid: Grid<T>,
+    // keyed by index so that iteration is in row-major order
+    meta: BTreeMap<usize, M>,
+}
+
+impl<T, M> WithMeta<T, M> {
+    /// Returns a reference to the grid.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Returns the grid, discarding the metadata.
+    #[inline]
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.grid.dim
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.grid.get(pos)
+    }
+
+    /// Returns a mutable reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, pos: Vector) -> Option<&mut T> {
+        self.grid.get_mut(pos)
+    }
+
+    /// Returns a reference to the metadata of the given position, or `None` if it has none or is out of bounds.
+    pub fn meta(&self, pos: Vector) -> Option<&M> {
+        self.meta.get(&self.grid.get_index(pos)?)
+    }
+
+    /// Returns a mutable reference to the metadata of the given position, or `None` if it has none or is out of bounds.
+    pub fn meta_mut(&mut self, pos: Vector) -> Option<&mut M> {
+        self.meta.get_mut(&self.grid.get_index(pos)?)
+    }
+
+    /// Sets the metadata of the given position, returning its old metadata, if any.
+    ///
+    /// Panics if `pos` is out of bounds.
+    #[track_caller]
+    pub fn set_meta(&mut self, pos: Vector, meta: M) -> Option<M> {
+        self.try_set_meta(pos, meta).or_panic()
+    }
+
+    /// Sets the metadata of the given position, returning its old metadata, if any,
+    /// or returns an error if `pos` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut map = Grid::new(2, 2, '.').with_meta::<&str>();
+    ///
+    /// assert_eq!(map.try_set_meta(v(1, 1), "door"), Ok(None));
+    /// assert_eq!(map.try_set_meta(v(1, 1), "gate"), Ok(Some("door")));
+    /// assert_eq!(
+    ///     map.try_set_meta(v(2, 1), "wall"),
+    ///     Err(GridError::OutOfBounds { dim: v(2, 2), region: Rect::new(v(2, 1), v(1, 1)) }),
+    /// );
+    /// assert_eq!(map.meta_len(), 1);
+    /// ```
+    pub fn try_set_meta(&mut self, pos: Vector, meta: M) -> Result<Option<M>, GridError> {
+        let index = self.try_index_of(pos)?;
+        Ok(self.meta.insert(index, meta))
+    }
+
+    /// Removes the metadata of the given position, returning it, or `None` if it has none or is out of bounds.
+    pub fn remove_meta(&mut self, pos: Vector) -> Option<M> {
+        self.meta.remove(&self.grid.get_index(pos)?)
+    }
+
+    /// Returns a mutable reference to the metadata of the given position,
+    /// first setting it to the value returned by `f` if it has none.
+    ///
+    /// Panics if `pos` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut board = Grid::new(9, 9, 0u8).with_meta::<Vec<u8>>();
+    ///
+    /// // pencil marks
+    /// board.meta_or_insert_with(v(4, 4), Vec::new).push(3);
+    /// board.meta_or_insert_with(v(4, 4), Vec::new).push(7);
+    ///
+    /// assert_eq!(board.meta(v(4, 4)), Some(&vec![3, 7]));
+    /// assert_eq!(board.meta_len(), 1);
+    /// ```
+    #[track_caller]
+    pub fn meta_or_insert_with<F>(&mut self, pos: Vector, f: F) -> &mut M
+    where
+        F: FnOnce() -> M,
+    {
+        self.try_meta_or_insert_with(pos, f).or_panic()
+    }
+
+    /// Returns a mutable reference to the metadata of the given position, as in [`meta_or_insert_with`](Self::meta_or_insert_with),
+    /// or an error if `pos` is out of bounds, in which case `f` is not called.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut board = Grid::new(3, 3, 0u8).with_meta::<Vec<u8>>();
+    ///
+    /// board.try_meta_or_insert_with(v(0, 2), Vec::new).unwrap().push(5);
+    ///
+    /// assert_eq!(board.meta(v(0, 2)), Some(&vec![5]));
+    /// assert!(board.try_meta_or_insert_with(v(0, 3), Vec::new).is_err());
+    /// assert_eq!(board.meta_len(), 1);
+    /// ```
+    pub fn try_meta_or_insert_with<F>(&mut self, pos: Vector, f: F) -> Result<&mut M, GridError>
+    where
+        F: FnOnce() -> M,
+    {
+        let index = self.try_index_of(pos)?;
+        Ok(match self.meta.entry(index) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(f()),
+        })
+    }
+
+    /// Returns the number of positions with metadata.
+    #[inline]
+    pub fn meta_len(&self) -> usize {
+        self.meta.len()
+    }
+
+    /// Removes the metadata of every position.
+    #[inline]
+    pub fn clear_meta(&mut self) {
+        self.meta.clear();
+    }
+
+    /// Keeps only the metadata for which `f` returns `true`, given its position.
+    pub fn retain_meta<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Vector, &mut M) -> bool,
+    {
+        let width = self.grid.dim.x as usize;
+        self.meta
+            .retain(|&index, meta| f(position(index, width), meta));
+    }
+
+    /// Returns an iterator over the positions with metadata and references to their metadata, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut costs = Grid::new(3, 3, '.').with_meta::<u32>();
+    ///
+    /// costs.set_meta(v(2, 2), 5);
+    /// costs.set_meta(v(1, 0), 9);
+    ///
+    /// assert_eq!(costs.iter_meta().collect::<Vec<_>>(), vec![(v(1, 0), &9), (v(2, 2), &5)]);
+    /// ```
+    pub fn iter_meta(&self) -> impl DoubleEndedIterator<Item = (Vector, &M)> + ExactSizeIterator {
+        let width = self.grid.dim.x as usize;
+        self.meta
+            .iter()
+            .map(move |(&index, meta)| (position(index, width), meta))
+    }
+
+    /// Returns an iterator over the positions with metadata and mutable references to their metadata, in row-major order.
+    pub fn iter_meta_mut(
+        &mut self,
+    ) -> impl DoubleEndedIterator<Item = (Vector, &mut M)> + ExactSizeIterator {
+        let width = self.grid.dim.x as usize;
+        self.meta
+            .iter_mut()
+            .map(move |(&index, meta)| (position(index, width), meta))
+    }
+
+    /// Returns the index of the given position, or an error if it is out of bounds.
+    fn try_index_of(&self, pos: Vector) -> Result<usize, GridError> {
+        self.grid
+            .get_index(pos)
+            .ok_or(GridError::out_of_bounds(self.grid.dim, pos))
+    }
+}
+
+fn position(index: usize, width: usize) -> Vector {
+    Vector::new((index % width) as i64, (index / width) as i64)
+}
+
+impl<T, M> Index<Vector> for WithMeta<T, M> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, pos: Vector) -> &Self::Output {
+        &self.grid[pos]
+    }
+}
+
+impl<T, M> IndexMut<Vector> for WithMeta<T, M> {
+    #[track_caller]
+    fn index_mut(&mut self, pos: Vector) -> &mut Self::Output {
+        &mut self.grid[pos]
+    }
+}
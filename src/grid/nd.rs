@@ -0,0 +1,387 @@
+//! A grid with any number of dimensions, for simulations such as cellular automata in three or more dimensions.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{self, Grid},
+    vector::Vector,
+};
+
+use std::{
+    iter::FusedIterator,
+    ops::{Index, IndexMut},
+    slice::{Iter, IterMut},
+};
+
+/// A grid with `D` dimensions, indexed by `[i64; D]` positions.
+///
+/// Values are stored with the first coordinate changing fastest, so a `GridN<T, 2>` stores its values in the same
+/// row-major order as a [`Grid`], and the two can be converted into each other without moving any values.
+///
+/// # Examples
+///
+/// ```
+/// use grid::nd::GridN;
+///
+/// // one step of Conway's Game of Life in four dimensions
+/// let mut cells: GridN<bool, 4> = GridN::new([3, 3, 3, 3], false);
+/// cells[[0, 1, 1, 1]] = true;
+/// cells[[1, 1, 1, 1]] = true;
+/// cells[[2, 1, 1, 1]] = true;
+///
+/// let next = cells.pos_map(|pos, &alive| {
+///     let live = cells.adjacent(pos).filter(|&n| cells[n]).count();
+///     live == 3 || (alive && live == 2)
+/// });
+///
+/// assert!(next[[1, 1, 1, 1]]);
+/// assert!(next[[1, 0, 0, 0]]);
+/// assert!(!next[[0, 1, 1, 1]]);
+/// assert_eq!(next.iter().filter(|&&alive| alive).count(), 27);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GridN<T, const D: usize> {
+    raw: Vec<T>,
+    dim: [i64; D],
+}
+
+impl<T: Clone, const D: usize> GridN<T, D> {
+    /// Constructs a new `GridN<T, D>` with the given dimensions, initialising all values to `value`.
+    ///
+    /// Panics if any of the dimensions are not positive, or if they are too large.
+    #[track_caller]
+    pub fn new(dim: [i64; D], value: T) -> Self {
+        Self::try_new(dim, value).or_panic()
+    }
+
+    /// Constructs a new `GridN<T, D>` with the given dimensions, initialising all values to `value`,
+    /// or returns an error if any of the dimensions are not positive, or if they are too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{nd::GridN, prelude::*};
+    ///
+    /// assert_eq!(GridN::try_new([2, 2, 2], 0).unwrap().len(), 8);
+    /// assert!(GridN::try_new([2, 0, 2], 0).is_err());
+    /// assert_eq!(
+    ///     GridN::try_new([1 << 22, 1 << 22, 1 << 22], 0u8),
+    ///     Err(GridError::TooLarge { width: 1 << 22, height: 1 << 44 }),
+    /// );
+    /// ```
+    pub fn try_new(dim: [i64; D], value: T) -> Result<Self, GridError> {
+        let (mut raw, size) = try_alloc(dim)?;
+        raw.resize(size, value);
+        Ok(Self { raw, dim })
+    }
+}
+
+impl<T, const D: usize> GridN<T, D> {
+    /// Constructs a new `GridN<T, D>` with the given dimensions, computing all initial values from the closure `f`
+    /// which maps each position to a value.
+    ///
+    /// Panics if any of the dimensions are not positive, or if they are too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::nd::GridN;
+    ///
+    /// let grid = GridN::from_fn([4, 3, 2], |[x, y, z]| x + y * 10 + z * 100);
+    ///
+    /// assert_eq!(grid[[3, 2, 1]], 123);
+    /// ```
+    #[track_caller]
+    pub fn from_fn<F>(dim: [i64; D], f: F) -> Self
+    where
+        F: FnMut([i64; D]) -> T,
+    {
+        Self::try_from_fn(dim, f).or_panic()
+    }
+
+    /// Constructs a new `GridN<T, D>` with the given dimensions, computing all initial values from the closure `f`
+    /// which maps each position to a value, or returns an error if any of the dimensions are not positive, or if they are too large.
+    pub fn try_from_fn<F>(dim: [i64; D], f: F) -> Result<Self, GridError>
+    where
+        F: FnMut([i64; D]) -> T,
+    {
+        let (mut raw, _) = try_alloc(dim)?;
+        raw.extend(PositionsN::new(dim).map(f));
+        Ok(Self { raw, dim })
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> [i64; D] {
+        self.dim
+    }
+
+    /// Returns the number of values in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    /// Returns `true` if the grid has no values, which is never the case.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    /// Returns a slice of the values in the grid, with the first coordinate changing fastest.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        &self.raw
+    }
+
+    /// Returns `true` if `pos` is in bounds of the grid, or `false` otherwise.
+    #[inline]
+    pub fn in_bounds(&self, pos: [i64; D]) -> bool {
+        pos.iter().zip(self.dim).all(|(&c, d)| (0..d).contains(&c))
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: [i64; D]) -> Option<&T> {
+        self.get_index(pos).map(|index| &self.raw[index])
+    }
+
+    /// Returns a mutable reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, pos: [i64; D]) -> Option<&mut T> {
+        self.get_index(pos).map(|index| &mut self.raw[index])
+    }
+
+    /// Returns an iterator over every position in the grid, with the first coordinate changing fastest.
+    #[inline]
+    pub fn positions(&self) -> PositionsN<D> {
+        PositionsN::new(self.dim)
+    }
+
+    /// Returns an iterator over references to the values in the grid, with the first coordinate changing fastest.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.raw.iter()
+    }
+
+    /// Returns an iterator over mutable references to the values in the grid, with the first coordinate changing fastest.
+    #[inline]
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        self.raw.iter_mut()
+    }
+
+    /// Returns an iterator over every position and a reference to its value, with the first coordinate changing fastest.
+    pub fn iter_positions(&self) -> impl Iterator<Item = ([i64; D], &T)> {
+        self.positions().zip(&self.raw)
+    }
+
+    /// Returns an iterator over the positions adjacent to `pos` that are in bounds, in every direction including diagonals,
+    /// of which there are up to `3^D - 1`.
+    ///
+    /// The iterator does not borrow the grid, so it can be used while changing the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::nd::GridN;
+    ///
+    /// let grid: GridN<u8, 3> = GridN::new([3, 3, 3], 0);
+    ///
+    /// assert_eq!(grid.adjacent([1, 1, 1]).count(), 26);
+    /// assert_eq!(grid.adjacent([0, 0, 0]).count(), 7);
+    /// ```
+    pub fn adjacent(&self, pos: [i64; D]) -> impl Iterator<Item = [i64; D]> {
+        let dim = self.dim;
+        PositionsN::new([3; D])
+            .filter(|offset| offset.iter().any(|&c| c != 1))
+            .map(move |offset| std::array::from_fn(|i| pos[i] + offset[i] - 1))
+            .filter(move |neighbor: &[i64; D]| {
+                neighbor.iter().zip(dim).all(|(&c, d)| (0..d).contains(&c))
+            })
+    }
+
+    /// Maps the values and positions of an existing grid to create a new grid with the same dimensions.
+    pub fn pos_map<F, U>(&self, mut f: F) -> GridN<U, D>
+    where
+        F: FnMut([i64; D], &T) -> U,
+    {
+        GridN {
+            raw: self
+                .iter_positions()
+                .map(|(pos, value)| f(pos, value))
+                .collect(),
+            dim: self.dim,
+        }
+    }
+
+    /// Maps the values of an existing grid to create a new grid with the same dimensions.
+    pub fn map<F, U>(&self, f: F) -> GridN<U, D>
+    where
+        F: FnMut(&T) -> U,
+    {
+        GridN {
+            raw: self.raw.iter().map(f).collect(),
+            dim: self.dim,
+        }
+    }
+
+    fn get_index(&self, pos: [i64; D]) -> Option<usize> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        let mut index = 0;
+        for (&c, d) in pos.iter().zip(self.dim).rev() {
+            index = index * d as usize + c as usize;
+        }
+        Some(index)
+    }
+}
+
+impl<T, const D: usize> Index<[i64; D]> for GridN<T, D> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, pos: [i64; D]) -> &Self::Output {
+        match self.get(pos) {
+            Some(value) => value,
+            None => panic!(
+                "position out of bounds: the dimensions are {:?} but the position is {pos:?}",
+                self.dim
+            ),
+        }
+    }
+}
+
+impl<T, const D: usize> IndexMut<[i64; D]> for GridN<T, D> {
+    #[track_caller]
+    fn index_mut(&mut self, pos: [i64; D]) -> &mut Self::Output {
+        let dim = self.dim;
+        match self.get_mut(pos) {
+            Some(value) => value,
+            None => panic!(
+                "position out of bounds: the dimensions are {dim:?} but the position is {pos:?}"
+            ),
+        }
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for &'a GridN<T, D> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a, T, const D: usize> IntoIterator for &'a mut GridN<T, D> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+impl<T> From<Grid<T>> for GridN<T, 2> {
+    /// Converts a `Grid` into a two-dimensional `GridN`, indexed by `[x, y]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{nd::GridN, prelude::*};
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1, 2], [3, 4]]);
+    /// let grid_n = GridN::from(grid.clone());
+    ///
+    /// assert_eq!(grid_n[[0, 1]], 3);
+    /// assert_eq!(Grid::from(grid_n), grid);
+    /// ```
+    fn from(grid: Grid<T>) -> Self {
+        Self {
+            dim: [grid.dim.x, grid.dim.y],
+            raw: grid.raw,
+        }
+    }
+}
+
+impl<T> From<GridN<T, 2>> for Grid<T> {
+    /// Converts a two-dimensional `GridN` into a `Grid`, where `[x, y]` becomes the position `(x, y)`.
+    fn from(grid: GridN<T, 2>) -> Self {
+        Self {
+            raw: grid.raw,
+            dim: Vector::new(grid.dim[0], grid.dim[1]),
+        }
+    }
+}
+
+/// An iterator over every position in a grid with `D` dimensions, with the first coordinate changing fastest.
+#[derive(Clone, Copy)]
+pub struct PositionsN<const D: usize> {
+    next: [i64; D],
+    dim: [i64; D],
+    remaining: usize,
+}
+
+impl<const D: usize> PositionsN<D> {
+    fn new(dim: [i64; D]) -> Self {
+        let remaining = dim.iter().map(|&d| d.max(0) as usize).product::<usize>();
+        Self {
+            next: [0; D],
+            dim,
+            remaining,
+        }
+    }
+}
+
+impl<const D: usize> Iterator for PositionsN<D> {
+    type Item = [i64; D];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let pos = self.next;
+        for (c, &d) in self.next.iter_mut().zip(&self.dim) {
+            *c += 1;
+            if *c < d {
+                break;
+            }
+            *c = 0;
+        }
+        Some(pos)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<const D: usize> ExactSizeIterator for PositionsN<D> {}
+
+impl<const D: usize> FusedIterator for PositionsN<D> {}
+
+/// Returns an empty vector with room for every value in a grid of `T` with the given dimensions, and the number of values,
+/// or an error if the dimensions are not positive or too large, or if the allocation fails.
+///
+/// The size is checked as that of a two-dimensional grid whose width is the first dimension and whose height is the product of the rest,
+/// which is also how the dimensions are reported in a [`GridError::TooLarge`].
+fn try_alloc<T, const D: usize>(dim: [i64; D]) -> Result<(Vec<T>, usize), GridError> {
+    if dim.iter().any(|&d| d <= 0) {
+        return Err(GridError::InvalidArgument("dimensions must be positive"));
+    }
+    let (width, rest) = dim
+        .split_first()
+        .map_or((1, &[][..]), |(&width, rest)| (width, rest));
+    let height = rest
+        .iter()
+        .try_fold(1i64, |height, &d| height.checked_mul(d))
+        .ok_or(GridError::TooLarge {
+            width,
+            height: i64::MAX,
+        })?;
+    grid::try_alloc(width, height)
+}
@@ -0,0 +1,156 @@
+//! Reading the values at fixed offsets around positions of a `Grid`, and counting neighbours.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{try_reserve, Grid},
+    vector::Vector,
+};
+
+impl<T> Grid<T> {
+    /// Returns references to the values at each of the given offsets from `pos`, or `None` for those out of bounds.
+    ///
+    /// The result is a fixed-size array, so no allocation takes place.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// assert_eq!(grid.gather(v(0, 1), &ORTHOGONAL), [Some(&5), Some(&0), None, Some(&8)]);
+    /// ```
+    pub fn gather<const N: usize>(&self, pos: Vector, offsets: &[Vector; N]) -> [Option<&T>; N] {
+        offsets.map(|offset| self.get(pos + offset))
+    }
+
+    /// Returns one grid for each of the given offsets, where the value at each position is a reference to the value at that offset from it in `self`, or `None` if out of bounds.
+    ///
+    /// Each grid is built a row at a time, so no bounds checks are needed for individual positions.
+    ///
+    /// Panics if the grids are too large to allocate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// let [east, north] = &grid.gather_all(&[EAST, NORTH])[..] else {
+    ///     unreachable!()
+    /// };
+    ///
+    /// assert_eq!(east[v(1, 2)], Some(&10));
+    /// assert_eq!(east[v(3, 2)], None);
+    /// assert_eq!(north[v(1, 2)], Some(&5));
+    /// assert_eq!(north[v(1, 0)], None);
+    ///
+    /// for (pos, value) in east.iter_positions() {
+    ///     assert_eq!(*value, grid.get(pos + EAST));
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn gather_all(&self, offsets: &[Vector]) -> Vec<Grid<Option<&T>>> {
+        self.try_gather_all(offsets).or_panic()
+    }
+
+    /// Returns one grid for each of the given offsets, as in [`gather_all`](Self::gather_all),
+    /// or an error if the grids are too large to allocate.
+    ///
+    /// Offsets of any size are allowed, however far they reach out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 2, 7);
+    ///
+    /// let far = grid.try_gather_all(&[v(i64::MIN, 0), v(0, i64::MAX), v(i64::MAX, i64::MIN)]).unwrap();
+    ///
+    /// assert!(far.iter().all(|gathered| gathered.iter().all(Option::is_none)));
+    /// ```
+    pub fn try_gather_all(&self, offsets: &[Vector]) -> Result<Vec<Grid<Option<&T>>>, GridError> {
+        let (width, height) = (self.width(), self.height());
+        let mut grids = Vec::with_capacity(offsets.len());
+        for &offset in offsets {
+            let mut raw = try_reserve(self.raw.len(), width, height)?;
+            let start = offset.x.clamp(-width, 0).unsigned_abs() as usize;
+            let end = width
+                .checked_sub(offset.x)
+                .map_or(width, |end| end.clamp(0, width)) as usize;
+            for y in 0..height {
+                let source_y = y
+                    .checked_add(offset.y)
+                    .filter(|source_y| (0..height).contains(source_y));
+                let Some(source_y) = source_y.filter(|_| start < end) else {
+                    raw.resize(raw.len() + width as usize, None);
+                    continue;
+                };
+                let row = &self.raw[(source_y * width) as usize..][..width as usize];
+                let shift = start as i64 + offset.x;
+                raw.resize(raw.len() + start, None);
+                raw.extend(row[shift as usize..][..end - start].iter().map(Some));
+                raw.resize(raw.len() + width as usize - end, None);
+            }
+            grids.push(Grid { raw, dim: self.dim });
+        }
+        Ok(grids)
+    }
+
+    /// Returns a grid where the value at each position is the number of its 8 adjacent neighbours
+    /// whose values satisfy `f`, as in the numbers of a Minesweeper board.
+    ///
+    /// `f` is called once per position, and the counts are built from running sums along the rows and then the columns,
+    /// which is much faster than querying the neighbours of every position separately.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mines: Grid<char> = Grid::from_nested_iter([
+    ///     "*...".chars(),
+    ///     "..*.".chars(),
+    ///     "....".chars(),
+    /// ]);
+    ///
+    /// let counts = mines.neighbor_counts(|&c| c == '*');
+    ///
+    /// assert_eq!(counts, Grid::from_nested_iter([
+    ///     [0, 2, 1, 1],
+    ///     [1, 2, 0, 1],
+    ///     [0, 1, 1, 1],
+    /// ]));
+    ///
+    /// let empty: Grid<char> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.neighbor_counts(|&c| c == '*').dim(), v(0, 2));
+    /// ```
+    pub fn neighbor_counts<F>(&self, mut f: F) -> Grid<u8>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if self.raw.is_empty() {
+            return Grid {
+                raw: Vec::new(),
+                dim: self.dim,
+            };
+        }
+        let width = self.width() as usize;
+        let hits: Vec<u8> = self.raw.iter().map(|value| f(value) as u8).collect();
+        let mut across = vec![0; hits.len()];
+        for (row, sums) in hits.chunks_exact(width).zip(across.chunks_exact_mut(width)) {
+            for x in 0..width {
+                sums[x] = row[x.saturating_sub(1)..(x + 2).min(width)].iter().sum();
+            }
+        }
+        let mut raw = Vec::with_capacity(hits.len());
+        for (i, &hit) in hits.iter().enumerate() {
+            let above = i.checked_sub(width).map_or(0, |i| across[i]);
+            let below = across.get(i + width).copied().unwrap_or(0);
+            raw.push(above + across[i] + below - hit);
+        }
+        Grid { raw, dim: self.dim }
+    }
+}
@@ -0,0 +1,268 @@
+//! Run-length clues for nonograms (Picross), and checking grids against them.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    rect::Rect,
+    vector::Vector,
+};
+
+impl<T> Grid<T> {
+    /// Returns the lengths of the runs of consecutive filled values in the row at the given `y` coordinate, from left to right,
+    /// where `filled` returns `true` for filled values.
+    ///
+    /// Panics if `y` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "##.#.###".chars(),
+    ///     "........".chars(),
+    /// ]);
+    ///
+    /// assert_eq!(grid.row_runs(0, |&c| c == '#'), vec![2, 1, 3]);
+    /// assert_eq!(grid.row_runs(1, |&c| c == '#'), vec![]);
+    /// ```
+    #[track_caller]
+    pub fn row_runs<F>(&self, y: i64, filled: F) -> Vec<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.try_row_runs(y, filled).or_panic()
+    }
+
+    /// Returns the lengths of the runs of filled values in the row at the given `y` coordinate, as in [`row_runs`](Self::row_runs),
+    /// or an error if `y` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter(["#.##".chars()]);
+    ///
+    /// assert_eq!(grid.try_row_runs(0, |&c| c == '#'), Ok(vec![1, 2]));
+    /// assert_eq!(
+    ///     grid.try_row_runs(1, |&c| c == '#'),
+    ///     Err(GridError::OutOfBounds { dim: v(4, 1), region: Rect::new(v(0, 1), v(4, 1)) }),
+    /// );
+    /// ```
+    pub fn try_row_runs<F>(&self, y: i64, filled: F) -> Result<Vec<usize>, GridError>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let row = self.get_row(y).ok_or(GridError::OutOfBounds {
+            dim: self.dim,
+            region: Rect::new(Vector::new(0, y), Vector::new(self.dim.x, 1)),
+        })?;
+        Ok(runs(row, filled))
+    }
+
+    /// Returns the lengths of the runs of consecutive filled values in the column at the given `x` coordinate, from top to bottom,
+    /// where `filled` returns `true` for filled values.
+    ///
+    /// Panics if `x` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<bool> = Grid::from_nested_iter([[true], [false], [true], [true]]);
+    ///
+    /// assert_eq!(grid.col_runs(0, |&filled| filled), vec![1, 2]);
+    /// ```
+    #[track_caller]
+    pub fn col_runs<F>(&self, x: i64, filled: F) -> Vec<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.try_col_runs(x, filled).or_panic()
+    }
+
+    /// Returns the lengths of the runs of filled values in the column at the given `x` coordinate, as in [`col_runs`](Self::col_runs),
+    /// or an error if `x` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<bool> = Grid::from_nested_iter([[true], [true], [false]]);
+    ///
+    /// assert_eq!(grid.try_col_runs(0, |&filled| filled), Ok(vec![2]));
+    /// assert_eq!(
+    ///     grid.try_col_runs(-1, |&filled| filled),
+    ///     Err(GridError::OutOfBounds { dim: v(1, 3), region: Rect::new(v(-1, 0), v(1, 3)) }),
+    /// );
+    /// ```
+    pub fn try_col_runs<F>(&self, x: i64, filled: F) -> Result<Vec<usize>, GridError>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let col = self.get_col(x).ok_or(GridError::OutOfBounds {
+            dim: self.dim,
+            region: Rect::new(Vector::new(x, 0), Vector::new(1, self.dim.y)),
+        })?;
+        Ok(runs(col, filled))
+    }
+}
+
+/// The clues of a nonogram: the lengths of the runs of filled values in each row and each column.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{nonogram::Clues, prelude::*};
+///
+/// let solution: Grid<char> = Grid::from_nested_iter([
+///     ".#.".chars(),
+///     "###".chars(),
+///     "#.#".chars(),
+/// ]);
+///
+/// let clues = Clues::from_grid(&solution, |&c| c == '#');
+///
+/// assert_eq!(clues.rows(), &[vec![1], vec![3], vec![1, 1]]);
+/// assert_eq!(clues.cols(), &[vec![2], vec![2], vec![2]]);
+///
+/// assert!(clues.check(&solution, |&c| c == '#'));
+///
+/// let attempt: Grid<char> = Grid::from_nested_iter([
+///     ".#.".chars(),
+///     "##.".chars(),
+///     "#.#".chars(),
+/// ]);
+///
+/// assert!(!clues.check(&attempt, |&c| c == '#'));
+/// assert_eq!(clues.wrong_rows(&attempt, |&c| c == '#'), vec![1]);
+/// assert_eq!(clues.wrong_cols(&attempt, |&c| c == '#'), vec![2]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Clues {
+    rows: Vec<Vec<usize>>,
+    cols: Vec<Vec<usize>>,
+}
+
+impl Clues {
+    /// Constructs new clues from the run lengths of each row, from top to bottom, and each column, from left to right.
+    #[inline]
+    pub fn new(rows: Vec<Vec<usize>>, cols: Vec<Vec<usize>>) -> Self {
+        Self { rows, cols }
+    }
+
+    /// Returns the clues solved by the given grid, where `filled` returns `true` for filled values.
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut filled: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Self {
+            rows: grid.rows().map(|row| runs(row, &mut filled)).collect(),
+            cols: grid.columns().map(|col| runs(col, &mut filled)).collect(),
+        }
+    }
+
+    /// Returns the run lengths of each row, from top to bottom.
+    #[inline]
+    pub fn rows(&self) -> &[Vec<usize>] {
+        &self.rows
+    }
+
+    /// Returns the run lengths of each column, from left to right.
+    #[inline]
+    pub fn cols(&self) -> &[Vec<usize>] {
+        &self.cols
+    }
+
+    /// Returns the width of grids the clues are for.
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.cols.len()
+    }
+
+    /// Returns the height of grids the clues are for.
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns `true` if the given grid solves the clues, where `filled` returns `true` for filled values, or `false` otherwise.
+    ///
+    /// A grid with the wrong dimensions never solves the clues.
+    pub fn check<T, F>(&self, grid: &Grid<T>, mut filled: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        grid.width() as usize == self.width()
+            && grid.height() as usize == self.height()
+            && grid
+                .rows()
+                .zip(&self.rows)
+                .all(|(row, clue)| runs(row, &mut filled) == *clue)
+            && grid
+                .columns()
+                .zip(&self.cols)
+                .all(|(col, clue)| runs(col, &mut filled) == *clue)
+    }
+
+    /// Returns the indices of the rows of the given grid that do not match their clues,
+    /// where `filled` returns `true` for filled values.
+    ///
+    /// Rows beyond the bottom of the grid, or beyond the last clue, are not compared.
+    pub fn wrong_rows<T, F>(&self, grid: &Grid<T>, mut filled: F) -> Vec<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        wrong(grid.rows(), &self.rows, &mut filled)
+    }
+
+    /// Returns the indices of the columns of the given grid that do not match their clues,
+    /// where `filled` returns `true` for filled values.
+    ///
+    /// Columns beyond the right of the grid, or beyond the last clue, are not compared.
+    pub fn wrong_cols<T, F>(&self, grid: &Grid<T>, mut filled: F) -> Vec<usize>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        wrong(grid.columns(), &self.cols, &mut filled)
+    }
+}
+
+/// Returns the lengths of the runs of consecutive filled values in a line.
+fn runs<'a, T: 'a, I, F>(line: I, mut filled: F) -> Vec<usize>
+where
+    I: IntoIterator<Item = &'a T>,
+    F: FnMut(&T) -> bool,
+{
+    let mut runs = Vec::new();
+    let mut len = 0;
+    for value in line {
+        if filled(value) {
+            len += 1;
+        } else if len > 0 {
+            runs.push(len);
+            len = 0;
+        }
+    }
+    if len > 0 {
+        runs.push(len);
+    }
+    runs
+}
+
+/// Returns the indices of the lines whose runs do not match their clues.
+fn wrong<'a, T: 'a, L, I, F>(lines: L, clues: &[Vec<usize>], filled: &mut F) -> Vec<usize>
+where
+    L: Iterator<Item = I>,
+    I: IntoIterator<Item = &'a T>,
+    F: FnMut(&T) -> bool,
+{
+    lines
+        .zip(clues)
+        .enumerate()
+        .filter_map(|(i, (line, clue))| (runs(line, &mut *filled) != *clue).then_some(i))
+        .collect()
+}
@@ -0,0 +1,350 @@
+//! Collision-free movement of entities over a `Grid`, with reservations and simultaneous move resolution.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+use std::{collections::BTreeMap, fmt};
+
+/// The reason an [`OccupancyGrid`] refused to place, move or reserve for an entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MoveError<Id> {
+    /// The position is out of bounds.
+    OutOfBounds,
+    /// The entity is not in the grid.
+    Unknown,
+    /// The entity is already in the grid.
+    AlreadyPlaced,
+    /// The position is occupied by the given entity.
+    Occupied(Id),
+    /// The position is reserved by the given entity.
+    Reserved(Id),
+    /// The move lost to the given entity's move to the same position.
+    Contested(Id),
+}
+
+impl<Id: fmt::Debug> fmt::Display for MoveError<Id> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "position out of bounds"),
+            Self::Unknown => write!(f, "entity not in the grid"),
+            Self::AlreadyPlaced => write!(f, "entity already in the grid"),
+            Self::Occupied(id) => write!(f, "position occupied by {id:?}"),
+            Self::Reserved(id) => write!(f, "position reserved by {id:?}"),
+            Self::Contested(id) => write!(f, "position taken by {id:?}"),
+        }
+    }
+}
+
+impl<Id: fmt::Debug> std::error::Error for MoveError<Id> {}
+
+/// Tracks which entity occupies each position of a grid, so that no two entities ever share a position.
+///
+/// An entity can also reserve one position it plans to move to, which no other entity may move to or reserve.
+/// Entities are identified by any small copyable ID type, such as an index or a handle.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{occupancy::{MoveError, OccupancyGrid}, prelude::*};
+///
+/// let mut units = OccupancyGrid::new(4, 4);
+///
+/// units.place('a', v(0, 0)).unwrap();
+/// units.place('b', v(1, 0)).unwrap();
+///
+/// assert_eq!(units.try_move('a', v(1, 0)), Err(MoveError::Occupied('b')));
+///
+/// units.reserve('b', v(2, 0)).unwrap();
+/// assert_eq!(units.try_move('a', v(2, 0)), Err(MoveError::Reserved('b')));
+///
+/// units.try_move('b', v(2, 0)).unwrap();
+/// units.try_move('a', v(1, 0)).unwrap();
+///
+/// assert_eq!(units.occupant(v(1, 0)), Some('a'));
+/// assert_eq!(units.position('b'), Some(v(2, 0)));
+/// assert_eq!(units.reservation(v(2, 0)), None);
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct OccupancyGrid<Id> {
+    occupants: Grid<Option<Id>>,
+    reservations: Grid<Option<Id>>,
+    positions: BTreeMap<Id, Vector>,
+    reserved: BTreeMap<Id, Vector>,
+}
+
+impl<Id: Copy + Ord> OccupancyGrid<Id> {
+    /// Constructs a new, empty `OccupancyGrid` with the given dimensions.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    #[track_caller]
+    pub fn new(width: i64, height: i64) -> Self {
+        Self::try_new(width, height).or_panic()
+    }
+
+    /// Constructs a new, empty `OccupancyGrid` with the given dimensions,
+    /// or returns an error if the dimensions are not positive or too large.
+    pub fn try_new(width: i64, height: i64) -> Result<Self, GridError> {
+        let occupants = Grid::try_new(width, height, None)?;
+        Ok(Self {
+            reservations: occupants.clone(),
+            occupants,
+            positions: BTreeMap::new(),
+            reserved: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.occupants.dim()
+    }
+
+    /// Returns the number of entities in the grid.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if there are no entities in the grid, or `false` otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the entity at the given position, or `None` if it is unoccupied or out of bounds.
+    #[inline]
+    pub fn occupant(&self, pos: Vector) -> Option<Id> {
+        self.occupants.get(pos).copied().flatten()
+    }
+
+    /// Returns the entity that has reserved the given position, or `None` if it is unreserved or out of bounds.
+    #[inline]
+    pub fn reservation(&self, pos: Vector) -> Option<Id> {
+        self.reservations.get(pos).copied().flatten()
+    }
+
+    /// Returns the position of the given entity, or `None` if it is not in the grid.
+    #[inline]
+    pub fn position(&self, id: Id) -> Option<Vector> {
+        self.positions.get(&id).copied()
+    }
+
+    /// Returns an iterator over every entity and its position, in order of ID.
+    pub fn iter(&self) -> impl Iterator<Item = (Id, Vector)> + '_ {
+        self.positions.iter().map(|(&id, &pos)| (id, pos))
+    }
+
+    /// Adds an entity to the grid at the given position.
+    pub fn place(&mut self, id: Id, pos: Vector) -> Result<(), MoveError<Id>> {
+        if self.positions.contains_key(&id) {
+            return Err(MoveError::AlreadyPlaced);
+        }
+        self.check_free(id, pos)?;
+        self.occupants[pos] = Some(id);
+        self.positions.insert(id, pos);
+        Ok(())
+    }
+
+    /// Removes an entity from the grid, along with its reservation.
+    ///
+    /// Returns its position, or `None` if it was not in the grid.
+    pub fn remove(&mut self, id: Id) -> Option<Vector> {
+        let pos = self.positions.remove(&id)?;
+        self.occupants[pos] = None;
+        self.release(id);
+        Some(pos)
+    }
+
+    /// Moves an entity to the given position, if it is unoccupied and not reserved by another entity.
+    ///
+    /// If the entity had reserved the position, the reservation is used up.
+    pub fn try_move(&mut self, id: Id, to: Vector) -> Result<(), MoveError<Id>> {
+        let from = self.position(id).ok_or(MoveError::Unknown)?;
+        if from == to {
+            return Ok(());
+        }
+        self.check_free(id, to)?;
+        self.occupants[from] = None;
+        self.occupants[to] = Some(id);
+        self.positions.insert(id, to);
+        if self.reserved.get(&id) == Some(&to) {
+            self.release(id);
+        }
+        Ok(())
+    }
+
+    /// Reserves the given position for an entity, replacing any position it had already reserved.
+    ///
+    /// The position must be unoccupied and not reserved by another entity.
+    pub fn reserve(&mut self, id: Id, pos: Vector) -> Result<(), MoveError<Id>> {
+        if !self.positions.contains_key(&id) {
+            return Err(MoveError::Unknown);
+        }
+        self.check_free(id, pos)?;
+        self.release(id);
+        self.reservations[pos] = Some(id);
+        self.reserved.insert(id, pos);
+        Ok(())
+    }
+
+    /// Cancels the reservation of an entity.
+    ///
+    /// Returns the position it had reserved, or `None` if it had no reservation.
+    pub fn release(&mut self, id: Id) -> Option<Vector> {
+        let pos = self.reserved.remove(&id)?;
+        self.reservations[pos] = None;
+        Some(pos)
+    }
+
+    /// Resolves a set of moves that happen simultaneously, such as every unit's move in one turn, applying those that succeed.
+    ///
+    /// When several entities move to the same position, `choose` is called with that position and the entities,
+    /// in order of ID, and returns the one that gets to move there; the rest fail.
+    /// An entity may move into a position that another entity is moving out of, including around a cycle of three or more,
+    /// but two entities may not swap places, since they would pass through each other.
+    ///
+    /// Returns the moves that failed, in order of ID, each with the reason it failed. If an entity appears more than once
+    /// in `moves`, only its last move is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{occupancy::{MoveError, OccupancyGrid}, prelude::*};
+    ///
+    /// let mut units = OccupancyGrid::new(5, 1);
+    /// units.place(0, v(0, 0)).unwrap();
+    /// units.place(1, v(1, 0)).unwrap();
+    /// units.place(2, v(2, 0)).unwrap();
+    /// units.place(3, v(4, 0)).unwrap();
+    ///
+    /// // 0, 1 and 2 try to shuffle along, but 2 and 3 both want the empty position between them
+    /// let failed = units.resolve_moves(
+    ///     [(0, v(1, 0)), (1, v(2, 0)), (2, v(3, 0)), (3, v(3, 0))],
+    ///     |_, contenders| *contenders.iter().max().unwrap(),
+    /// );
+    ///
+    /// assert_eq!(failed, vec![
+    ///     (0, MoveError::Occupied(1)),
+    ///     (1, MoveError::Occupied(2)),
+    ///     (2, MoveError::Contested(3)),
+    /// ]);
+    /// assert_eq!(units.position(0), Some(v(0, 0)));
+    /// assert_eq!(units.position(3), Some(v(3, 0)));
+    /// ```
+    ///
+    /// Because 2 lost its move, 1 could not move into its position, and so neither could 0.
+    /// Had 2 won instead, all three would have moved along:
+    ///
+    /// ```
+    /// use grid::{occupancy::{MoveError, OccupancyGrid}, prelude::*};
+    ///
+    /// let mut units = OccupancyGrid::new(5, 1);
+    /// units.place(0, v(0, 0)).unwrap();
+    /// units.place(1, v(1, 0)).unwrap();
+    /// units.place(2, v(2, 0)).unwrap();
+    /// units.place(3, v(4, 0)).unwrap();
+    ///
+    /// let failed = units.resolve_moves(
+    ///     [(0, v(1, 0)), (1, v(2, 0)), (2, v(3, 0)), (3, v(3, 0))],
+    ///     |_, contenders| *contenders.iter().min().unwrap(),
+    /// );
+    ///
+    /// assert_eq!(failed, vec![(3, MoveError::Contested(2))]);
+    /// assert_eq!(units.iter().collect::<Vec<_>>(), vec![(0, v(1, 0)), (1, v(2, 0)), (2, v(3, 0)), (3, v(4, 0))]);
+    /// ```
+    pub fn resolve_moves<I, F>(&mut self, moves: I, mut choose: F) -> Vec<(Id, MoveError<Id>)>
+    where
+        I: IntoIterator<Item = (Id, Vector)>,
+        F: FnMut(Vector, &[Id]) -> Id,
+    {
+        let mut pending: BTreeMap<Id, Vector> = BTreeMap::new();
+        let mut failed: BTreeMap<Id, MoveError<Id>> = BTreeMap::new();
+        for (id, to) in moves {
+            pending.insert(id, to);
+        }
+        pending.retain(|&id, &mut to| match self.position(id) {
+            None => {
+                failed.insert(id, MoveError::Unknown);
+                false
+            }
+            Some(from) => from != to,
+        });
+
+        // each position goes to at most one entity
+        let mut claims: BTreeMap<Vector, Vec<Id>> = BTreeMap::new();
+        for (&id, &to) in &pending {
+            claims.entry(to).or_default().push(id);
+        }
+        for (to, contenders) in claims {
+            if contenders.len() > 1 {
+                let winner = choose(to, &contenders);
+                for id in contenders {
+                    if id != winner {
+                        failed.insert(id, MoveError::Contested(winner));
+                    }
+                }
+            }
+        }
+        pending.retain(|id, _| !failed.contains_key(id));
+
+        // positions must be in bounds and not reserved by others
+        pending.retain(|&id, &mut to| match self.reservations.get(to) {
+            None => {
+                failed.insert(id, MoveError::OutOfBounds);
+                false
+            }
+            Some(&Some(other)) if other != id => {
+                failed.insert(id, MoveError::Reserved(other));
+                false
+            }
+            _ => true,
+        });
+
+        // a move into an occupied position succeeds only if the occupant moves away, but not by swapping places
+        loop {
+            let blocked: Vec<(Id, Id)> = pending
+                .iter()
+                .filter_map(|(&id, &to)| {
+                    let other = self.occupant(to)?;
+                    let leaves = pending
+                        .get(&other)
+                        .is_some_and(|&their_to| Some(their_to) != self.position(id));
+                    (!leaves).then_some((id, other))
+                })
+                .collect();
+            if blocked.is_empty() {
+                break;
+            }
+            for (id, other) in blocked {
+                pending.remove(&id);
+                failed.insert(id, MoveError::Occupied(other));
+            }
+        }
+
+        for &id in pending.keys() {
+            let from = self.positions[&id];
+            self.occupants[from] = None;
+        }
+        for (&id, &to) in &pending {
+            self.occupants[to] = Some(id);
+            self.positions.insert(id, to);
+            if self.reserved.get(&id) == Some(&to) {
+                self.release(id);
+            }
+        }
+        failed.into_iter().collect()
+    }
+
+    /// Returns `Ok` if `id` may occupy or reserve `pos`.
+    fn check_free(&self, id: Id, pos: Vector) -> Result<(), MoveError<Id>> {
+        match (self.occupants.get(pos), self.reservations.get(pos)) {
+            (None, _) | (_, None) => Err(MoveError::OutOfBounds),
+            (Some(&Some(other)), _) if other != id => Err(MoveError::Occupied(other)),
+            (_, Some(&Some(other))) if other != id => Err(MoveError::Reserved(other)),
+            _ => Ok(()),
+        }
+    }
+}
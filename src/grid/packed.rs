@@ -0,0 +1,285 @@
+//! A grid of small unsigned integers packed into 1, 2 or 4 bits each.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{try_reserve, try_size, Grid},
+    vector::Vector,
+};
+
+use std::iter::FusedIterator;
+
+/// A 2D grid of small unsigned integers, each stored in `BITS` bits, indexed by `Vector`.
+///
+/// `BITS` must be 1, 2 or 4, allowing values up to `1`, `3` or `15` respectively.
+/// This uses 8, 4 or 2 times less memory than a `Grid<u8>`, at the cost of returning values rather than references.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{packed::PackedGrid, prelude::*};
+///
+/// let mut tiles: PackedGrid<4> = PackedGrid::new(8, 10, 3);
+///
+/// assert_eq!(tiles.set(v(1, 2), 15), Some(3));
+/// assert_eq!(tiles.get(v(1, 2)), Some(15));
+/// assert_eq!(tiles.get(v(0, 2)), Some(3));
+/// assert_eq!(tiles.get(v(8, 2)), None);
+///
+/// let grid: Grid<u8> = tiles.to_grid();
+///
+/// assert_eq!(grid[v(1, 2)], 15);
+/// assert_eq!(PackedGrid::<4>::from_grid(&grid), tiles);
+/// assert_eq!(PackedGrid::<1>::from_grid(&Grid::new(3, 3, 1)), PackedGrid::new(3, 3, 1));
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Hash)]
+pub struct PackedGrid<const BITS: usize> {
+    // the padding bits after the last value are always zero, so the derived traits only compare values
+    raw: Vec<u8>,
+    dim: Vector,
+}
+
+impl<const BITS: usize> PackedGrid<BITS> {
+    /// The largest value that can be stored in each cell.
+    pub const MAX: u8 = ((1u16 << BITS) - 1) as u8;
+
+    const VALID_BITS: () = assert!(
+        BITS == 1 || BITS == 2 || BITS == 4,
+        "BITS must be 1, 2 or 4"
+    );
+
+    const PER_BYTE: usize = 8 / BITS;
+
+    /// Constructs a new `PackedGrid<BITS>` with the given dimensions, initialising all values to `value`.
+    ///
+    /// Panics if the dimensions are not positive or too large, or if `value` is greater than [`Self::MAX`].
+    #[track_caller]
+    pub fn new(width: i64, height: i64, value: u8) -> Self {
+        Self::try_new(width, height, value).or_panic()
+    }
+
+    /// Constructs a new `PackedGrid<BITS>` with the given dimensions, initialising all values to `value`,
+    /// or returns an error if the dimensions are not positive or too large, or if `value` is greater than [`Self::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{packed::PackedGrid, prelude::*};
+    ///
+    /// assert!(PackedGrid::<2>::try_new(4, 4, 3).is_ok());
+    /// assert!(PackedGrid::<2>::try_new(4, 4, 4).is_err());
+    /// assert_eq!(PackedGrid::<2>::try_new(0, 4, 0), Err(GridError::InvalidDimensions { width: 0, height: 4 }));
+    /// ```
+    pub fn try_new(width: i64, height: i64, value: u8) -> Result<Self, GridError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_BITS;
+        Self::check_value(value)?;
+        let size = try_size::<u8>(width, height)?;
+        let len = size.div_ceil(Self::PER_BYTE);
+        let mut raw = try_reserve(len, width, height)?;
+        raw.resize(len, value * (u8::MAX / Self::MAX));
+        let used = size % Self::PER_BYTE;
+        if used != 0 {
+            *raw.last_mut().unwrap() &= (1 << (used * BITS)) - 1;
+        }
+        Ok(Self {
+            raw,
+            dim: Vector::new(width, height),
+        })
+    }
+
+    /// Constructs a new `PackedGrid<BITS>` with the same dimensions and values as `grid`.
+    ///
+    /// Panics if any value is greater than [`Self::MAX`].
+    #[track_caller]
+    pub fn from_grid(grid: &Grid<u8>) -> Self {
+        Self::try_from_grid(grid).or_panic()
+    }
+
+    /// Constructs a new `PackedGrid<BITS>` with the same dimensions and values as `grid`,
+    /// or returns an error if any value is greater than [`Self::MAX`] or if the allocation fails.
+    ///
+    /// Unlike [`try_new`](Self::try_new), this accepts a grid with no values, such as one with a width of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{packed::PackedGrid, prelude::*};
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[0, 1], [1, 2]]);
+    ///
+    /// assert!(PackedGrid::<2>::try_from_grid(&grid).is_ok());
+    /// assert!(PackedGrid::<1>::try_from_grid(&grid).is_err());
+    ///
+    /// let empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// let packed = PackedGrid::<4>::try_from_grid(&empty).unwrap();
+    ///
+    /// assert_eq!(packed.dim(), v(0, 2));
+    /// assert_eq!(packed.to_grid(), empty);
+    /// ```
+    pub fn try_from_grid(grid: &Grid<u8>) -> Result<Self, GridError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::VALID_BITS;
+        let len = grid.raw.len().div_ceil(Self::PER_BYTE);
+        let mut raw = try_reserve(len, grid.width(), grid.height())?;
+        raw.resize(len, 0);
+        let mut packed = Self { raw, dim: grid.dim };
+        for (i, &value) in grid.iter().enumerate() {
+            Self::check_value(value)?;
+            packed.write(i, value);
+        }
+        Ok(packed)
+    }
+
+    /// Returns a new `Grid<u8>` with the same dimensions and values as `self`.
+    pub fn to_grid(&self) -> Grid<u8> {
+        Grid {
+            raw: self.iter().collect(),
+            dim: self.dim,
+        }
+    }
+
+    /// Returns the width of the grid.
+    #[inline]
+    pub fn width(&self) -> i64 {
+        self.dim.x
+    }
+
+    /// Returns the height of the grid.
+    #[inline]
+    pub fn height(&self) -> i64 {
+        self.dim.y
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.dim
+    }
+
+    /// Returns `true` if the given position is within the bounds of the grid, or `false` otherwise.
+    pub fn in_bounds(&self, pos: Vector) -> bool {
+        (0..self.width()).contains(&pos.x) && (0..self.height()).contains(&pos.y)
+    }
+
+    /// Returns the value at the given position of the grid, or `None` if out of bounds.
+    pub fn get(&self, pos: Vector) -> Option<u8> {
+        Some(self.read(self.get_index(pos)?))
+    }
+
+    /// Sets the value at the given position of the grid.
+    ///
+    /// Returns the old value at that position, or `None` if out of bounds.
+    ///
+    /// Panics if `value` is greater than [`Self::MAX`].
+    #[track_caller]
+    pub fn set(&mut self, pos: Vector, value: u8) -> Option<u8> {
+        self.try_set(pos, value).or_panic()
+    }
+
+    /// Sets the value at the given position of the grid, as in [`set`](Self::set),
+    /// or returns an error if `value` is greater than [`Self::MAX`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{packed::PackedGrid, prelude::*};
+    ///
+    /// let mut grid: PackedGrid<1> = PackedGrid::new(2, 2, 0);
+    ///
+    /// assert_eq!(grid.try_set(v(1, 1), 1), Ok(Some(0)));
+    /// assert_eq!(grid.try_set(v(2, 1), 1), Ok(None));
+    /// assert!(grid.try_set(v(1, 1), 2).is_err());
+    /// ```
+    pub fn try_set(&mut self, pos: Vector, value: u8) -> Result<Option<u8>, GridError> {
+        Self::check_value(value)?;
+        let Some(index) = self.get_index(pos) else {
+            return Ok(None);
+        };
+        let old = self.read(index);
+        self.write(index, value);
+        Ok(Some(old))
+    }
+
+    /// Returns an iterator over the values in the grid, in row-major order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::packed::PackedGrid;
+    ///
+    /// let grid: PackedGrid<1> = PackedGrid::new(5, 3, 1);
+    ///
+    /// assert_eq!(grid.iter().len(), 15);
+    /// assert_eq!(grid.iter().map(u32::from).sum::<u32>(), 15);
+    /// ```
+    pub fn iter(&self) -> PackedIter<'_, BITS> {
+        PackedIter {
+            grid: self,
+            index: 0,
+            end: (self.width() * self.height()) as usize,
+        }
+    }
+
+    fn get_index(&self, pos: Vector) -> Option<usize> {
+        self.in_bounds(pos)
+            .then(|| pos.x as usize + ((pos.y as usize) * (self.width() as usize)))
+    }
+
+    #[inline]
+    fn read(&self, index: usize) -> u8 {
+        let shift = (index % Self::PER_BYTE) * BITS;
+        (self.raw[index / Self::PER_BYTE] >> shift) & Self::MAX
+    }
+
+    #[inline]
+    fn write(&mut self, index: usize, value: u8) {
+        let shift = (index % Self::PER_BYTE) * BITS;
+        let byte = &mut self.raw[index / Self::PER_BYTE];
+        *byte = (*byte & !(Self::MAX << shift)) | (value << shift);
+    }
+
+    fn check_value(value: u8) -> Result<(), GridError> {
+        if value > Self::MAX {
+            return Err(GridError::InvalidArgument(
+                "value too large for the number of bits",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// An iterator over the values in a [`PackedGrid`], in row-major order.
+pub struct PackedIter<'a, const BITS: usize> {
+    grid: &'a PackedGrid<BITS>,
+    index: usize,
+    end: usize,
+}
+
+impl<'a, const BITS: usize> Iterator for PackedIter<'a, BITS> {
+    type Item = u8;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index == self.end {
+            return None;
+        }
+        self.index += 1;
+        Some(self.grid.read(self.index - 1))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.end.min(self.index.saturating_add(n));
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.index;
+        (len, Some(len))
+    }
+}
+
+impl<'a, const BITS: usize> ExactSizeIterator for PackedIter<'a, BITS> {}
+
+impl<'a, const BITS: usize> FusedIterator for PackedIter<'a, BITS> {}
@@ -0,0 +1,155 @@
+//! Packing of rectangles into the free space of a `Grid<bool>`.
+
+use crate::{grid::Grid, rect::Rect, vector::Vector};
+
+impl Grid<bool> {
+    /// Places rectangles of the given dimensions into the free space of the grid, where `false` is free and `true` is occupied,
+    /// marking the positions of each placed rectangle as occupied.
+    ///
+    /// Returns the placement of each rectangle, in the same order as `dims`, or `None` for each rectangle that did not fit.
+    ///
+    /// This is a guillotine packer. The free space is first divided into disjoint free rectangles,
+    /// then rectangles are placed largest first, each at the top-left corner of the free rectangle it fits most tightly,
+    /// by the shorter of the two leftover sides, with ties going to the free rectangle nearest the top-left of the grid.
+    /// What is left of that free rectangle is cut in two along the shorter leftover side, so the larger piece stays whole,
+    /// and free rectangles are merged again wherever two of them line up exactly.
+    ///
+    /// Since a rectangle must fit inside a single free rectangle, one can fail to fit even though enough free positions
+    /// are next to each other, but each placement only takes time in the number of free rectangles, not the size of the grid.
+    /// Rectangles with a non-positive dimension are never placed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut atlas: Grid<bool> = Grid::new(7, 4, false);
+    /// atlas[v(0, 0)] = true;
+    ///
+    /// let placements = atlas.pack(&[v(2, 2), v(4, 3), v(3, 3), v(9, 1)]);
+    ///
+    /// assert_eq!(placements, vec![
+    ///     Some(Rect::new(v(5, 0), v(2, 2))),
+    ///     Some(Rect::new(v(1, 0), v(4, 3))),
+    ///     None,
+    ///     None,
+    /// ]);
+    /// assert_eq!(atlas.iter().filter(|&&occupied| occupied).count(), 1 + 12 + 4);
+    ///
+    /// // an empty grid is a single free rectangle, which a perfect fit fills exactly
+    /// let mut sheet: Grid<bool> = Grid::new(6, 4, false);
+    /// let placements = sheet.pack(&[v(2, 2); 6]);
+    ///
+    /// assert!(placements.iter().all(Option::is_some));
+    /// assert!(sheet.iter().all(|&occupied| occupied));
+    /// ```
+    pub fn pack(&mut self, dims: &[Vector]) -> Vec<Option<Rect>> {
+        let mut order: Vec<usize> = (0..dims.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse((dims[i].x * dims[i].y, dims[i].y)));
+        let mut placements = vec![None; dims.len()];
+        let mut free = free_rects(self);
+        for i in order {
+            let dim = dims[i];
+            if dim.x <= 0 || dim.y <= 0 {
+                continue;
+            }
+            let best = free
+                .iter()
+                .enumerate()
+                .filter(|(_, rect)| rect.dim.x >= dim.x && rect.dim.y >= dim.y)
+                .min_by_key(|(_, rect)| {
+                    let leftover = rect.dim - dim;
+                    let (short, long) = (leftover.x.min(leftover.y), leftover.x.max(leftover.y));
+                    (short, long, rect.pos.y, rect.pos.x)
+                })
+                .map(|(index, _)| index);
+            let Some(index) = best else {
+                continue;
+            };
+            let container = free.swap_remove(index);
+            let rect = Rect::new(container.pos, dim);
+            for pos in rect.positions() {
+                self[pos] = true;
+            }
+            free.extend(
+                split(container, dim)
+                    .into_iter()
+                    .filter(|rect| !rect.is_empty()),
+            );
+            merge(&mut free);
+            placements[i] = Some(rect);
+        }
+        placements
+    }
+}
+
+/// Divides the free positions of the grid into disjoint rectangles, each as wide and then as tall as possible,
+/// starting from the first free position in row-major order that is not yet covered.
+fn free_rects(grid: &Grid<bool>) -> Vec<Rect> {
+    let mut covered = grid.clone();
+    let mut free = Vec::new();
+    for y in 0..grid.dim.y {
+        for x in 0..grid.dim.x {
+            if covered[Vector::new(x, y)] {
+                continue;
+            }
+            let width = (x..grid.dim.x)
+                .take_while(|&x| !covered[Vector::new(x, y)])
+                .count() as i64;
+            let height = (y..grid.dim.y)
+                .take_while(|&y| (x..x + width).all(|x| !covered[Vector::new(x, y)]))
+                .count() as i64;
+            let rect = Rect::new(Vector::new(x, y), Vector::new(width, height));
+            for pos in rect.positions() {
+                covered[pos] = true;
+            }
+            free.push(rect);
+        }
+    }
+    free
+}
+
+/// Returns the two free rectangles left over after placing a rectangle of dimensions `dim` at the top-left corner of `container`,
+/// cutting along the shorter leftover side so that the larger leftover rectangle is as large as possible.
+fn split(container: Rect, dim: Vector) -> [Rect; 2] {
+    let leftover = container.dim - dim;
+    let (right_height, below_width) = if leftover.x < leftover.y {
+        (dim.y, container.dim.x)
+    } else {
+        (container.dim.y, dim.x)
+    };
+    [
+        Rect::new(
+            container.pos + Vector::new(dim.x, 0),
+            Vector::new(leftover.x, right_height),
+        ),
+        Rect::new(
+            container.pos + Vector::new(0, dim.y),
+            Vector::new(below_width, leftover.y),
+        ),
+    ]
+}
+
+/// Merges pairs of free rectangles that share a whole side into single rectangles, until no more pairs can be merged.
+fn merge(free: &mut Vec<Rect>) {
+    let mut merged = true;
+    while merged {
+        merged = false;
+        'search: for i in 0..free.len() {
+            for j in 0..free.len() {
+                let (a, b) = (free[i], free[j]);
+                let joined = if a.pos.x == b.pos.x && a.dim.x == b.dim.x && a.max().y == b.pos.y {
+                    Rect::new(a.pos, Vector::new(a.dim.x, a.dim.y + b.dim.y))
+                } else if a.pos.y == b.pos.y && a.dim.y == b.dim.y && a.max().x == b.pos.x {
+                    Rect::new(a.pos, Vector::new(a.dim.x + b.dim.x, a.dim.y))
+                } else {
+                    continue;
+                };
+                free[i] = joined;
+                free.swap_remove(j);
+                merged = true;
+                break 'search;
+            }
+        }
+    }
+}
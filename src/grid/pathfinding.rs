@@ -0,0 +1,663 @@
+//! Finding shortest paths between positions of a grid.
+//!
+//! Searches are deterministic: for the same inputs, the same path is returned on every run and every platform.
+//! Where several paths are equally short, which one is returned depends only on the inputs,
+//! or on a seed given to [`Searcher::with_tie_break`].
+
+pub mod hpa;
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{heap::GridHeap, Grid},
+    rng::Rng,
+    trace::{event, span},
+    vector::{
+        constants::{ADJACENT, ORTHOGONAL},
+        Vector,
+    },
+};
+
+/// A path between two positions of a grid, along with its total cost.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Path {
+    positions: Vec<Vector>,
+    cost: u64,
+}
+
+impl Path {
+    /// Returns the positions along the path, from the start to the goal inclusive.
+    #[inline]
+    pub fn positions(&self) -> &[Vector] {
+        &self.positions
+    }
+
+    /// Returns the positions along the path, consuming the path.
+    #[inline]
+    pub fn into_positions(self) -> Vec<Vector> {
+        self.positions
+    }
+
+    /// Returns the total cost of the path, which is the sum of the costs of entering each position after the start.
+    #[inline]
+    pub fn cost(&self) -> u64 {
+        self.cost
+    }
+
+    /// Returns the first position of the path.
+    #[inline]
+    pub fn start(&self) -> Vector {
+        self.positions[0]
+    }
+
+    /// Returns the last position of the path.
+    #[inline]
+    pub fn goal(&self) -> Vector {
+        self.positions[self.positions.len() - 1]
+    }
+
+    /// Returns the waypoints of a smoothed version of the path, with every unnecessary waypoint removed.
+    ///
+    /// Starting from the start of the path, each waypoint is joined to the furthest later position on the path
+    /// that it has [line of sight](Grid::line_of_sight) to, where `passable` determines which values can be moved through.
+    /// This is known as string pulling, and turns the staircase patterns of grid paths into straight lines.
+    ///
+    /// The first and last waypoints are always the start and goal of the path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::astar, prelude::*};
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "......".chars(),
+    ///     "......".chars(),
+    ///     "..##..".chars(),
+    ///     "......".chars(),
+    /// ]);
+    ///
+    /// let path = astar(grid.dim(), v(0, 3), v(5, 0), |pos| (grid[pos] != '#').then_some(1)).unwrap();
+    /// assert_eq!(path.positions().len(), 9);
+    ///
+    /// let waypoints = path.smooth(&grid, |&c| c != '#');
+    ///
+    /// assert_eq!(waypoints.len(), 3);
+    /// assert_eq!(waypoints[0], v(0, 3));
+    /// assert_eq!(waypoints[2], v(5, 0));
+    /// ```
+    pub fn smooth<T, F>(&self, grid: &Grid<T>, mut passable: F) -> Vec<Vector>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut waypoints = vec![self.start()];
+        let mut anchor = self.start();
+        for window in self.positions.windows(2) {
+            let (previous, pos) = (window[0], window[1]);
+            if previous != anchor && !grid.line_of_sight(anchor, pos, &mut passable) {
+                waypoints.push(previous);
+                anchor = previous;
+            }
+        }
+        if self.goal() != anchor {
+            waypoints.push(self.goal());
+        }
+        waypoints
+    }
+}
+
+/// The state of a [`Searcher`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchState {
+    /// The search has not finished, and needs more steps.
+    Searching,
+    /// A path to the goal has been found, and can be retrieved with [`Searcher::path`].
+    Found,
+    /// Every position reachable from the start has been searched, and the goal was not among them.
+    NotFound,
+}
+
+/// What happened to a position during a search, as reported to the hook set with [`Searcher::on_visit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Visit {
+    /// The position was reached by a shorter route than before, and added to the positions waiting to be expanded.
+    Opened,
+    /// The position was expanded: its shortest distance from the start is now known, and its neighbours are considered next.
+    Closed,
+}
+
+/// The moves allowed when finding a path, and the cost of each move, shared by every pathfinding entry point.
+///
+/// The cost of a move is the cost of the move itself multiplied by the cost of entering the destination position.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{pathfinding::{MovementRules, Searcher}, prelude::*};
+///
+/// let grid: Grid<char> = Grid::from_nested_iter([
+///     ".#..".chars(),
+///     "....".chars(),
+///     "....".chars(),
+///     "....".chars(),
+/// ]);
+///
+/// let cost = |pos| (grid[pos] != '#').then_some(1);
+///
+/// let path = Searcher::new(grid.dim(), v(0, 0), v(3, 3), cost)
+///     .with_rules(MovementRules::OCTILE)
+///     .finish()
+///     .unwrap();
+///
+/// // one orthogonal move to get around the wall without cutting its corner, then diagonals
+/// assert_eq!(path.cost(), 10 + 14 * 2 + 10);
+/// assert_eq!(path.positions().len(), 5);
+///
+/// let path = Searcher::new(grid.dim(), v(0, 0), v(3, 3), cost)
+///     .with_rules(MovementRules::OCTILE.with_corner_cutting(true))
+///     .finish()
+///     .unwrap();
+///
+/// assert_eq!(path.cost(), 14 * 3);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MovementRules {
+    /// Whether diagonal moves are allowed, in addition to orthogonal moves.
+    pub diagonals: bool,
+    /// The cost of an orthogonal move.
+    pub orthogonal_cost: u64,
+    /// The cost of a diagonal move.
+    pub diagonal_cost: u64,
+    /// Whether a diagonal move is allowed when either of the two orthogonal positions beside it cannot be entered.
+    pub corner_cutting: bool,
+}
+
+impl MovementRules {
+    /// Orthogonal moves only, each costing `1`.
+    pub const FOUR_WAY: Self = Self {
+        diagonals: false,
+        orthogonal_cost: 1,
+        diagonal_cost: 1,
+        corner_cutting: false,
+    };
+
+    /// Orthogonal and diagonal moves, each costing `1`, without cutting corners.
+    pub const EIGHT_WAY: Self = Self {
+        diagonals: true,
+        orthogonal_cost: 1,
+        diagonal_cost: 1,
+        corner_cutting: false,
+    };
+
+    /// Orthogonal moves costing `10` and diagonal moves costing `14`, approximating Euclidean distance, without cutting corners.
+    pub const OCTILE: Self = Self {
+        diagonals: true,
+        orthogonal_cost: 10,
+        diagonal_cost: 14,
+        corner_cutting: false,
+    };
+
+    /// Returns `self` with corner cutting allowed or disallowed.
+    #[inline]
+    pub const fn with_corner_cutting(mut self, corner_cutting: bool) -> Self {
+        self.corner_cutting = corner_cutting;
+        self
+    }
+
+    /// Returns the offsets of the allowed moves.
+    #[inline]
+    pub fn moves(&self) -> &'static [Vector] {
+        if self.diagonals {
+            &ADJACENT
+        } else {
+            &ORTHOGONAL
+        }
+    }
+
+    /// Returns the cost of a single move by `offset`, which must be one of the allowed [moves](Self::moves).
+    #[inline]
+    pub fn move_cost(&self, offset: Vector) -> u64 {
+        if offset.x != 0 && offset.y != 0 {
+            self.diagonal_cost
+        } else {
+            self.orthogonal_cost
+        }
+    }
+
+    /// Returns the lowest possible cost of moving from `from` to `to` with these rules, if every position has a cost of `1`.
+    ///
+    /// This never overestimates, so it can be used as an A* heuristic.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::MovementRules, prelude::*};
+    ///
+    /// assert_eq!(MovementRules::FOUR_WAY.heuristic(v(0, 0), v(3, -5)), 8);
+    /// assert_eq!(MovementRules::EIGHT_WAY.heuristic(v(0, 0), v(3, -5)), 5);
+    /// assert_eq!(MovementRules::OCTILE.heuristic(v(0, 0), v(3, -5)), 14 * 3 + 10 * 2);
+    /// ```
+    pub fn heuristic(&self, from: Vector, to: Vector) -> u64 {
+        let diff = (to - from).abs();
+        if !self.diagonals {
+            return (diff.x + diff.y) as u64 * self.orthogonal_cost;
+        }
+        let (long, short) = (diff.x.max(diff.y) as u64, diff.x.min(diff.y) as u64);
+        let diagonal_cost = self.diagonal_cost.min(2 * self.orthogonal_cost);
+        (long - short) * self.orthogonal_cost + short * diagonal_cost
+    }
+
+    /// Returns `true` if a move from `pos` by `offset` does not cut a corner that it is not allowed to,
+    /// where `open` determines which positions can be entered.
+    pub(crate) fn can_cut<F>(&self, pos: Vector, offset: Vector, mut open: F) -> bool
+    where
+        F: FnMut(Vector) -> bool,
+    {
+        self.corner_cutting
+            || offset.x == 0
+            || offset.y == 0
+            || (open(pos + offset.horizontal()) && open(pos + offset.vertical()))
+    }
+}
+
+impl Default for MovementRules {
+    fn default() -> Self {
+        Self::FOUR_WAY
+    }
+}
+
+/// A resumable A* search for a shortest path between two positions of a grid.
+///
+/// The cost of entering each position is given by a function, which returns `None` for positions that cannot be entered.
+/// By default the search only moves orthogonally, and other moves can be allowed with [`Searcher::with_rules`].
+/// The [`MovementRules::heuristic`] is used, so every cost should be at least `1` for the path to be optimal.
+///
+/// Rather than running to completion, the search can be advanced a limited number of steps at a time with [`Searcher::step`],
+/// so that an expensive search can be spread across several frames of a game.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{pathfinding::{Searcher, SearchState}, prelude::*};
+///
+/// let maze: Grid<char> = Grid::from_nested_iter([
+///     "..#....".chars(),
+///     "..#.##.".chars(),
+///     "....#..".chars(),
+///     "###.#.#".chars(),
+///     "....#..".chars(),
+/// ]);
+///
+/// let mut searcher = Searcher::new(maze.dim(), v(0, 0), v(6, 4), |pos| {
+///     (maze[pos] != '#').then_some(1)
+/// });
+///
+/// let mut frames = 1;
+/// while searcher.step(5) == SearchState::Searching {
+///     frames += 1;
+/// }
+///
+/// assert!(frames > 1);
+/// assert_eq!(searcher.state(), SearchState::Found);
+///
+/// let path = searcher.path().unwrap();
+/// assert_eq!(path.cost(), 16);
+/// assert_eq!(path.positions().len(), 17);
+/// assert_eq!(path.goal(), v(6, 4));
+/// ```
+pub struct Searcher<F, V = fn(Vector, Visit)> {
+    cost: F,
+    visit: V,
+    rules: MovementRules,
+    start: Vector,
+    goal: Vector,
+    open: GridHeap<(u64, u64, u64)>,
+    tie_break: Option<u64>,
+    dist: Grid<u64>,
+    came_from: Grid<Option<Vector>>,
+    closed: Grid<bool>,
+    state: SearchState,
+    expanded: usize,
+}
+
+impl<F> Searcher<F>
+where
+    F: FnMut(Vector) -> Option<u64>,
+{
+    /// Constructs a new `Searcher` for a path from `start` to `goal` within a grid of the given dimensions,
+    /// where `cost(pos)` is the cost of entering `pos`, or `None` if it cannot be entered.
+    ///
+    /// The cost of the start position is never queried.
+    /// A path whose total cost would not fit in a `u64` is never taken.
+    ///
+    /// Panics if the dimensions are not positive or too large, or if `start` or `goal` are out of bounds.
+    #[track_caller]
+    pub fn new(dim: Vector, start: Vector, goal: Vector, cost: F) -> Self {
+        Self::try_new(dim, start, goal, cost).or_panic()
+    }
+
+    /// Constructs a new `Searcher` for a path from `start` to `goal`, as in [`new`](Self::new),
+    /// or returns an error if the dimensions are not positive or too large, or if `start` or `goal` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::Searcher, prelude::*};
+    ///
+    /// assert!(Searcher::try_new(v(3, 3), v(0, 0), v(2, 2), |_| Some(1)).is_ok());
+    /// assert_eq!(
+    ///     Searcher::try_new(v(3, 3), v(0, 0), v(3, 0), |_| Some(1)).err(),
+    ///     Some(GridError::OutOfBounds { dim: v(3, 3), region: Rect::new(v(3, 0), v(1, 1)) }),
+    /// );
+    /// assert!(Searcher::try_new(v(1 << 31, 1 << 31), v(0, 0), v(2, 2), |_| Some(1)).is_err());
+    ///
+    /// // the only path costs more than fits in a `u64`
+    /// let mut searcher = Searcher::try_new(v(3, 1), v(0, 0), v(2, 0), |_| Some(u64::MAX / 2 + 1)).unwrap();
+    /// assert_eq!(searcher.finish(), None);
+    /// ```
+    pub fn try_new(dim: Vector, start: Vector, goal: Vector, cost: F) -> Result<Self, GridError> {
+        let mut dist = Grid::try_new(dim.x, dim.y, u64::MAX)?;
+        for pos in [start, goal] {
+            if !dist.in_bounds(pos) {
+                return Err(GridError::out_of_bounds(dim, pos));
+            }
+        }
+        dist[start] = 0;
+        let mut searcher = Self {
+            cost,
+            visit: |_, _| {},
+            rules: MovementRules::FOUR_WAY,
+            start,
+            goal,
+            open: GridHeap::try_new(dim.x, dim.y)?,
+            tie_break: None,
+            dist,
+            came_from: Grid::try_new(dim.x, dim.y, None)?,
+            closed: Grid::try_new(dim.x, dim.y, false)?,
+            state: SearchState::Searching,
+            expanded: 0,
+        };
+        searcher.open.push(start, searcher.priority(start, 0));
+        Ok(searcher)
+    }
+}
+
+impl<F, V> Searcher<F, V>
+where
+    F: FnMut(Vector) -> Option<u64>,
+    V: FnMut(Vector, Visit),
+{
+    /// Returns `self` with `hook` called with each position as it is opened or closed, so the search can be animated or debugged.
+    ///
+    /// The start position is open from the beginning, so it is only reported when it is closed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::{Searcher, Visit}, prelude::*};
+    ///
+    /// let mut log = Vec::new();
+    ///
+    /// let path = Searcher::new(v(3, 1), v(0, 0), v(2, 0), |_| Some(1))
+    ///     .on_visit(|pos, visit| log.push((pos, visit)))
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(path.cost(), 2);
+    /// assert_eq!(log, vec![
+    ///     (v(0, 0), Visit::Closed),
+    ///     (v(1, 0), Visit::Opened),
+    ///     (v(1, 0), Visit::Closed),
+    ///     (v(2, 0), Visit::Opened),
+    ///     (v(2, 0), Visit::Closed),
+    /// ]);
+    /// ```
+    pub fn on_visit<H>(self, hook: H) -> Searcher<F, H>
+    where
+        H: FnMut(Vector, Visit),
+    {
+        Searcher {
+            cost: self.cost,
+            visit: hook,
+            rules: self.rules,
+            start: self.start,
+            goal: self.goal,
+            open: self.open,
+            tie_break: self.tie_break,
+            dist: self.dist,
+            came_from: self.came_from,
+            closed: self.closed,
+            state: self.state,
+            expanded: self.expanded,
+        }
+    }
+
+    /// Returns `self` with the given rules for moving between positions, instead of [`MovementRules::FOUR_WAY`].
+    ///
+    /// Panics if the search has already started.
+    #[track_caller]
+    pub fn with_rules(mut self, rules: MovementRules) -> Self {
+        if self.expanded > 0 {
+            panic!("movement rules must be set before the search starts");
+        }
+        self.rules = rules;
+        self.open
+            .change_priority(self.start, self.priority(self.start, 0));
+        self
+    }
+
+    /// Returns `self` with ties between equally promising positions broken pseudorandomly using the given seed,
+    /// so that different seeds can choose between equally short paths differently.
+    ///
+    /// Without a seed, ties are broken in a fixed order. Either way, the same inputs always give the same path.
+    ///
+    /// Panics if the search has already started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::Searcher, prelude::*};
+    ///
+    /// let search = |seed| {
+    ///     Searcher::new(v(8, 8), v(0, 0), v(7, 7), |_| Some(1))
+    ///         .with_tie_break(seed)
+    ///         .finish()
+    ///         .unwrap()
+    /// };
+    ///
+    /// let paths: Vec<_> = (0..8).map(search).collect();
+    ///
+    /// assert!(paths.iter().all(|path| path.cost() == 14));
+    /// assert!(paths.iter().any(|path| path != &paths[0]));
+    /// assert_eq!(search(3), paths[3]);
+    /// ```
+    #[track_caller]
+    pub fn with_tie_break(mut self, seed: u64) -> Self {
+        if self.expanded > 0 {
+            panic!("tie breaking must be set before the search starts");
+        }
+        self.tie_break = Some(seed);
+        self.open
+            .change_priority(self.start, self.priority(self.start, 0));
+        self
+    }
+
+    /// Returns the rules for moving between positions.
+    #[inline]
+    pub fn rules(&self) -> MovementRules {
+        self.rules
+    }
+
+    /// Returns the start position of the search.
+    #[inline]
+    pub fn start(&self) -> Vector {
+        self.start
+    }
+
+    /// Returns the goal position of the search.
+    #[inline]
+    pub fn goal(&self) -> Vector {
+        self.goal
+    }
+
+    /// Returns the current state of the search.
+    #[inline]
+    pub fn state(&self) -> SearchState {
+        self.state
+    }
+
+    /// Returns the number of positions expanded so far.
+    #[inline]
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    /// Advances the search by expanding at most `budget` positions, and returns the new state of the search.
+    ///
+    /// Once the search has finished, calling this has no effect.
+    pub fn step(&mut self, budget: usize) -> SearchState {
+        span!("searcher_step", budget);
+        for _ in 0..budget {
+            if self.state != SearchState::Searching {
+                break;
+            }
+            self.expand();
+        }
+        event!(expanded = self.expanded, state = ?self.state, "search stepped");
+        self.state
+    }
+
+    /// Runs the search to completion, and returns the path to the goal, or `None` if there is no such path.
+    pub fn finish(&mut self) -> Option<Path> {
+        span!("searcher_finish");
+        while self.state == SearchState::Searching {
+            self.expand();
+        }
+        event!(expanded = self.expanded, state = ?self.state, "search finished");
+        self.path()
+    }
+
+    /// Returns the shortest path from the start to the goal if it has been found, or `None` otherwise.
+    pub fn path(&self) -> Option<Path> {
+        if self.state != SearchState::Found {
+            return None;
+        }
+        let mut positions = vec![self.goal];
+        let mut pos = self.goal;
+        while let Some(previous) = self.came_from[pos] {
+            positions.push(previous);
+            pos = previous;
+        }
+        positions.reverse();
+        Some(Path {
+            positions,
+            cost: self.dist[self.goal],
+        })
+    }
+
+    fn expand(&mut self) {
+        let Some((pos, _)) = self.open.pop() else {
+            self.state = SearchState::NotFound;
+            return;
+        };
+        self.closed[pos] = true;
+        self.expanded += 1;
+        (self.visit)(pos, Visit::Closed);
+        if pos == self.goal {
+            self.state = SearchState::Found;
+            return;
+        }
+        for &offset in self.rules.moves() {
+            let next = pos + offset;
+            if self.closed.get(next) != Some(&false) {
+                continue;
+            }
+            let Some(cost) = (self.cost)(next) else {
+                continue;
+            };
+            let (rules, closed, cost_fn) = (self.rules, &self.closed, &mut self.cost);
+            if !rules.can_cut(pos, offset, |side| {
+                closed.in_bounds(side) && cost_fn(side).is_some()
+            }) {
+                continue;
+            }
+            let Some(dist) = cost
+                .checked_mul(self.rules.move_cost(offset))
+                .and_then(|cost| self.dist[pos].checked_add(cost))
+            else {
+                continue;
+            };
+            if dist < self.dist[next] {
+                self.dist[next] = dist;
+                self.came_from[next] = Some(pos);
+                self.open.push(next, self.priority(next, dist));
+                (self.visit)(next, Visit::Opened);
+            }
+        }
+    }
+
+    /// Returns the priority of a position reached at the given distance from the start:
+    /// the estimated total cost, then the estimated remaining cost, then the tie break.
+    fn priority(&self, pos: Vector, dist: u64) -> (u64, u64, u64) {
+        let h = self.rules.heuristic(pos, self.goal);
+        let tie = self.tie_break.map_or(0, |seed| {
+            let index = pos.x as u64 + pos.y as u64 * self.dist.width() as u64;
+            Rng::new(seed ^ index.wrapping_mul(0x9e37_79b9_7f4a_7c15)).next_u64()
+        });
+        (dist.saturating_add(h), h, tie)
+    }
+}
+
+/// Returns a shortest path from `start` to `goal` within a grid of the given dimensions, or `None` if there is no such path,
+/// where `cost(pos)` is the cost of entering `pos`, or `None` if it cannot be entered.
+///
+/// This runs a [`Searcher`] with [`MovementRules::FOUR_WAY`] to completion.
+///
+/// Panics if the dimensions are not positive or too large, or if `start` or `goal` are out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{pathfinding::astar, prelude::*};
+///
+/// let costs: Grid<u64> = Grid::from_nested_iter([
+///     [1, 1, 5],
+///     [1, 9, 1],
+///     [1, 1, 1],
+/// ]);
+///
+/// let path = astar(costs.dim(), v(0, 0), v(2, 2), |pos| Some(costs[pos])).unwrap();
+///
+/// assert_eq!(path.cost(), 4);
+/// assert_eq!(path.positions(), &[v(0, 0), v(0, 1), v(0, 2), v(1, 2), v(2, 2)]);
+///
+/// assert_eq!(astar(costs.dim(), v(0, 0), v(2, 2), |pos| (pos.x < 2).then_some(1)), None);
+/// ```
+#[track_caller]
+pub fn astar<F>(dim: Vector, start: Vector, goal: Vector, cost: F) -> Option<Path>
+where
+    F: FnMut(Vector) -> Option<u64>,
+{
+    Searcher::new(dim, start, goal, cost).finish()
+}
+
+/// Returns a shortest path from `start` to `goal`, as in [`astar`], or an error if the dimensions are not positive or too large,
+/// or if `start` or `goal` are out of bounds.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{pathfinding::try_astar, prelude::*};
+///
+/// assert_eq!(try_astar(v(3, 1), v(0, 0), v(2, 0), |_| Some(1)).unwrap().unwrap().cost(), 2);
+/// assert!(try_astar(v(3, 1), v(0, 0), v(0, 1), |_| Some(1)).is_err());
+/// ```
+pub fn try_astar<F>(
+    dim: Vector,
+    start: Vector,
+    goal: Vector,
+    cost: F,
+) -> Result<Option<Path>, GridError>
+where
+    F: FnMut(Vector) -> Option<u64>,
+{
+    Ok(Searcher::try_new(dim, start, goal, cost)?.finish())
+}
@@ -0,0 +1,575 @@
+//! Hierarchical pathfinding over clusters of a grid, with cached portals between them.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{
+        pathfinding::{MovementRules, Path},
+        Grid,
+    },
+    rect::Rect,
+    trace::{event, span},
+    vector::{constants::ADJACENT, Vector},
+};
+
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, BinaryHeap},
+};
+
+/// A hierarchical pathfinding (HPA*) structure over the passable positions of a grid.
+///
+/// The grid is partitioned into square clusters. Wherever passable positions line up on either side of the border between two clusters,
+/// a portal is placed in the middle of each contiguous run, and the distances between the portals within each cluster are cached.
+/// Queries search this much smaller graph of portals, then refine the result into a full path one cluster at a time,
+/// so the work done per query depends far less on the size of the grid than a flat search.
+///
+/// Moves follow the [`MovementRules`] of the map, and positions cannot be entered at different costs.
+/// Since paths between clusters pass through portals, they are not always the shortest, but are usually close.
+///
+/// Changes to the passability of positions only require the affected clusters to be rebuilt.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{pathfinding::hpa::HpaMap, prelude::*};
+///
+/// let mut walls: Grid<bool> = Grid::new(32, 32, false);
+/// for y in 0..28 {
+///     walls[v(15, y)] = true;
+/// }
+///
+/// let mut map = HpaMap::new(&walls, 8, |&wall| !wall);
+///
+/// let path = map.find_path(v(0, 0), v(31, 0)).unwrap();
+/// assert_eq!(path.start(), v(0, 0));
+/// assert_eq!(path.goal(), v(31, 0));
+/// assert!(path.cost() >= 31 + 2 * 28);
+/// assert!(path.positions().windows(2).all(|pair| pair[0].manhattan(pair[1]) == 1));
+///
+/// // close the gap at the bottom of the wall
+/// for y in 28..32 {
+///     map.set_passable(v(15, y), false);
+/// }
+///
+/// assert_eq!(map.find_path(v(0, 0), v(31, 0)), None);
+/// ```
+#[derive(Clone)]
+pub struct HpaMap {
+    passable: Grid<bool>,
+    cluster_size: i64,
+    rules: MovementRules,
+    nodes: Grid<Vec<Vector>>,
+    edges: BTreeMap<Vector, Vec<(Vector, u64)>>,
+}
+
+impl HpaMap {
+    /// Constructs a new `HpaMap` over `grid` with clusters of the given size and [`MovementRules::FOUR_WAY`],
+    /// where `passable` determines which values can be moved through.
+    ///
+    /// Panics if `cluster_size` is not positive.
+    #[track_caller]
+    pub fn new<T, F>(grid: &Grid<T>, cluster_size: i64, passable: F) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Self::with_rules(grid, cluster_size, MovementRules::FOUR_WAY, passable)
+    }
+
+    /// Constructs a new `HpaMap` over `grid` with clusters of the given size and the given movement rules,
+    /// where `passable` determines which values can be moved through.
+    ///
+    /// Panics if `cluster_size` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::{hpa::HpaMap, MovementRules}, prelude::*};
+    ///
+    /// let grid: Grid<bool> = Grid::new(40, 40, true);
+    /// let map = HpaMap::with_rules(&grid, 10, MovementRules::EIGHT_WAY, |&open| open);
+    ///
+    /// let path = map.find_path(v(0, 0), v(9, 9)).unwrap();
+    /// assert_eq!(path.cost(), 9);
+    ///
+    /// let path = map.find_path(v(0, 0), v(39, 39)).unwrap();
+    /// assert!(path.cost() >= 39);
+    /// ```
+    #[track_caller]
+    pub fn with_rules<T, F>(
+        grid: &Grid<T>,
+        cluster_size: i64,
+        rules: MovementRules,
+        passable: F,
+    ) -> Self
+    where
+        F: FnMut(&T) -> bool,
+    {
+        Self::try_with_rules(grid, cluster_size, rules, passable).or_panic()
+    }
+
+    /// Constructs a new `HpaMap` as in [`with_rules`](Self::with_rules),
+    /// or returns an error if `cluster_size` is not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::{hpa::HpaMap, MovementRules}, prelude::*};
+    ///
+    /// let grid: Grid<bool> = Grid::new(40, 40, true);
+    ///
+    /// assert!(HpaMap::try_with_rules(&grid, 10, MovementRules::FOUR_WAY, |&open| open).is_ok());
+    /// assert!(HpaMap::try_with_rules(&grid, 0, MovementRules::FOUR_WAY, |&open| open).is_err());
+    /// ```
+    pub fn try_with_rules<T, F>(
+        grid: &Grid<T>,
+        cluster_size: i64,
+        rules: MovementRules,
+        passable: F,
+    ) -> Result<Self, GridError>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if cluster_size <= 0 {
+            return Err(GridError::InvalidArgument("cluster size must be positive"));
+        }
+        let clusters =
+            (grid.dim() + Vector::new(cluster_size - 1, cluster_size - 1)) / cluster_size;
+        let mut map = Self {
+            passable: grid.map(passable),
+            cluster_size,
+            rules,
+            nodes: Grid::try_new(clusters.x, clusters.y, Vec::new())?,
+            edges: BTreeMap::new(),
+        };
+        for y in 0..clusters.y {
+            for x in 0..clusters.x {
+                map.rebuild_cluster(Vector::new(x, y));
+            }
+        }
+        Ok(map)
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.passable.dim()
+    }
+
+    /// Returns the side length of the clusters.
+    #[inline]
+    pub fn cluster_size(&self) -> i64 {
+        self.cluster_size
+    }
+
+    /// Returns the rules for moving between positions.
+    #[inline]
+    pub fn rules(&self) -> MovementRules {
+        self.rules
+    }
+
+    /// Returns the total number of portal positions across all clusters.
+    pub fn portal_count(&self) -> usize {
+        self.nodes.iter().map(Vec::len).sum()
+    }
+
+    /// Returns `true` if the given position is in bounds and passable, or `false` otherwise.
+    #[inline]
+    pub fn is_passable(&self, pos: Vector) -> bool {
+        self.passable.get(pos).copied().unwrap_or(false)
+    }
+
+    /// Sets whether the given position is passable, rebuilding the portals of its cluster and the neighbouring clusters if it changed.
+    ///
+    /// Panics if `pos` is out of bounds.
+    #[track_caller]
+    pub fn set_passable(&mut self, pos: Vector, passable: bool) {
+        self.try_set_passable(pos, passable).or_panic();
+    }
+
+    /// Sets whether the given position is passable, as in [`set_passable`](Self::set_passable),
+    /// and returns whether it was passable before, or an error if `pos` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::hpa::HpaMap, prelude::*};
+    ///
+    /// let grid: Grid<bool> = Grid::new(8, 8, true);
+    /// let mut map = HpaMap::new(&grid, 4, |&open| open);
+    ///
+    /// assert_eq!(map.try_set_passable(v(2, 2), false), Ok(true));
+    /// assert!(map.try_set_passable(v(8, 2), false).is_err());
+    /// assert!(!map.is_passable(v(2, 2)));
+    /// ```
+    pub fn try_set_passable(&mut self, pos: Vector, passable: bool) -> Result<bool, GridError> {
+        let dim = self.dim();
+        let old = std::mem::replace(
+            self.passable
+                .get_mut(pos)
+                .ok_or(GridError::out_of_bounds(dim, pos))?,
+            passable,
+        );
+        if old != passable {
+            self.rebuild_around(Rect::new(pos, Vector::new(1, 1)));
+        }
+        Ok(old)
+    }
+
+    /// Re-reads the passability of every position of `grid` within `rect`, after the values there have been changed,
+    /// and rebuilds only the clusters whose portals could have been affected.
+    ///
+    /// This is much cheaper than constructing a new `HpaMap` when a small area of a large grid is edited.
+    /// The part of `rect` outside the bounds of the grid is ignored.
+    ///
+    /// Panics if the dimensions of `grid` differ from those the map was constructed with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::hpa::HpaMap, prelude::*};
+    ///
+    /// let mut grid: Grid<char> = Grid::new(64, 64, '.');
+    /// let mut map = HpaMap::new(&grid, 16, |&c| c != '#');
+    ///
+    /// assert!(map.find_path(v(0, 20), v(63, 20)).is_some());
+    ///
+    /// // build a wall across the whole grid
+    /// let wall = Rect::new(v(0, 30), v(64, 2));
+    /// for pos in wall.positions() {
+    ///     grid[pos] = '#';
+    /// }
+    /// map.notify_changed(&grid, wall, |&c| c != '#');
+    ///
+    /// assert!(map.find_path(v(0, 20), v(63, 20)).is_some());
+    /// assert_eq!(map.find_path(v(0, 20), v(63, 40)), None);
+    /// ```
+    #[track_caller]
+    pub fn notify_changed<T, F>(&mut self, grid: &Grid<T>, rect: Rect, passable: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.try_notify_changed(grid, rect, passable).or_panic();
+    }
+
+    /// Re-reads the passability of every position of `grid` within `rect`, as in [`notify_changed`](Self::notify_changed),
+    /// or returns an error if the dimensions of `grid` differ from those the map was constructed with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::hpa::HpaMap, prelude::*};
+    ///
+    /// let grid: Grid<bool> = Grid::new(8, 8, true);
+    /// let mut map = HpaMap::new(&grid, 4, |&open| open);
+    ///
+    /// let other: Grid<bool> = Grid::new(8, 4, true);
+    /// assert_eq!(
+    ///     map.try_notify_changed(&other, Rect::new(v(0, 0), v(8, 4)), |&open| open),
+    ///     Err(GridError::DimensionMismatch { expected: v(8, 8), found: v(8, 4) }),
+    /// );
+    /// ```
+    pub fn try_notify_changed<T, F>(
+        &mut self,
+        grid: &Grid<T>,
+        rect: Rect,
+        mut passable: F,
+    ) -> Result<(), GridError>
+    where
+        F: FnMut(&T) -> bool,
+    {
+        if grid.dim() != self.dim() {
+            return Err(GridError::DimensionMismatch {
+                expected: self.dim(),
+                found: grid.dim(),
+            });
+        }
+        let Some(rect) = rect.intersection(&Rect::new(Vector::new(0, 0), self.dim())) else {
+            return Ok(());
+        };
+        for pos in rect.positions() {
+            self.passable[pos] = passable(&grid[pos]);
+        }
+        self.rebuild_around(rect);
+        Ok(())
+    }
+
+    /// Returns a path from `start` to `goal` through passable positions, or `None` if there is no such path.
+    ///
+    /// Returns `None` if `start` or `goal` are out of bounds or not passable.
+    pub fn find_path(&self, start: Vector, goal: Vector) -> Option<Path> {
+        span!("hpa_find_path", %start, %goal);
+        if !self.is_passable(start) || !self.is_passable(goal) {
+            return None;
+        }
+        let (start_cluster, goal_cluster) = (self.cluster_of(start), self.cluster_of(goal));
+
+        // within a single cluster, a local search is both exact and cheap
+        if start_cluster == goal_cluster {
+            let search = self.local_search(start_cluster, start);
+            if search.get(goal).is_some() {
+                return Some(self.path_from(search.path_to(goal)));
+            }
+        }
+
+        let start_search = self.local_search(start_cluster, start);
+        let start_edges: Vec<_> = self.nodes[start_cluster]
+            .iter()
+            .filter_map(|&node| Some((node, start_search.get(node)?)))
+            .collect();
+        let goal_search = self.local_search(goal_cluster, goal);
+
+        let mut dist = BTreeMap::from([(start, 0)]);
+        let mut came_from = BTreeMap::new();
+        let mut open = BinaryHeap::from([Reverse((self.rules.heuristic(start, goal), start))]);
+        while let Some(Reverse((estimate, node))) = open.pop() {
+            if node == goal {
+                break;
+            }
+            let d = dist[&node];
+            if estimate > d + self.rules.heuristic(node, goal) {
+                continue;
+            }
+            let goal_edge = (self.cluster_of(node) == goal_cluster)
+                .then(|| goal_search.get(node).map(|cost| (goal, cost)))
+                .flatten();
+            let edges = self.edges.get(&node).into_iter().flatten().copied();
+            let edges = edges.chain(goal_edge);
+            let edges: Vec<_> = if node == start {
+                edges.chain(start_edges.iter().copied()).collect()
+            } else {
+                edges.collect()
+            };
+            for (next, cost) in edges {
+                let next_dist = d + cost;
+                if dist.get(&next).is_none_or(|&old| next_dist < old) {
+                    dist.insert(next, next_dist);
+                    came_from.insert(next, node);
+                    open.push(Reverse((
+                        next_dist + self.rules.heuristic(next, goal),
+                        next,
+                    )));
+                }
+            }
+        }
+
+        event!(nodes = dist.len(), "abstract search finished");
+
+        let mut waypoints = vec![goal];
+        while let Some(&previous) = came_from.get(waypoints.last()?) {
+            waypoints.push(previous);
+            if previous == start {
+                break;
+            }
+        }
+        if *waypoints.last()? != start {
+            return None;
+        }
+        waypoints.reverse();
+
+        let mut positions = vec![start];
+        for pair in waypoints.windows(2) {
+            let (from, to) = (pair[0], pair[1]);
+            let cluster = self.cluster_of(from);
+            if cluster == self.cluster_of(to) {
+                let search = self.local_search(cluster, from);
+                positions.extend(search.path_to(to).into_iter().skip(1));
+            } else {
+                positions.push(to);
+            }
+        }
+        Some(self.path_from(positions))
+    }
+
+    fn path_from(&self, positions: Vec<Vector>) -> Path {
+        let cost = positions
+            .windows(2)
+            .map(|pair| self.rules.move_cost(pair[1] - pair[0]))
+            .sum();
+        Path { positions, cost }
+    }
+
+    fn cluster_of(&self, pos: Vector) -> Vector {
+        pos / self.cluster_size
+    }
+
+    fn cluster_rect(&self, cluster: Vector) -> Rect {
+        let pos = cluster * self.cluster_size;
+        let size = Vector::new(self.cluster_size, self.cluster_size);
+        Rect::new(pos, size.min(self.dim() - pos))
+    }
+
+    /// Returns the pairs of positions on either side of each portal between two adjacent clusters,
+    /// with the position in `cluster` first.
+    fn portals(&self, cluster: Vector, offset: Vector) -> Vec<(Vector, Vector)> {
+        let (rect, other) = (self.cluster_rect(cluster), cluster + offset);
+        if !self.nodes.in_bounds(other) {
+            return Vec::new();
+        }
+        if offset.x != 0 && offset.y != 0 {
+            // diagonally adjacent clusters meet at a single corner
+            let corner = Vector::new(
+                if offset.x > 0 {
+                    rect.max().x - 1
+                } else {
+                    rect.pos.x
+                },
+                if offset.y > 0 {
+                    rect.max().y - 1
+                } else {
+                    rect.pos.y
+                },
+            );
+            return self
+                .diagonal_only(corner, offset)
+                .then_some((corner, corner + offset))
+                .into_iter()
+                .collect();
+        }
+        // the positions of `cluster` along the shared border, and the direction along it
+        let (first, along, len) = match offset {
+            Vector { x: 1, y: 0 } => (
+                Vector::new(rect.max().x - 1, rect.pos.y),
+                Vector::new(0, 1),
+                rect.dim.y,
+            ),
+            Vector { x: -1, y: 0 } => (rect.pos, Vector::new(0, 1), rect.dim.y),
+            Vector { x: 0, y: 1 } => (
+                Vector::new(rect.pos.x, rect.max().y - 1),
+                Vector::new(1, 0),
+                rect.dim.x,
+            ),
+            _ => (rect.pos, Vector::new(1, 0), rect.dim.x),
+        };
+        let open = |i: i64| {
+            let pos = first + along * i;
+            self.passable[pos] && self.passable[pos + offset]
+        };
+        let mut portals = Vec::new();
+        let mut i = 0;
+        while i < len {
+            if !open(i) {
+                i += 1;
+                continue;
+            }
+            let run_start = i;
+            while i < len && open(i) {
+                i += 1;
+            }
+            let pos = first + along * ((run_start + i - 1) / 2);
+            portals.push((pos, pos + offset));
+        }
+        for i in 0..len {
+            for j in [i - 1, i + 1] {
+                let (pos, diagonal) = (first + along * i, offset + along * (j - i));
+                if (0..len).contains(&j) && self.diagonal_only(pos, diagonal) {
+                    portals.push((pos, pos + diagonal));
+                }
+            }
+        }
+        portals
+    }
+
+    /// Returns `true` if a diagonal move from `pos` by `offset` is allowed, and is the only way to move between the two positions,
+    /// because both positions beside it are impassable.
+    fn diagonal_only(&self, pos: Vector, offset: Vector) -> bool {
+        self.rules.diagonals
+            && self.rules.corner_cutting
+            && self.passable[pos]
+            && self.passable[pos + offset]
+            && !self.passable[pos + offset.horizontal()]
+            && !self.passable[pos + offset.vertical()]
+    }
+
+    /// Rebuilds every cluster that overlaps `rect`, which must be in bounds, along with their neighbours.
+    fn rebuild_around(&mut self, rect: Rect) {
+        let min = self.cluster_of(rect.min()) - Vector::new(1, 1);
+        let max = self.cluster_of(rect.max() - Vector::new(1, 1)) + Vector::new(2, 2);
+        for cluster in Rect::from_corners(min, max).positions() {
+            if self.nodes.in_bounds(cluster) {
+                self.rebuild_cluster(cluster);
+            }
+        }
+    }
+
+    fn rebuild_cluster(&mut self, cluster: Vector) {
+        for node in std::mem::take(&mut self.nodes[cluster]) {
+            self.edges.remove(&node);
+        }
+        let mut edges: BTreeMap<Vector, Vec<(Vector, u64)>> = BTreeMap::new();
+        for offset in ADJACENT {
+            for (node, other) in self.portals(cluster, offset) {
+                let cost = self.rules.move_cost(other - node);
+                edges.entry(node).or_default().push((other, cost));
+            }
+        }
+        let nodes: BTreeSet<Vector> = edges.keys().copied().collect();
+        for &node in &nodes {
+            let search = self.local_search(cluster, node);
+            let node_edges = edges.get_mut(&node).unwrap();
+            for &other in &nodes {
+                if let Some(cost) = search.get(other).filter(|_| other != node) {
+                    node_edges.push((other, cost));
+                }
+            }
+        }
+        self.nodes[cluster] = nodes.into_iter().collect();
+        self.edges.extend(edges);
+    }
+
+    /// Runs a uniform cost search from `from` restricted to the given cluster.
+    fn local_search(&self, cluster: Vector, from: Vector) -> LocalSearch {
+        let rect = self.cluster_rect(cluster);
+        let mut parents: Grid<Option<(Vector, u64)>> = Grid::new(rect.dim.x, rect.dim.y, None);
+        parents[from - rect.pos] = Some((from, 0));
+        let mut open = BinaryHeap::from([Reverse((0, from))]);
+        let inside = |pos: Vector| rect.contains(pos) && self.passable[pos];
+        while let Some(Reverse((dist, pos))) = open.pop() {
+            if parents[pos - rect.pos].is_some_and(|(_, best)| dist > best) {
+                continue;
+            }
+            for &offset in self.rules.moves() {
+                let next = pos + offset;
+                if !inside(next) || !self.rules.can_cut(pos, offset, &inside) {
+                    continue;
+                }
+                let next_dist = dist + self.rules.move_cost(offset);
+                if parents[next - rect.pos].is_none_or(|(_, best)| next_dist < best) {
+                    parents[next - rect.pos] = Some((pos, next_dist));
+                    open.push(Reverse((next_dist, next)));
+                }
+            }
+        }
+        LocalSearch { rect, parents }
+    }
+}
+
+/// The result of a uniform cost search within a single cluster.
+struct LocalSearch {
+    rect: Rect,
+    parents: Grid<Option<(Vector, u64)>>,
+}
+
+impl LocalSearch {
+    /// Returns the distance to `pos`, or `None` if it was not reached.
+    fn get(&self, pos: Vector) -> Option<u64> {
+        if !self.rect.contains(pos) {
+            return None;
+        }
+        self.parents[pos - self.rect.pos].map(|(_, dist)| dist)
+    }
+
+    /// Returns the positions from the start of the search to `pos`, which must have been reached.
+    fn path_to(&self, pos: Vector) -> Vec<Vector> {
+        let mut positions = vec![pos];
+        let mut pos = pos;
+        while let Some((parent, dist)) = self.parents[pos - self.rect.pos] {
+            if dist == 0 {
+                break;
+            }
+            positions.push(parent);
+            pos = parent;
+        }
+        positions.reverse();
+        positions
+    }
+}
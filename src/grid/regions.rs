@@ -0,0 +1,248 @@
+//! Partitions of a `Grid` into regions, such as the boxes of a sudoku or the irregular shapes of a jigsaw sudoku.
+
+use std::{collections::HashMap, hash::Hash, iter::Copied, slice};
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+/// A partition of the positions of a grid into numbered regions, with lookup in both directions.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, regions::Regions};
+///
+/// let sudoku: Grid<u8> = Grid::from_nested_iter([
+///     [1, 2, 3, 4],
+///     [3, 4, 1, 2],
+///     [2, 1, 4, 3],
+///     [4, 3, 2, 1],
+/// ]);
+///
+/// let boxes = Regions::boxes(sudoku.dim(), v(2, 2));
+///
+/// assert_eq!(boxes.len(), 4);
+/// assert_eq!(boxes.region_of(v(3, 1)), Some(1));
+///
+/// for id in 0..boxes.len() {
+///     let mut values: Vec<u8> = boxes.iter_region(id).map(|pos| sudoku[pos]).collect();
+///     values.sort();
+///     assert_eq!(values, vec![1, 2, 3, 4]);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Regions {
+    ids: Grid<usize>,
+    regions: Vec<Vec<Vector>>,
+}
+
+impl Regions {
+    /// Constructs new `Regions` in which each position belongs to the region with the id it holds in `ids`.
+    ///
+    /// There is a region for every id from `0` up to the largest id in `ids`, so ids that do not appear
+    /// have empty regions.
+    ///
+    /// Panics if any id is not less than the number of positions in `ids`.
+    #[track_caller]
+    pub fn new(ids: Grid<usize>) -> Self {
+        Self::try_new(ids).or_panic()
+    }
+
+    /// Constructs new `Regions` in which each position belongs to the region with the id it holds in `ids`, as in [`new`](Self::new),
+    /// or returns an error if any id is not less than the number of positions in `ids`,
+    /// which keeps the number of regions no greater than the number of positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, regions::Regions};
+    ///
+    /// let regions = Regions::try_new(Grid::from_nested_iter([[0, 3], [3, 0]])).unwrap();
+    ///
+    /// assert_eq!(regions.len(), 4);
+    /// assert_eq!(regions.region(1), Some(&[][..]));
+    /// assert!(Regions::try_new(Grid::from_nested_iter([[0, usize::MAX]])).is_err());
+    /// ```
+    pub fn try_new(ids: Grid<usize>) -> Result<Self, GridError> {
+        if ids.iter().any(|&id| id >= ids.raw.len()) {
+            return Err(GridError::InvalidArgument(
+                "every region id must be less than the number of positions",
+            ));
+        }
+        let len = ids.iter().max().map_or(0, |&max| max + 1);
+        let mut regions = vec![Vec::new(); len];
+        for (pos, &id) in ids.iter_positions() {
+            regions[id].push(pos);
+        }
+        Ok(Self { ids, regions })
+    }
+
+    /// Constructs new `Regions` dividing a grid with the given dimensions into rectangular boxes with dimensions `box_dim`,
+    /// numbered in row-major order.
+    ///
+    /// Panics if `dim` or `box_dim` is not positive, if `dim` is too large, or if `box_dim` does not divide `dim` exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, regions::Regions};
+    ///
+    /// let boxes = Regions::boxes(v(6, 4), v(3, 2));
+    ///
+    /// assert_eq!(boxes.ids(), &Grid::from_nested_iter([
+    ///     [0, 0, 0, 1, 1, 1],
+    ///     [0, 0, 0, 1, 1, 1],
+    ///     [2, 2, 2, 3, 3, 3],
+    ///     [2, 2, 2, 3, 3, 3],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn boxes(dim: Vector, box_dim: Vector) -> Self {
+        Self::try_boxes(dim, box_dim).or_panic()
+    }
+
+    /// Constructs new `Regions` dividing a grid into rectangular boxes, as in [`boxes`](Self::boxes),
+    /// or returns an error if `dim` or `box_dim` is not positive, if `dim` is too large,
+    /// or if `box_dim` does not divide `dim` exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, regions::Regions};
+    ///
+    /// assert_eq!(Regions::try_boxes(v(9, 9), v(3, 3)).map(|boxes| boxes.len()), Ok(9));
+    /// assert_eq!(
+    ///     Regions::try_boxes(v(9, 9), v(0, 3)),
+    ///     Err(GridError::InvalidDimensions { width: 0, height: 3 }),
+    /// );
+    /// assert!(Regions::try_boxes(v(9, 9), v(2, 3)).is_err());
+    /// ```
+    pub fn try_boxes(dim: Vector, box_dim: Vector) -> Result<Self, GridError> {
+        if box_dim.x <= 0 || box_dim.y <= 0 {
+            return Err(GridError::InvalidDimensions {
+                width: box_dim.x,
+                height: box_dim.y,
+            });
+        }
+        if dim.x % box_dim.x != 0 || dim.y % box_dim.y != 0 {
+            return Err(GridError::InvalidArgument(
+                "the boxes must divide the grid exactly",
+            ));
+        }
+        let across = dim.x / box_dim.x;
+        Ok(Self::new(Grid::try_from_fn(dim.x, dim.y, |pos| {
+            (pos.x / box_dim.x + pos.y / box_dim.y * across) as usize
+        })?))
+    }
+
+    /// Constructs new `Regions` in which positions holding equal values in `labels` belong to the same region,
+    /// numbered in the row-major order of their first positions.
+    ///
+    /// Positions with equal values belong to the same region whether or not they are connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, regions::Regions};
+    ///
+    /// let jigsaw: Grid<char> = Grid::from_nested_iter([
+    ///     "aabb".chars(),
+    ///     "acbb".chars(),
+    ///     "accd".chars(),
+    ///     "ccdd".chars(),
+    /// ]);
+    ///
+    /// let regions = Regions::from_labels(&jigsaw);
+    ///
+    /// assert_eq!(regions.region_of(v(1, 2)), Some(2));
+    /// assert_eq!(regions.iter_region(3).collect::<Vec<_>>(), vec![v(3, 2), v(2, 3), v(3, 3)]);
+    /// ```
+    pub fn from_labels<T: Eq + Hash>(labels: &Grid<T>) -> Self {
+        let mut ids = HashMap::new();
+        let raw = labels
+            .iter()
+            .map(|label| {
+                let next = ids.len();
+                *ids.entry(label).or_insert(next)
+            })
+            .collect();
+        Self::new(Grid {
+            raw,
+            dim: labels.dim,
+        })
+    }
+
+    /// Returns the number of regions.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// Returns `true` if there are no regions, which is only the case when the partitioned grid has no positions.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// Returns the dimensions of the partitioned grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.ids.dim()
+    }
+
+    /// Returns a grid holding the id of the region of each position.
+    #[inline]
+    pub fn ids(&self) -> &Grid<usize> {
+        &self.ids
+    }
+
+    /// Returns the id of the region containing the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn region_of(&self, pos: Vector) -> Option<usize> {
+        self.ids.get(pos).copied()
+    }
+
+    /// Returns the positions in the region with the given id, in row-major order, or `None` if there is no such region.
+    #[inline]
+    pub fn region(&self, id: usize) -> Option<&[Vector]> {
+        self.regions.get(id).map(Vec::as_slice)
+    }
+
+    /// Returns an iterator over the positions in the region with the given id, in row-major order.
+    ///
+    /// Panics if there is no region with the given id.
+    #[track_caller]
+    pub fn iter_region(&self, id: usize) -> Copied<slice::Iter<'_, Vector>> {
+        self.try_iter_region(id).or_panic()
+    }
+
+    /// Returns an iterator over the positions in the region with the given id, in row-major order,
+    /// or an error if there is no region with the given id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, regions::Regions};
+    ///
+    /// let boxes = Regions::boxes(v(4, 2), v(2, 2));
+    ///
+    /// assert_eq!(boxes.try_iter_region(1).unwrap().next(), Some(v(2, 0)));
+    /// assert!(boxes.try_iter_region(2).is_err());
+    /// ```
+    pub fn try_iter_region(&self, id: usize) -> Result<Copied<slice::Iter<'_, Vector>>, GridError> {
+        match self.region(id) {
+            Some(region) => Ok(region.iter().copied()),
+            None => Err(GridError::InvalidArgument(
+                "there is no region with the given id",
+            )),
+        }
+    }
+
+    /// Returns an iterator over the positions in each region, in order of id.
+    pub fn iter(&self) -> impl Iterator<Item = &[Vector]> {
+        self.regions.iter().map(Vec::as_slice)
+    }
+}
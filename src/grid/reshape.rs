@@ -0,0 +1,773 @@
+//! Changing the dimensions of a `Grid`, in place or by building new grids out of the values of existing ones.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{check_region, try_alloc, Grid},
+    rect::Rect,
+    vector::{Axis, Vector},
+};
+
+impl<T: Clone> Grid<T> {
+    /// Returns a new grid with dimensions `dim`, containing copies of the values in the rectangular region of `self`
+    /// with its top-left corner at `top_left`.
+    ///
+    /// Panics if `dim` is not positive, or if the region is not entirely in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3, 4],
+    ///     [5, 6, 7, 8],
+    ///     [9, 10, 11, 12],
+    /// ]);
+    ///
+    /// assert_eq!(grid.subgrid(v(1, 1), v(3, 2)), Grid::from_nested_iter([
+    ///     [6, 7, 8],
+    ///     [10, 11, 12],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn subgrid(&self, top_left: Vector, dim: Vector) -> Self {
+        self.try_subgrid(top_left, dim).or_panic()
+    }
+
+    /// Returns a new grid with dimensions `dim`, containing copies of the values in the rectangular region of `self`
+    /// with its top-left corner at `top_left`, or an error if `dim` is not positive or the region is not entirely in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// assert_eq!(grid.try_subgrid(v(3, 0), v(1, 3)), Ok(Grid::from_nested_iter([[3], [7], [11]])));
+    /// assert_eq!(
+    ///     grid.try_subgrid(v(3, 0), v(2, 3)),
+    ///     Err(GridError::OutOfBounds { dim: v(4, 3), region: Rect::new(v(3, 0), v(2, 3)) }),
+    /// );
+    /// assert!(grid.try_subgrid(v(-1, 0), v(1, 1)).is_err());
+    /// assert_eq!(grid.try_subgrid(v(0, 0), v(0, 3)), Err(GridError::InvalidDimensions { width: 0, height: 3 }));
+    /// assert!(grid.try_subgrid(v(1, 0), v(i64::MAX, 1)).is_err());
+    /// ```
+    pub fn try_subgrid(&self, top_left: Vector, dim: Vector) -> Result<Self, GridError> {
+        check_region(self.dim, Rect::new(top_left, dim))?;
+        let (start, end) = (top_left.x as usize, (top_left.x + dim.x) as usize);
+        let raw = self
+            .rows()
+            .skip(top_left.y as usize)
+            .take(dim.y as usize)
+            .flat_map(|row| row[start..end].iter().cloned())
+            .collect();
+        Ok(Grid { raw, dim })
+    }
+
+    /// Returns a new grid containing the values of `self` followed by the values of `other` along the given axis,
+    /// or an error if the grids have different lengths along the other axis.
+    ///
+    /// This is [`concat_horizontal`](Self::concat_horizontal) for [`Axis::X`] and [`concat_vertical`](Self::concat_vertical) for [`Axis::Y`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let tile: Grid<u8> = Grid::from_nested_iter([[1, 2]]);
+    ///
+    /// assert_eq!(tile.concat(&tile, Axis::X), Ok(Grid::from_nested_iter([[1, 2, 1, 2]])));
+    /// assert_eq!(tile.concat(&tile, Axis::Y), Ok(Grid::from_nested_iter([[1, 2], [1, 2]])));
+    /// ```
+    pub fn concat(&self, other: &Self, axis: Axis) -> Result<Self, GridError> {
+        match axis {
+            Axis::X => self.concat_horizontal(other),
+            Axis::Y => self.concat_vertical(other),
+        }
+    }
+
+    /// Returns a new grid containing the values of `self` with the values of `other` to the right of them,
+    /// or an error if the grids have different heights.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let left: Grid<u8> = Grid::from_nested_iter([[1, 2], [3, 4]]);
+    /// let right: Grid<u8> = Grid::from_nested_iter([[5], [6]]);
+    ///
+    /// assert_eq!(left.concat_horizontal(&right), Ok(Grid::from_nested_iter([[1, 2, 5], [3, 4, 6]])));
+    /// assert_eq!(
+    ///     left.concat_horizontal(&Grid::new(1, 3, 0)),
+    ///     Err(GridError::DimensionMismatch { expected: v(1, 2), found: v(1, 3) }),
+    /// );
+    ///
+    /// let empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.concat_horizontal(&right), Ok(right.clone()));
+    /// ```
+    pub fn concat_horizontal(&self, other: &Self) -> Result<Self, GridError> {
+        if self.dim.y != other.dim.y {
+            return Err(GridError::DimensionMismatch {
+                expected: Vector::new(other.dim.x, self.dim.y),
+                found: other.dim,
+            });
+        }
+        // rows are sliced by hand, since either grid may have a width of 0 and so no rows to iterate
+        let (left, right) = (self.dim.x as usize, other.dim.x as usize);
+        let raw = (0..self.dim.y as usize)
+            .flat_map(|y| {
+                self.raw[y * left..][..left]
+                    .iter()
+                    .chain(&other.raw[y * right..][..right])
+            })
+            .cloned()
+            .collect();
+        Ok(Grid {
+            raw,
+            dim: Vector::new(self.dim.x + other.dim.x, self.dim.y),
+        })
+    }
+
+    /// Returns a new grid containing the values of `self` with the values of `other` below them,
+    /// or an error if the grids have different widths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let tile: Grid<char> = Grid::from_nested_iter(["#.".chars(), ".#".chars()]);
+    ///
+    /// let row = tile.concat_horizontal(&tile).unwrap();
+    /// let map = row.concat_vertical(&row).unwrap();
+    ///
+    /// assert_eq!(map, Grid::from_nested_iter([
+    ///     "#.#.".chars(),
+    ///     ".#.#".chars(),
+    ///     "#.#.".chars(),
+    ///     ".#.#".chars(),
+    /// ]));
+    /// assert_eq!(
+    ///     tile.concat_vertical(&row),
+    ///     Err(GridError::DimensionMismatch { expected: v(2, 2), found: v(4, 2) }),
+    /// );
+    /// ```
+    pub fn concat_vertical(&self, other: &Self) -> Result<Self, GridError> {
+        if self.dim.x != other.dim.x {
+            return Err(GridError::DimensionMismatch {
+                expected: Vector::new(self.dim.x, other.dim.y),
+                found: other.dim,
+            });
+        }
+        let mut raw = Vec::with_capacity(self.raw.len() + other.raw.len());
+        raw.extend_from_slice(&self.raw);
+        raw.extend_from_slice(&other.raw);
+        Ok(Grid {
+            raw,
+            dim: Vector::new(self.dim.x, self.dim.y + other.dim.y),
+        })
+    }
+
+    /// Returns a new grid containing the values of `self` surrounded by a border of clones of `value`,
+    /// `n` positions thick on every side.
+    ///
+    /// Panics if `n` is negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1, 2]]);
+    ///
+    /// assert_eq!(grid.pad(1, 0), Grid::from_nested_iter([
+    ///     [0, 0, 0, 0],
+    ///     [0, 1, 2, 0],
+    ///     [0, 0, 0, 0],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn pad(&self, n: i64, value: T) -> Self {
+        self.try_pad(n, value).or_panic()
+    }
+
+    /// Returns a new grid containing the values of `self` surrounded by a border of clones of `value`, as in [`pad`](Self::pad),
+    /// or an error if `n` is negative or the padded grid would be too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1]]);
+    ///
+    /// assert_eq!(grid.try_pad(1, 0).map(|grid| grid.dim()), Ok(v(3, 3)));
+    /// assert!(grid.try_pad(-1, 0).is_err());
+    /// assert!(grid.try_pad(i64::MAX, 0).is_err());
+    /// ```
+    pub fn try_pad(&self, n: i64, value: T) -> Result<Self, GridError> {
+        self.try_pad_sides(Vector::new(n, n), Vector::new(n, n), value)
+    }
+
+    /// Returns a new grid containing the values of `self` surrounded by a border of clones of `value`,
+    /// `before.x` positions thick on the left, `before.y` on the top, `after.x` on the right and `after.y` on the bottom.
+    ///
+    /// Panics if any of the amounts are negative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter(["ab".chars(), "cd".chars()]);
+    ///
+    /// assert_eq!(grid.pad_sides(v(1, 0), v(0, 2), '.'), Grid::from_nested_iter([
+    ///     ".ab".chars(),
+    ///     ".cd".chars(),
+    ///     "...".chars(),
+    ///     "...".chars(),
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn pad_sides(&self, before: Vector, after: Vector, value: T) -> Self {
+        self.try_pad_sides(before, after, value).or_panic()
+    }
+
+    /// Returns a new grid containing the values of `self` surrounded by a border of clones of `value`,
+    /// as in [`pad_sides`](Self::pad_sides), or an error if any of the amounts are negative
+    /// or the padded grid would be too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1]]);
+    ///
+    /// assert_eq!(grid.try_pad_sides(v(0, 1), v(1, 0), 0), Ok(Grid::from_nested_iter([[0, 0], [1, 0]])));
+    /// assert!(grid.try_pad_sides(v(-1, 0), v(0, 0), 0).is_err());
+    /// ```
+    pub fn try_pad_sides(
+        &self,
+        before: Vector,
+        after: Vector,
+        value: T,
+    ) -> Result<Self, GridError> {
+        if before.x < 0 || before.y < 0 || after.x < 0 || after.y < 0 {
+            return Err(GridError::InvalidArgument("padding must not be negative"));
+        }
+        let (width, height) = (
+            self.dim.x.saturating_add(before.x).saturating_add(after.x),
+            self.dim.y.saturating_add(before.y).saturating_add(after.y),
+        );
+        let (mut raw, size) = try_alloc(width, height)?;
+        let dim = Vector::new(width, height);
+        raw.resize((before.y * dim.x) as usize, value.clone());
+        for row in self.rows() {
+            let start = raw.len();
+            raw.resize(start + before.x as usize, value.clone());
+            raw.extend_from_slice(row);
+            raw.resize(start + dim.x as usize, value.clone());
+        }
+        raw.resize(size, value);
+        Ok(Grid { raw, dim })
+    }
+}
+
+impl<T> Grid<T> {
+    /// Inserts a new row at the given `y` coordinate, moving the rows from `y` onwards down by one,
+    /// taking the new values from `values`, from left to right.
+    ///
+    /// Only the first `width` values are used, so a row filled with a single value can be inserted
+    /// by passing [`iter::repeat`](std::iter::repeat).
+    ///
+    /// Panics if `y` is greater than the height, or if `values` has fewer than `width` values,
+    /// in which case the grid is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[1, 2], [3, 4]]);
+    ///
+    /// grid.insert_row(1, [5, 6]);
+    /// grid.insert_row(3, std::iter::repeat(0));
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2], [5, 6], [3, 4], [0, 0]]));
+    /// ```
+    #[track_caller]
+    pub fn insert_row<I>(&mut self, y: i64, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_insert_row(y, values).or_panic()
+    }
+
+    /// Inserts a new row at the given `y` coordinate, as in [`insert_row`](Self::insert_row),
+    /// or returns an error without changing the grid if `y` is out of bounds or there are fewer than `width` values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[1, 2]]);
+    ///
+    /// assert_eq!(grid.try_insert_row(0, [3]), Err(GridError::TooFewValues { expected: 2, found: 1 }));
+    /// assert!(grid.try_insert_row(2, [3, 4]).is_err());
+    /// assert_eq!(grid.try_insert_row(1, [3, 4]), Ok(()));
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2], [3, 4]]));
+    /// ```
+    pub fn try_insert_row<I>(&mut self, y: i64, values: I) -> Result<(), GridError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if !(0..=self.dim.y).contains(&y) {
+            return Err(GridError::OutOfBounds {
+                dim: self.dim,
+                region: Rect::new(Vector::new(0, y), Vector::new(self.dim.x, 1)),
+            });
+        }
+        let row = take_exactly(values, self.dim.x)?;
+        let start = (y * self.dim.x) as usize;
+        self.raw.splice(start..start, row);
+        self.dim.y += 1;
+        Ok(())
+    }
+
+    /// Inserts a new column at the given `x` coordinate, moving the columns from `x` onwards right by one,
+    /// taking the new values from `values`, from top to bottom.
+    ///
+    /// Only the first `height` values are used, so a column filled with a single value can be inserted
+    /// by passing [`iter::repeat`](std::iter::repeat).
+    ///
+    /// Panics if `x` is greater than the width, or if `values` has fewer than `height` values,
+    /// in which case the grid is left unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<char> = Grid::from_nested_iter([
+    ///     "#..#".chars(),
+    ///     "....".chars(),
+    ///     "..#.".chars(),
+    /// ]);
+    ///
+    /// // expand every empty column
+    /// for x in (0..grid.width()).rev() {
+    ///     if grid.col(x).iter().all(|&c| c == '.') {
+    ///         grid.insert_col(x, std::iter::repeat('.'));
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     "#...#".chars(),
+    ///     ".....".chars(),
+    ///     "...#.".chars(),
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn insert_col<I>(&mut self, x: i64, values: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        self.try_insert_col(x, values).or_panic()
+    }
+
+    /// Inserts a new column at the given `x` coordinate, as in [`insert_col`](Self::insert_col),
+    /// or returns an error without changing the grid if `x` is out of bounds or there are fewer than `height` values.
+    pub fn try_insert_col<I>(&mut self, x: i64, values: I) -> Result<(), GridError>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        if !(0..=self.dim.x).contains(&x) {
+            return Err(GridError::OutOfBounds {
+                dim: self.dim,
+                region: Rect::new(Vector::new(x, 0), Vector::new(1, self.dim.y)),
+            });
+        }
+        let col = take_exactly(values, self.dim.y)?;
+        let (width, x) = (self.dim.x as usize, x as usize);
+        let mut old = std::mem::take(&mut self.raw).into_iter();
+        self.raw.reserve_exact(old.len() + col.len());
+        for value in col {
+            self.raw.extend(old.by_ref().take(x));
+            self.raw.push(value);
+            self.raw.extend(old.by_ref().take(width - x));
+        }
+        self.dim.x += 1;
+        Ok(())
+    }
+
+    /// Removes the row at the given `y` coordinate, moving the rows below it up by one,
+    /// and returns its values, from left to right.
+    ///
+    /// Panics if `y` is out of bounds, or if it is the only row, as a grid cannot be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[1, 2], [3, 4], [5, 6]]);
+    ///
+    /// assert_eq!(grid.remove_row(1), vec![3, 4]);
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2], [5, 6]]));
+    /// ```
+    #[track_caller]
+    pub fn remove_row(&mut self, y: i64) -> Vec<T> {
+        self.try_remove_row(y).or_panic()
+    }
+
+    /// Removes the row at the given `y` coordinate, as in [`remove_row`](Self::remove_row),
+    /// or returns an error without changing the grid if `y` is out of bounds or it is the only row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[1, 2], [3, 4]]);
+    ///
+    /// assert!(grid.try_remove_row(2).is_err());
+    /// assert_eq!(grid.try_remove_row(0), Ok(vec![1, 2]));
+    /// assert_eq!(grid.try_remove_row(0), Err(GridError::InvalidDimensions { width: 2, height: 0 }));
+    /// ```
+    pub fn try_remove_row(&mut self, y: i64) -> Result<Vec<T>, GridError> {
+        if !(0..self.dim.y).contains(&y) {
+            return Err(GridError::OutOfBounds {
+                dim: self.dim,
+                region: Rect::new(Vector::new(0, y), Vector::new(self.dim.x, 1)),
+            });
+        }
+        if self.dim.y == 1 {
+            return Err(GridError::InvalidDimensions {
+                width: self.dim.x,
+                height: 0,
+            });
+        }
+        let start = (y * self.dim.x) as usize;
+        let row = self.raw.drain(start..start + self.dim.x as usize).collect();
+        self.dim.y -= 1;
+        Ok(row)
+    }
+
+    /// Removes the column at the given `x` coordinate, moving the columns to the right of it left by one,
+    /// and returns its values, from top to bottom.
+    ///
+    /// Panics if `x` is out of bounds, or if it is the only column, as a grid cannot be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<char> = Grid::from_nested_iter([
+    ///     ".#..".chars(),
+    ///     "#...".chars(),
+    /// ]);
+    ///
+    /// // prune empty margins
+    /// while grid.col(grid.width() - 1).iter().all(|&c| c == '.') {
+    ///     grid.remove_col(grid.width() - 1);
+    /// }
+    ///
+    /// assert_eq!(grid.remove_col(0), vec!['.', '#']);
+    /// assert_eq!(grid, Grid::from_nested_iter([['#'], ['.']]));
+    /// ```
+    #[track_caller]
+    pub fn remove_col(&mut self, x: i64) -> Vec<T> {
+        self.try_remove_col(x).or_panic()
+    }
+
+    /// Removes the column at the given `x` coordinate, as in [`remove_col`](Self::remove_col),
+    /// or returns an error without changing the grid if `x` is out of bounds or it is the only column.
+    pub fn try_remove_col(&mut self, x: i64) -> Result<Vec<T>, GridError> {
+        if !(0..self.dim.x).contains(&x) {
+            return Err(GridError::OutOfBounds {
+                dim: self.dim,
+                region: Rect::new(Vector::new(x, 0), Vector::new(1, self.dim.y)),
+            });
+        }
+        if self.dim.x == 1 {
+            return Err(GridError::InvalidDimensions {
+                width: 0,
+                height: self.dim.y,
+            });
+        }
+        let (width, x) = (self.dim.x as usize, x as usize);
+        let mut col = Vec::with_capacity(self.dim.y as usize);
+        let mut old = std::mem::take(&mut self.raw).into_iter();
+        self.raw.reserve_exact(old.len() - self.dim.y as usize);
+        while old.len() > 0 {
+            self.raw.extend(old.by_ref().take(x));
+            col.extend(old.next());
+            self.raw.extend(old.by_ref().take(width - x - 1));
+        }
+        self.dim.x -= 1;
+        Ok(col)
+    }
+
+    /// Resizes the grid in place to the given dimensions, keeping the values that are still in bounds at the same positions,
+    /// and filling new positions with values returned by calling `f`, in row-major order.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[1, 2, 3], [4, 5, 6]]);
+    ///
+    /// let mut next = 10;
+    /// grid.resize_with(2, 3, || {
+    ///     next += 1;
+    ///     next
+    /// });
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2], [4, 5], [11, 12]]));
+    /// ```
+    #[track_caller]
+    pub fn resize_with<F>(&mut self, width: i64, height: i64, f: F)
+    where
+        F: FnMut() -> T,
+    {
+        self.try_resize_with(width, height, f).or_panic()
+    }
+
+    /// Resizes the grid in place to the given dimensions, as in [`resize_with`](Self::resize_with),
+    /// or returns an error without changing the grid if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(2, 2, 1);
+    ///
+    /// assert_eq!(grid.try_resize_with(0, 2, || 0), Err(GridError::InvalidDimensions { width: 0, height: 2 }));
+    /// assert_eq!(grid.try_resize_with(3, 1, || 0), Ok(()));
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 1, 0]]));
+    /// ```
+    pub fn try_resize_with<F>(&mut self, width: i64, height: i64, mut f: F) -> Result<(), GridError>
+    where
+        F: FnMut() -> T,
+    {
+        let (raw, _) = try_alloc(width, height)?;
+        let (old_width, new_width) = (self.dim.x as usize, width as usize);
+        let kept = old_width.min(new_width);
+        let mut old = std::mem::replace(&mut self.raw, raw).into_iter();
+        for _ in 0..height {
+            let start = self.raw.len();
+            if old.len() > 0 {
+                self.raw.extend(old.by_ref().take(kept));
+                old.by_ref().take(old_width - kept).for_each(drop);
+            }
+            self.raw.resize_with(start + new_width, &mut f);
+        }
+        self.dim = Vector::new(width, height);
+        Ok(())
+    }
+
+    /// Resizes the grid in place to the given dimensions, keeping the values that are still in bounds at the same positions,
+    /// and filling new positions with clones of `value`.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<char> = Grid::from_nested_iter(["ab".chars(), "cd".chars()]);
+    ///
+    /// grid.resize(3, 3, '.');
+    /// assert_eq!(grid, Grid::from_nested_iter(["ab.".chars(), "cd.".chars(), "...".chars()]));
+    ///
+    /// grid.resize(1, 2, '.');
+    /// assert_eq!(grid, Grid::from_nested_iter([['a'], ['c']]));
+    /// ```
+    #[track_caller]
+    pub fn resize(&mut self, width: i64, height: i64, value: T)
+    where
+        T: Clone,
+    {
+        self.resize_with(width, height, || value.clone());
+    }
+
+    /// Resizes the grid in place to the given dimensions, as in [`resize`](Self::resize),
+    /// or returns an error without changing the grid if the dimensions are not positive or too large.
+    pub fn try_resize(&mut self, width: i64, height: i64, value: T) -> Result<(), GridError>
+    where
+        T: Clone,
+    {
+        self.try_resize_with(width, height, || value.clone())
+    }
+
+    /// Resizes the grid in place to the given dimensions, keeping the values that are still in bounds at the same positions,
+    /// and filling new positions with their default value.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(2, 1, 7);
+    ///
+    /// grid.resize_default(3, 2);
+    /// assert_eq!(grid, Grid::from_nested_iter([[7, 7, 0], [0, 0, 0]]));
+    /// ```
+    #[track_caller]
+    pub fn resize_default(&mut self, width: i64, height: i64)
+    where
+        T: Default,
+    {
+        self.resize_with(width, height, T::default);
+    }
+
+    /// Resizes the grid in place to the given dimensions, as in [`resize_default`](Self::resize_default),
+    /// or returns an error without changing the grid if the dimensions are not positive or too large.
+    pub fn try_resize_default(&mut self, width: i64, height: i64) -> Result<(), GridError>
+    where
+        T: Default,
+    {
+        self.try_resize_with(width, height, T::default)
+    }
+
+    /// Splits the grid into two at the given row, returning the rows above `y` and the rows from `y` onwards.
+    ///
+    /// Panics if either grid would be empty, that is if `y` is not between `1` and `height - 1` inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1, 2], [3, 4], [5, 6]]);
+    ///
+    /// let (top, bottom) = grid.split_at_row(1);
+    ///
+    /// assert_eq!(top, Grid::from_nested_iter([[1, 2]]));
+    /// assert_eq!(bottom, Grid::from_nested_iter([[3, 4], [5, 6]]));
+    /// ```
+    #[track_caller]
+    pub fn split_at_row(self, y: i64) -> (Self, Self) {
+        self.try_split_at_row(y).or_panic()
+    }
+
+    /// Splits the grid into two at the given row, as in [`split_at_row`](Self::split_at_row),
+    /// or returns an error if either grid would be empty, in which case the grid is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1], [2]]);
+    ///
+    /// assert!(grid.clone().try_split_at_row(2).is_err());
+    /// assert_eq!(grid.try_split_at_row(1), Ok((Grid::from_nested_iter([[1]]), Grid::from_nested_iter([[2]]))));
+    /// ```
+    pub fn try_split_at_row(mut self, y: i64) -> Result<(Self, Self), GridError> {
+        if !(1..self.dim.y).contains(&y) {
+            return Err(GridError::InvalidArgument(
+                "a split must leave both halves non-empty",
+            ));
+        }
+        let raw = self.raw.split_off((y * self.dim.x) as usize);
+        let dim = Vector::new(self.dim.x, self.dim.y - y);
+        self.dim.y = y;
+        Ok((self, Grid { raw, dim }))
+    }
+
+    /// Splits the grid into two at the given column, returning the columns to the left of `x` and the columns from `x` onwards.
+    ///
+    /// Panics if either grid would be empty, that is if `x` is not between `1` and `width - 1` inclusive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let paper: Grid<char> = Grid::from_nested_iter([
+    ///     "#..|.#.".chars(),
+    ///     ".#.|..#".chars(),
+    /// ]);
+    ///
+    /// // fold the right half over the left half
+    /// let (left, right) = paper.split_at_col(3);
+    /// let (_, right) = right.split_at_col(1);
+    /// let right = right.flip_horizontal();
+    ///
+    /// let folded = Grid::from_fn(3, 2, |pos| if left[pos] == '#' || right[pos] == '#' { '#' } else { '.' });
+    ///
+    /// assert_eq!(folded, Grid::from_nested_iter(["##.".chars(), "##.".chars()]));
+    /// ```
+    #[track_caller]
+    pub fn split_at_col(self, x: i64) -> (Self, Self) {
+        self.try_split_at_col(x).or_panic()
+    }
+
+    /// Splits the grid into two at the given column, as in [`split_at_col`](Self::split_at_col),
+    /// or returns an error if either grid would be empty, in which case the grid is dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[1, 2]]);
+    ///
+    /// assert!(grid.clone().try_split_at_col(0).is_err());
+    /// assert_eq!(grid.try_split_at_col(1), Ok((Grid::from_nested_iter([[1]]), Grid::from_nested_iter([[2]]))));
+    /// ```
+    pub fn try_split_at_col(self, x: i64) -> Result<(Self, Self), GridError> {
+        if !(1..self.dim.x).contains(&x) {
+            return Err(GridError::InvalidArgument(
+                "a split must leave both halves non-empty",
+            ));
+        }
+        let (width, x) = (self.dim.x as usize, x as usize);
+        let height = self.dim.y as usize;
+        let mut left = Vec::with_capacity(x * height);
+        let mut right = Vec::with_capacity((width - x) * height);
+        let mut values = self.raw.into_iter();
+        while values.len() > 0 {
+            left.extend(values.by_ref().take(x));
+            right.extend(values.by_ref().take(width - x));
+        }
+        Ok((
+            Grid {
+                raw: left,
+                dim: Vector::new(x as i64, self.dim.y),
+            },
+            Grid {
+                raw: right,
+                dim: Vector::new((width - x) as i64, self.dim.y),
+            },
+        ))
+    }
+}
+
+/// Collects exactly `len` values from `values`, or returns an error if there are fewer.
+fn take_exactly<T, I>(values: I, len: i64) -> Result<Vec<T>, GridError>
+where
+    I: IntoIterator<Item = T>,
+{
+    let values: Vec<T> = values.into_iter().take(len as usize).collect();
+    if values.len() < len as usize {
+        return Err(GridError::TooFewValues {
+            expected: len as usize,
+            found: values.len(),
+        });
+    }
+    Ok(values)
+}
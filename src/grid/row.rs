@@ -0,0 +1,339 @@
+//! Slices over the rows of a `Grid`.
+
+use crate::grid::Grid;
+
+use std::{iter::FusedIterator, mem, ops::Range, vec};
+
+impl<T> Grid<T> {
+    /// Returns a slice of the row of the grid at the given `y` coordinate.
+    ///
+    /// Panics if `y` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// assert_eq!(grid.row(1), &[4, 5, 6, 7]);
+    /// assert_eq!(grid.row(2).iter().sum::<i64>(), 8 + 9 + 10 + 11);
+    /// ```
+    #[track_caller]
+    pub fn row(&self, y: i64) -> &[T] {
+        let range = self.check_row(y);
+        &self.raw[range]
+    }
+
+    /// Returns a mutable slice of the row of the grid at the given `y` coordinate.
+    ///
+    /// Panics if `y` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// grid.row_mut(1).reverse();
+    /// grid.row_mut(2).fill(0);
+    ///
+    /// assert_eq!(grid.row(1), &[7, 6, 5, 4]);
+    /// assert_eq!(grid[v(3, 2)], 0);
+    /// ```
+    #[track_caller]
+    pub fn row_mut(&mut self, y: i64) -> &mut [T] {
+        let range = self.check_row(y);
+        &mut self.raw[range]
+    }
+
+    /// Returns a slice of the row of the grid at the given `y` coordinate, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// assert_eq!(grid.get_row(0), Some(&[0, 1, 2, 3][..]));
+    /// assert_eq!(grid.get_row(3), None);
+    /// assert_eq!(grid.get_row(-1), None);
+    /// ```
+    pub fn get_row(&self, y: i64) -> Option<&[T]> {
+        let range = self.row_range(y)?;
+        Some(&self.raw[range])
+    }
+
+    /// Returns a mutable slice of the row of the grid at the given `y` coordinate, or `None` if out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x + pos.y * 4);
+    ///
+    /// if let Some(row) = grid.get_row_mut(2) {
+    ///     row[0] = -1;
+    /// }
+    ///
+    /// assert_eq!(grid[v(0, 2)], -1);
+    /// assert!(grid.get_row_mut(3).is_none());
+    /// ```
+    pub fn get_row_mut(&mut self, y: i64) -> Option<&mut [T]> {
+        let range = self.row_range(y)?;
+        Some(&mut self.raw[range])
+    }
+
+    /// Returns an iterator over slices of the rows of the grid, from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(3, 4, |pos| pos.x + pos.y * 3);
+    ///
+    /// let mut rows = grid.rows();
+    ///
+    /// assert_eq!(rows.len(), 4);
+    /// assert_eq!(rows.next(), Some(&[0, 1, 2][..]));
+    /// assert_eq!(rows.next_back(), Some(&[9, 10, 11][..]));
+    /// assert_eq!(rows.len(), 2);
+    ///
+    /// let increasing = grid.rows().filter(|row| row.windows(2).all(|pair| pair[0] < pair[1])).count();
+    /// assert_eq!(increasing, 4);
+    ///
+    /// // a grid with a width of 0 still has a row for every `y` coordinate, as with `get_row`
+    /// let empty: Grid<i64> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.dim(), v(0, 2));
+    /// assert_eq!(empty.rows().collect::<Vec<_>>(), vec![&[][..], &[][..]]);
+    /// assert_eq!(empty.get_row(1), Some(&[][..]));
+    /// ```
+    #[inline]
+    pub fn rows(&self) -> Rows<'_, T> {
+        Rows {
+            raw: &self.raw,
+            width: self.width() as usize,
+            len: self.height() as usize,
+        }
+    }
+
+    /// Returns an iterator over mutable slices of the rows of the grid, from top to bottom.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<i64> = Grid::from_fn(3, 2, |pos| pos.x + pos.y * 3);
+    ///
+    /// for row in grid.rows_mut() {
+    ///     row.rotate_left(1);
+    /// }
+    ///
+    /// assert_eq!(grid.row(0), &[1, 2, 0]);
+    /// assert_eq!(grid.row(1), &[4, 5, 3]);
+    /// ```
+    #[inline]
+    pub fn rows_mut(&mut self) -> RowsMut<'_, T> {
+        RowsMut {
+            width: self.width() as usize,
+            len: self.height() as usize,
+            raw: &mut self.raw,
+        }
+    }
+
+    /// Returns an iterator over the rows of the grid, from top to bottom, each as a `Vec<T>`, consuming the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<String> = Grid::from_fn(2, 3, |pos| pos.to_string());
+    ///
+    /// let mut rows = grid.into_rows();
+    ///
+    /// assert_eq!(rows.next_back(), Some(vec!["(0, 2)".to_string(), "(1, 2)".to_string()]));
+    /// assert_eq!(rows.len(), 2);
+    /// assert_eq!(rows.next().unwrap()[1], "(1, 0)");
+    ///
+    /// let empty: Grid<String> = Grid::from_nested_iter([[], [], []]);
+    /// assert_eq!(empty.into_rows().len(), 3);
+    /// ```
+    #[inline]
+    pub fn into_rows(self) -> IntoRows<T> {
+        IntoRows {
+            width: self.width() as usize,
+            len: self.height() as usize,
+            iter: self.raw.into_iter(),
+        }
+    }
+
+    fn row_range(&self, y: i64) -> Option<Range<usize>> {
+        (0..self.height()).contains(&y).then(|| {
+            let width = self.width() as usize;
+            y as usize * width..(y as usize + 1) * width
+        })
+    }
+
+    #[track_caller]
+    fn check_row(&self, y: i64) -> Range<usize> {
+        match self.row_range(y) {
+            Some(range) => range,
+            None => panic!(
+                "row out of bounds: the height is {} but the row is {y}",
+                self.height()
+            ),
+        }
+    }
+}
+
+/// An iterator over slices of the rows of a grid, from top to bottom.
+///
+/// This is created by [`Grid::rows`].
+#[derive(Debug)]
+pub struct Rows<'a, T> {
+    raw: &'a [T],
+    width: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for Rows<'a, T> {
+    type Item = &'a [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nth(0)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if n >= self.len {
+            self.raw = &[];
+            self.len = 0;
+            return None;
+        }
+        let (row, rest) = self.raw[n * self.width..].split_at(self.width);
+        self.raw = rest;
+        self.len -= n + 1;
+        Some(row)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Rows<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        let (rest, row) = self.raw.split_at(self.len * self.width);
+        self.raw = rest;
+        Some(row)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Rows<'a, T> {}
+
+impl<'a, T> FusedIterator for Rows<'a, T> {}
+
+impl<'a, T> Clone for Rows<'a, T> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+/// An iterator over mutable slices of the rows of a grid, from top to bottom.
+///
+/// This is created by [`Grid::rows_mut`].
+#[derive(Debug)]
+pub struct RowsMut<'a, T> {
+    raw: &'a mut [T],
+    width: usize,
+    len: usize,
+}
+
+impl<'a, T> Iterator for RowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nth(0)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let raw = mem::take(&mut self.raw);
+        if n >= self.len {
+            self.len = 0;
+            return None;
+        }
+        let (row, rest) = raw[n * self.width..].split_at_mut(self.width);
+        self.raw = rest;
+        self.len -= n + 1;
+        Some(row)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for RowsMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        let (rest, row) = mem::take(&mut self.raw).split_at_mut(self.len * self.width);
+        self.raw = rest;
+        Some(row)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for RowsMut<'a, T> {}
+
+impl<'a, T> FusedIterator for RowsMut<'a, T> {}
+
+/// An iterator over the rows of a grid, each as a `Vec<T>`, from top to bottom.
+///
+/// This is created by [`Grid::into_rows`].
+pub struct IntoRows<T> {
+    iter: vec::IntoIter<T>,
+    width: usize,
+    len: usize,
+}
+
+impl<T> Iterator for IntoRows<T> {
+    type Item = Vec<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        Some(self.iter.by_ref().take(self.width).collect())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T> DoubleEndedIterator for IntoRows<T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.len = self.len.checked_sub(1)?;
+        let mut row: Vec<T> = self.iter.by_ref().rev().take(self.width).collect();
+        row.reverse();
+        Some(row)
+    }
+}
+
+impl<T> ExactSizeIterator for IntoRows<T> {}
+
+impl<T> FusedIterator for IntoRows<T> {}
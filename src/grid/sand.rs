@@ -0,0 +1,215 @@
+//! Reusable falling-sand rules for powders, liquids and gases, run as a [`System`] over a grid of user-defined tiles.
+
+use crate::{
+    grid::{set::PositionSet, system::System, Grid},
+    rng::Rng,
+    vector::{constants::*, Vector},
+};
+
+/// How a tile moves during a [`FallingSand`] step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Behavior {
+    /// The tile is empty space, which anything may move into.
+    Empty,
+    /// The tile never moves, and nothing may move into it.
+    Static,
+    /// The tile falls straight down, or diagonally down if blocked, and piles up.
+    Powder,
+    /// The tile falls like a powder, and also spreads sideways if it cannot fall.
+    Liquid,
+    /// The tile rises straight up, or diagonally up if blocked, and also spreads sideways if it cannot rise.
+    Gas,
+}
+
+/// A tile type that [`FallingSand`] can simulate.
+///
+/// # Examples
+///
+/// ```
+/// use grid::sand::{Behavior, Material};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Tile {
+///     Air,
+///     Stone,
+///     Sand,
+///     Water,
+/// }
+///
+/// impl Material for Tile {
+///     fn behavior(&self) -> Behavior {
+///         match self {
+///             Tile::Air => Behavior::Empty,
+///             Tile::Stone => Behavior::Static,
+///             Tile::Sand => Behavior::Powder,
+///             Tile::Water => Behavior::Liquid,
+///         }
+///     }
+///
+///     fn density(&self) -> u32 {
+///         match self {
+///             Tile::Sand => 2,
+///             _ => 1,
+///         }
+///     }
+/// }
+/// ```
+pub trait Material {
+    /// Returns how the tile moves.
+    fn behavior(&self) -> Behavior;
+
+    /// Returns the density of the tile, which decides whether it sinks through or floats on liquids and gases.
+    ///
+    /// A falling tile swaps places with a liquid or gas below it that has a lower density,
+    /// and a rising tile swaps places with a gas above it that has a higher density.
+    ///
+    /// The default implementation returns `0`.
+    #[inline]
+    fn density(&self) -> u32 {
+        0
+    }
+}
+
+/// A [`System`] that moves the powders, liquids and gases in a grid of [`Material`]s by at most one position per step.
+///
+/// Rows are updated from the bottom up, so that a column of powder falls together,
+/// and the direction each row is scanned in alternates between steps so that neither side is favoured.
+/// A tile that has moved or been displaced in the current step is not moved again until the next step,
+/// which has the effect of double buffering without copying the grid.
+/// Choices between two equally good directions are made at random, from a seeded generator,
+/// so the same seed always produces the same simulation.
+///
+/// Positions out of bounds block movement.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, sand::{Behavior, FallingSand, Material}, system::Runner};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum Tile {
+///     Air,
+///     Sand,
+/// }
+///
+/// impl Material for Tile {
+///     fn behavior(&self) -> Behavior {
+///         match self {
+///             Tile::Air => Behavior::Empty,
+///             Tile::Sand => Behavior::Powder,
+///         }
+///     }
+/// }
+///
+/// let mut grid: Grid<Tile> = Grid::new(5, 4, Tile::Air);
+/// for y in 0..3 {
+///     grid[v(2, y)] = Tile::Sand;
+/// }
+///
+/// let mut runner = Runner::new().with_system(FallingSand::new(42));
+/// runner.run(&mut grid, 10);
+///
+/// // the column collapses until no grain can slide any further
+/// assert_eq!(grid[v(1, 3)], Tile::Sand);
+/// assert_eq!(grid[v(2, 3)], Tile::Sand);
+/// assert_eq!(grid[v(3, 3)], Tile::Sand);
+/// assert_eq!(grid.iter().filter(|&&t| t == Tile::Sand).count(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FallingSand {
+    rng: Rng,
+    moved: Vec<bool>,
+    swapped: Vec<Vector>,
+    steps: u64,
+}
+
+impl FallingSand {
+    /// Constructs a new `FallingSand` system, whose random choices are determined by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: Rng::new(seed),
+            moved: Vec::new(),
+            swapped: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// Moves the tile at `pos` by the first offset in the given groups that it can move by.
+    ///
+    /// The two offsets in each group are tried in a random order.
+    fn try_moves<T: Material>(&mut self, grid: &mut Grid<T>, pos: Vector, groups: &[[Vector; 2]]) {
+        for &[a, b] in groups {
+            let (first, second) = if self.rng.coin() { (a, b) } else { (b, a) };
+            if self.try_move(grid, pos, first) || self.try_move(grid, pos, second) {
+                return;
+            }
+        }
+    }
+
+    /// Moves the tile at `pos` by `offset` if possible, returning `true` if it moved.
+    fn try_move<T: Material>(&mut self, grid: &mut Grid<T>, pos: Vector, offset: Vector) -> bool {
+        let target = pos + offset;
+        if !grid.in_bounds(target) {
+            return false;
+        }
+        let (i, j) = (index(grid.dim, pos), index(grid.dim, target));
+        if self.moved[j] {
+            return false;
+        }
+        let (mover, other) = (&grid.raw[i], &grid.raw[j]);
+        let allowed = match other.behavior() {
+            Behavior::Empty => true,
+            Behavior::Static | Behavior::Powder => false,
+            Behavior::Liquid | Behavior::Gas if offset.y > 0 => other.density() < mover.density(),
+            Behavior::Gas if offset.y < 0 => other.density() > mover.density(),
+            Behavior::Liquid | Behavior::Gas => false,
+        };
+        if allowed {
+            grid.raw.swap(i, j);
+            self.swapped.extend([pos, target]);
+            self.moved[i] = grid.raw[i].behavior() != Behavior::Empty;
+            self.moved[j] = true;
+        }
+        allowed
+    }
+}
+
+impl<T: Material> System<T> for FallingSand {
+    fn step(&mut self, grid: &mut Grid<T>) {
+        self.moved.clear();
+        self.moved.resize(grid.raw.len(), false);
+        self.swapped.clear();
+        let reverse = self.steps % 2 == 1;
+        self.steps += 1;
+        for y in (0..grid.dim.y).rev() {
+            for i in 0..grid.dim.x {
+                let x = if reverse { grid.dim.x - 1 - i } else { i };
+                let pos = Vector::new(x, y);
+                if self.moved[index(grid.dim, pos)] {
+                    continue;
+                }
+                match grid[pos].behavior() {
+                    Behavior::Empty | Behavior::Static => {}
+                    Behavior::Powder => self.try_moves(grid, pos, &[[SOUTH, SOUTH], [SW, SE]]),
+                    Behavior::Liquid => {
+                        self.try_moves(grid, pos, &[[SOUTH, SOUTH], [SW, SE], [WEST, EAST]])
+                    }
+                    Behavior::Gas => {
+                        self.try_moves(grid, pos, &[[NORTH, NORTH], [NW, NE], [WEST, EAST]])
+                    }
+                }
+            }
+        }
+    }
+
+    /// Advances the grid by one step, adding every position that a tile moved into or out of to `changes`.
+    fn step_changes(&mut self, grid: &mut Grid<T>, changes: &mut PositionSet) {
+        self.step(grid);
+        changes.extend(self.swapped.iter().copied());
+    }
+}
+
+#[inline]
+fn index(dim: Vector, pos: Vector) -> usize {
+    (pos.y * dim.x + pos.x) as usize
+}
@@ -0,0 +1,303 @@
+//! A sparse set of positions, with set algebra and conversions to and from `Grid<bool>`.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    rect::Rect,
+    vector::Vector,
+};
+
+use std::{collections::btree_set, collections::BTreeSet, iter::FusedIterator};
+
+/// A sparse set of positions, iterated in row-major order.
+///
+/// Unlike a `Grid<bool>`, a `PositionSet` is unbounded, so it can hold negative positions,
+/// and its memory use depends only on the number of positions it contains.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, set::PositionSet};
+///
+/// let mut visited = PositionSet::new();
+///
+/// assert!(visited.insert(v(3, 1)));
+/// assert!(visited.insert(v(-2, 0)));
+/// assert!(!visited.insert(v(3, 1)));
+///
+/// assert!(visited.contains(v(-2, 0)));
+/// assert_eq!(visited.len(), 2);
+/// assert_eq!(visited.iter().collect::<Vec<_>>(), vec![v(-2, 0), v(3, 1)]);
+/// assert_eq!(visited.bounding_box(), Some(Rect::new(v(-2, 0), v(6, 2))));
+/// ```
+#[derive(PartialEq, Eq, Clone, Hash, Debug, Default)]
+pub struct PositionSet {
+    // keyed by (y, x) so that iteration is in row-major order
+    set: BTreeSet<(i64, i64)>,
+}
+
+impl PositionSet {
+    /// Constructs a new, empty `PositionSet`.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Constructs a new `PositionSet` containing the positions of `grid` whose values are `true`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet};
+    ///
+    /// let grid: Grid<bool> = Grid::from_nested_iter([
+    ///     [false, true, false],
+    ///     [true, false, false],
+    /// ]);
+    ///
+    /// let set = PositionSet::from_grid(&grid);
+    ///
+    /// assert_eq!(set.iter().collect::<Vec<_>>(), vec![v(1, 0), v(0, 1)]);
+    /// assert_eq!(set.to_grid(grid.dim()), grid);
+    /// ```
+    pub fn from_grid(grid: &Grid<bool>) -> Self {
+        grid.iter_positions()
+            .filter(|&(_, &value)| value)
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+
+    /// Returns a new `Grid<bool>` with the given dimensions, where the positions in the set are `true`.
+    ///
+    /// Positions in the set that are out of bounds of the grid are ignored.
+    ///
+    /// Panics if the dimensions are not positive or too large.
+    #[track_caller]
+    pub fn to_grid(&self, dim: Vector) -> Grid<bool> {
+        self.try_to_grid(dim).or_panic()
+    }
+
+    /// Returns a new `Grid<bool>` where the positions in the set are `true`, as in [`to_grid`](Self::to_grid),
+    /// or an error if the dimensions are not positive or too large.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet};
+    ///
+    /// let set: PositionSet = [v(0, 0), v(5, 5)].into_iter().collect();
+    ///
+    /// assert_eq!(set.try_to_grid(v(2, 1)), Ok(Grid::from_nested_iter([[true, false]])));
+    /// assert_eq!(set.try_to_grid(v(2, 0)), Err(GridError::InvalidDimensions { width: 2, height: 0 }));
+    /// ```
+    pub fn try_to_grid(&self, dim: Vector) -> Result<Grid<bool>, GridError> {
+        let mut grid = Grid::try_new(dim.x, dim.y, false)?;
+        for pos in self {
+            grid.set(pos, true);
+        }
+        Ok(grid)
+    }
+
+    /// Returns the number of positions in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    /// Returns `true` if the set contains no positions, or `false` otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.set.is_empty()
+    }
+
+    /// Removes every position from the set.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.set.clear();
+    }
+
+    /// Returns `true` if the set contains the given position, or `false` otherwise.
+    #[inline]
+    pub fn contains(&self, pos: Vector) -> bool {
+        self.set.contains(&key(pos))
+    }
+
+    /// Adds a position to the set.
+    ///
+    /// Returns `true` if the position was not already in the set, or `false` otherwise.
+    #[inline]
+    pub fn insert(&mut self, pos: Vector) -> bool {
+        self.set.insert(key(pos))
+    }
+
+    /// Removes a position from the set.
+    ///
+    /// Returns `true` if the position was in the set, or `false` otherwise.
+    #[inline]
+    pub fn remove(&mut self, pos: Vector) -> bool {
+        self.set.remove(&key(pos))
+    }
+
+    /// Returns an iterator over the positions in the set, in row-major order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_> {
+        Iter {
+            iter: self.set.iter(),
+        }
+    }
+
+    /// Returns a new set containing the positions in `self`, `other`, or both.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet};
+    ///
+    /// let a: PositionSet = [v(0, 0), v(1, 0), v(2, 0)].into_iter().collect();
+    /// let b: PositionSet = [v(1, 0), v(2, 0), v(3, 0)].into_iter().collect();
+    ///
+    /// assert_eq!(a.union(&b).len(), 4);
+    /// assert_eq!(a.intersection(&b).iter().collect::<Vec<_>>(), vec![v(1, 0), v(2, 0)]);
+    /// assert_eq!(a.difference(&b).iter().collect::<Vec<_>>(), vec![v(0, 0)]);
+    /// assert_eq!(a.symmetric_difference(&b).iter().collect::<Vec<_>>(), vec![v(0, 0), v(3, 0)]);
+    /// assert!(a.intersection(&b).is_subset(&a));
+    /// ```
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            set: self.set.union(&other.set).copied().collect(),
+        }
+    }
+
+    /// Returns a new set containing the positions in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        Self {
+            set: self.set.intersection(&other.set).copied().collect(),
+        }
+    }
+
+    /// Returns a new set containing the positions in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        Self {
+            set: self.set.difference(&other.set).copied().collect(),
+        }
+    }
+
+    /// Returns a new set containing the positions in exactly one of `self` and `other`.
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        Self {
+            set: self.set.symmetric_difference(&other.set).copied().collect(),
+        }
+    }
+
+    /// Returns `true` if every position in `self` is also in `other`, or `false` otherwise.
+    #[inline]
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.set.is_subset(&other.set)
+    }
+
+    /// Returns `true` if `self` and `other` have no positions in common, or `false` otherwise.
+    #[inline]
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.set.is_disjoint(&other.set)
+    }
+
+    /// Returns a new set containing every position of `self` moved by `offset`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet};
+    ///
+    /// let set: PositionSet = [v(0, 0), v(2, 1)].into_iter().collect();
+    ///
+    /// let moved = set.translate(v(-1, 3));
+    ///
+    /// assert_eq!(moved.iter().collect::<Vec<_>>(), vec![v(-1, 3), v(1, 4)]);
+    /// ```
+    pub fn translate(&self, offset: Vector) -> Self {
+        // translation preserves row-major order, so the new tree can be built from sorted keys
+        Self {
+            set: self
+                .set
+                .iter()
+                .map(|&(y, x)| (y + offset.y, x + offset.x))
+                .collect(),
+        }
+    }
+
+    /// Returns the smallest rectangle containing every position in the set, or `None` if the set is empty.
+    pub fn bounding_box(&self) -> Option<Rect> {
+        let &(min_y, _) = self.set.first()?;
+        let &(max_y, _) = self.set.last()?;
+        let (min_x, max_x) = self
+            .set
+            .iter()
+            .fold((i64::MAX, i64::MIN), |(min, max), &(_, x)| {
+                (min.min(x), max.max(x))
+            });
+        Some(Rect::from_corners(
+            Vector::new(min_x, min_y),
+            Vector::new(max_x + 1, max_y + 1),
+        ))
+    }
+}
+
+#[inline]
+fn key(pos: Vector) -> (i64, i64) {
+    (pos.y, pos.x)
+}
+
+impl FromIterator<Vector> for PositionSet {
+    fn from_iter<I: IntoIterator<Item = Vector>>(iter: I) -> Self {
+        Self {
+            set: iter.into_iter().map(key).collect(),
+        }
+    }
+}
+
+impl Extend<Vector> for PositionSet {
+    fn extend<I: IntoIterator<Item = Vector>>(&mut self, iter: I) {
+        self.set.extend(iter.into_iter().map(key));
+    }
+}
+
+impl<'a> IntoIterator for &'a PositionSet {
+    type Item = Vector;
+    type IntoIter = Iter<'a>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the positions in a [`PositionSet`], in row-major order.
+#[derive(Clone)]
+pub struct Iter<'a> {
+    iter: btree_set::Iter<'a, (i64, i64)>,
+}
+
+impl<'a> Iterator for Iter<'a> {
+    type Item = Vector;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|&(y, x)| Vector::new(x, y))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for Iter<'a> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|&(y, x)| Vector::new(x, y))
+    }
+}
+
+impl<'a> ExactSizeIterator for Iter<'a> {}
+
+impl<'a> FusedIterator for Iter<'a> {}
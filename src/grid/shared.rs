@@ -0,0 +1,121 @@
+//! A `Grid` with cheap immutable snapshots, for reading on other threads while writing continues.
+
+use std::{ops::Index, sync::Arc};
+
+use crate::{grid::Grid, vector::Vector};
+
+impl<T> Grid<T> {
+    /// Moves the grid into a [`SharedGrid`], which can hand out cheap snapshots of it.
+    #[inline]
+    pub fn into_shared(self) -> SharedGrid<T> {
+        SharedGrid {
+            grid: Arc::new(self),
+        }
+    }
+}
+
+/// A grid that can hand out cheap immutable snapshots of itself, with copy-on-write for the writer.
+///
+/// Taking a snapshot only bumps a reference count. The first write after a snapshot is taken copies the values
+/// if the snapshot is still alive, so that the snapshot never changes, and later writes go straight to the copy.
+/// A writer that takes one snapshot per frame therefore copies the grid at most once per frame,
+/// and not at all if readers drop their snapshots before the next write.
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let mut world = Grid::new(64, 64, 0u32).into_shared();
+///
+/// for frame in 1..=3 {
+///     let snapshot = world.snapshot();
+///     let reader = std::thread::spawn(move || snapshot.iter().sum::<u32>());
+///
+///     // the writer carries on while the reader sums the previous frame
+///     world.set(v(frame, 0), frame as u32);
+///
+///     let expected: u32 = (1..frame as u32).sum();
+///     assert_eq!(reader.join().unwrap(), expected);
+/// }
+///
+/// assert_eq!(world[v(3, 0)], 3);
+/// ```
+#[derive(PartialEq, Eq, Clone, Hash)]
+pub struct SharedGrid<T> {
+    grid: Arc<Grid<T>>,
+}
+
+impl<T> SharedGrid<T> {
+    /// Returns an immutable snapshot of the grid as it is now, which later writes will not change.
+    #[inline]
+    pub fn snapshot(&self) -> Arc<Grid<T>> {
+        Arc::clone(&self.grid)
+    }
+
+    /// Returns a reference to the grid.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Returns `true` if any snapshots of the current values are still alive, so that the next write will copy them,
+    /// or `false` otherwise.
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.grid) > 1
+    }
+
+    /// Returns the dimensions of the grid.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.grid.dim
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.grid.get(pos)
+    }
+}
+
+impl<T: Clone> SharedGrid<T> {
+    /// Returns a mutable reference to the grid, first copying the values if any snapshots of them are still alive.
+    #[inline]
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        Arc::make_mut(&mut self.grid)
+    }
+
+    /// Returns a mutable reference to the value at the given position, or `None` if out of bounds,
+    /// first copying the values if any snapshots of them are still alive.
+    #[inline]
+    pub fn get_mut(&mut self, pos: Vector) -> Option<&mut T> {
+        if !self.grid.in_bounds(pos) {
+            return None;
+        }
+        self.grid_mut().get_mut(pos)
+    }
+
+    /// Sets the value at the given position, first copying the values if any snapshots of them are still alive.
+    ///
+    /// Returns the old value at that position, or `None` if out of bounds, in which case nothing is copied.
+    #[inline]
+    pub fn set(&mut self, pos: Vector, value: T) -> Option<T> {
+        Some(std::mem::replace(self.get_mut(pos)?, value))
+    }
+
+    /// Returns the grid, copying the values if any snapshots of them are still alive.
+    #[inline]
+    pub fn into_grid(self) -> Grid<T> {
+        Arc::unwrap_or_clone(self.grid)
+    }
+}
+
+impl<T> Index<Vector> for SharedGrid<T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, pos: Vector) -> &Self::Output {
+        &self.grid[pos]
+    }
+}
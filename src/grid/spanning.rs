@@ -0,0 +1,214 @@
+//! Minimum spanning trees over the orthogonal neighbours of a `Grid`, for maze generation and network layout.
+//!
+//! The graph is implicit: every cell is a vertex, every edge between two orthogonal neighbours is an edge,
+//! and the weight of each edge is given by a closure, so no explicit graph is ever built.
+
+use crate::{
+    grid::{dual::EdgeId, Grid},
+    trace::{event, span},
+    vector::{constants::ORTHOGONAL, Axis, Vector},
+};
+
+impl<T> Grid<T> {
+    /// Returns the edges of a minimum spanning tree of the grid, in ascending order,
+    /// where `weight(a, b)` is the weight of the edge between the orthogonal neighbours `a` and `b`.
+    ///
+    /// `weight` is called exactly once for each edge separating two cells of the grid,
+    /// with `a` the top or left cell, as in [`EdgeId::cells`].
+    /// Edges of equal weight are ordered as `EdgeId`s, which makes the tree unique:
+    /// it is the tree found by adding edges in ascending order of `(weight, edge)` whenever they join two separate parts.
+    ///
+    /// This uses a variant of Prim's algorithm that relies on the shape of the grid. With at most two edges per cell,
+    /// the edges are first ranked in ascending order of `(weight, edge)` by a radix sort on their integer weights, in `O(n)` time for `n` cells.
+    /// The priority queue then only holds distinct ranks below `2n`, in a tree of 64-bit words that takes one step per level
+    /// for each insertion or removal. The tree has `⌈log₆₄ 2n⌉` levels, which is at most `6` for any grid with fewer than `2³⁵` cells,
+    /// so the whole tree is found in `O(n)` time for any grid that fits in memory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{dual::EdgeId, prelude::*};
+    ///
+    /// // join the cells with the smallest total change in height
+    /// let heights: Grid<u64> = Grid::from_nested_iter([
+    ///     [1, 1, 5],
+    ///     [9, 2, 5],
+    ///     [9, 9, 6],
+    /// ]);
+    ///
+    /// let tree = heights.mst(|a, b| heights[a].abs_diff(heights[b]));
+    ///
+    /// assert_eq!(tree.len(), 9 - 1);
+    /// assert!(tree.contains(&EdgeId::between(v(0, 0), v(1, 0)).unwrap()));
+    /// assert!(tree.contains(&EdgeId::between(v(1, 0), v(1, 1)).unwrap()));
+    ///
+    /// let total: u64 = tree
+    ///     .iter()
+    ///     .map(|edge| {
+    ///         let [a, b] = edge.cells();
+    ///         heights[a].abs_diff(heights[b])
+    ///     })
+    ///     .sum();
+    ///
+    /// assert_eq!(total, 0 + 0 + 0 + 0 + 1 + 1 + 3 + 3);
+    /// ```
+    pub fn mst<F>(&self, mut weight: F) -> Vec<EdgeId>
+    where
+        F: FnMut(Vector, Vector) -> u64,
+    {
+        if self.raw.is_empty() {
+            return Vec::new();
+        }
+        span!("mst", cells = self.raw.len());
+        // every edge separating two cells, in ascending order of `EdgeId`, which compares the starts first,
+        // and `Vector` derives `Ord` from `x` then `y`, so the columns go in the outer loop
+        let mut edges = Vec::with_capacity(2 * self.raw.len());
+        for x in 0..self.dim.x {
+            for y in 0..self.dim.y {
+                let start = Vector::new(x, y);
+                if y > 0 {
+                    edges.push(EdgeId::new(start, Axis::X));
+                }
+                if x > 0 {
+                    edges.push(EdgeId::new(start, Axis::Y));
+                }
+            }
+        }
+        let weights: Vec<u64> = edges
+            .iter()
+            .map(|edge| {
+                let [a, b] = edge.cells();
+                weight(a, b)
+            })
+            .collect();
+
+        // each edge is found from its start and axis, at one of two slots for every cell
+        let slot = |edge: EdgeId| 2 * self.get_index(edge.start()).unwrap() + edge.axis() as usize;
+        let order = radix_sort(&weights);
+        let mut ranks = vec![0; 2 * self.raw.len()];
+        for (rank, &i) in order.iter().enumerate() {
+            ranks[slot(edges[i])] = rank;
+        }
+
+        let mut in_tree = vec![false; self.raw.len()];
+        let mut tree_edges = vec![false; edges.len()];
+        let mut queue = RankQueue::new(edges.len());
+        let mut joined = 0;
+        let mut next = Some(Vector::new(0, 0));
+        while let Some(pos) = next.take() {
+            in_tree[self.get_index(pos).unwrap()] = true;
+            if joined == self.raw.len() - 1 {
+                break;
+            }
+            for offset in ORTHOGONAL {
+                let neighbor = pos + offset;
+                if self.get_index(neighbor).is_some_and(|j| !in_tree[j]) {
+                    queue.insert(ranks[slot(EdgeId::between(pos, neighbor).unwrap())]);
+                }
+            }
+            // the cheapest edge leaving the tree, skipping those whose cells have both joined it since
+            while let Some(rank) = queue.pop() {
+                let i = order[rank];
+                let cell = edges[i]
+                    .cells()
+                    .into_iter()
+                    .find(|&cell| !in_tree[self.get_index(cell).unwrap()]);
+                if cell.is_some() {
+                    tree_edges[i] = true;
+                    joined += 1;
+                    next = cell;
+                    break;
+                }
+            }
+        }
+        let tree: Vec<EdgeId> = edges
+            .into_iter()
+            .zip(tree_edges)
+            .filter_map(|(edge, in_tree)| in_tree.then_some(edge))
+            .collect();
+        event!(edges = tree.len(), "spanning tree found");
+        tree
+    }
+}
+
+/// Returns the indices of `keys` in ascending order of key, with equal keys in ascending order of index.
+///
+/// This is a least significant digit radix sort, one byte per pass, skipping the bytes that every key has in common.
+fn radix_sort(keys: &[u64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut buffer = vec![0; keys.len()];
+    let differ = keys.iter().fold(0, |differ, &key| differ | (key ^ keys[0]));
+    for shift in (0..u64::BITS)
+        .step_by(8)
+        .filter(|&shift| (differ >> shift) & 0xff != 0)
+    {
+        let digit = |i: usize| ((keys[i] >> shift) & 0xff) as usize;
+        let mut starts = [0; 257];
+        for &i in &order {
+            starts[digit(i) + 1] += 1;
+        }
+        for d in 0..256 {
+            starts[d + 1] += starts[d];
+        }
+        for &i in &order {
+            buffer[starts[digit(i)]] = i;
+            starts[digit(i)] += 1;
+        }
+        std::mem::swap(&mut order, &mut buffer);
+    }
+    order
+}
+
+/// A set of distinct integers below a fixed bound, stored as a tree of 64-bit words with a bit for each child that is not empty,
+/// so the smallest is found by following the lowest set bit down from the root.
+struct RankQueue {
+    /// The words of each level of the tree, from the leaves, which have a bit for each integer, up to the single root word.
+    levels: Vec<Vec<u64>>,
+}
+
+impl RankQueue {
+    fn new(bound: usize) -> Self {
+        let mut levels = Vec::new();
+        let mut len = bound.max(1);
+        loop {
+            len = len.div_ceil(64);
+            levels.push(vec![0; len]);
+            if len == 1 {
+                return Self { levels };
+            }
+        }
+    }
+
+    fn insert(&mut self, mut i: usize) {
+        for level in &mut self.levels {
+            let word = &mut level[i / 64];
+            let was_empty = *word == 0;
+            *word |= 1 << (i % 64);
+            if !was_empty {
+                break;
+            }
+            i /= 64;
+        }
+    }
+
+    /// Removes and returns the smallest integer in the set, or `None` if it is empty.
+    fn pop(&mut self) -> Option<usize> {
+        if self.levels.last()?[0] == 0 {
+            return None;
+        }
+        let mut i = 0;
+        for level in self.levels.iter().rev() {
+            i = i * 64 + level[i].trailing_zeros() as usize;
+        }
+        let smallest = i;
+        for level in &mut self.levels {
+            let word = &mut level[i / 64];
+            *word &= !(1 << (i % 64));
+            if *word != 0 {
+                break;
+            }
+            i /= 64;
+        }
+        Some(smallest)
+    }
+}
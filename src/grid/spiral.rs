@@ -0,0 +1,260 @@
+//! Iterating over a `Grid` in spiral order, either from the border inwards or from a position outwards.
+
+use crate::{
+    grid::{
+        iterators::{border_len, border_pos},
+        Grid,
+    },
+    vector::{
+        constants::{EAST, ORTHOGONAL},
+        Vector,
+    },
+};
+
+use std::{iter::FusedIterator, ops::Range};
+
+impl<T> Grid<T> {
+    /// Returns an iterator over every position and value in the grid, spiralling inwards:
+    /// clockwise around the border from the top-left corner, then around the border of what is left, and so on to the middle.
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    ///     [7, 8, 9],
+    /// ]);
+    ///
+    /// let values: Vec<u8> = grid.iter_spiral().map(|(_, &value)| value).collect();
+    ///
+    /// assert_eq!(values, vec![1, 2, 3, 6, 9, 8, 7, 4, 5]);
+    ///
+    /// let grid: Grid<u8> = Grid::new(4, 3, 0);
+    ///
+    /// let positions: Vec<Vector> = grid.iter_spiral().map(|(pos, _)| pos).collect();
+    ///
+    /// assert_eq!(positions, vec![
+    ///     v(0, 0), v(1, 0), v(2, 0), v(3, 0),
+    ///     v(3, 1), v(3, 2), v(2, 2), v(1, 2), v(0, 2), v(0, 1),
+    ///     v(1, 1), v(2, 1),
+    /// ]);
+    /// ```
+    pub fn iter_spiral(&self) -> Spiral<'_, T> {
+        Spiral {
+            grid: self,
+            top_left: Vector::new(0, 0),
+            dim: self.dim,
+            index: 0,
+            remaining: self.raw.len(),
+        }
+    }
+
+    /// Returns an iterator over every position and value in the grid, spiralling outwards from `center`
+    /// in a square spiral, as in the Ulam spiral: one step east, one north, two west, two south, three east, and so on.
+    ///
+    /// Positions of the spiral that are out of bounds are skipped, and the iterator ends once every position in the grid
+    /// has been visited. `center` itself need not be in bounds.
+    ///
+    /// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+    /// `(Vector, &T)`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<usize> = Grid::new(5, 5, 0);
+    ///
+    /// let order: Vec<Vector> = grid.iter_spiral_from(v(2, 2)).map(|(pos, _)| pos).collect();
+    ///
+    /// let mut ulam = grid.clone();
+    /// for (i, pos) in order.into_iter().enumerate() {
+    ///     ulam[pos] = i + 1;
+    /// }
+    ///
+    /// assert_eq!(ulam, Grid::from_nested_iter([
+    ///     [17, 16, 15, 14, 13],
+    ///     [18, 5, 4, 3, 12],
+    ///     [19, 6, 1, 2, 11],
+    ///     [20, 7, 8, 9, 10],
+    ///     [21, 22, 23, 24, 25],
+    /// ]));
+    ///
+    /// // search outwards for the nearest match, by Chebyshev distance
+    /// let map: Grid<char> = Grid::from_nested_iter([
+    ///     "#.......".chars(),
+    ///     "........".chars(),
+    ///     ".....#..".chars(),
+    /// ]);
+    ///
+    /// let nearest = map.iter_spiral_from(v(2, 1)).find(|&(_, &c)| c == '#');
+    ///
+    /// assert_eq!(nearest, Some((v(0, 0), &'#')));
+    /// assert_eq!(map.iter_spiral_from(v(7, 0)).len(), 24);
+    ///
+    /// // a center far outside the grid starts at the first ring that reaches it
+    /// let far = map.iter_spiral_from(v(i64::MAX, -1_000_000_000)).next();
+    /// assert_eq!(far, Some((v(7, 0), &'.')));
+    /// ```
+    pub fn iter_spiral_from(&self, center: Vector) -> SpiralFrom<'_, T> {
+        let (x, y) = (center.x as i128, center.y as i128);
+        let (width, height) = (self.dim.x as i128, self.dim.y as i128);
+        // the rings closer to the center than the grid are skipped, since none of their positions are in bounds
+        let ring = (-x).max(x - width + 1).max(-y).max(y - height + 1).max(0);
+        let mut spiral = SpiralFrom {
+            grid: self,
+            corner: (x, y),
+            segment: 0,
+            steps: 0..0,
+            remaining: self.raw.len(),
+        };
+        if ring == 0 {
+            spiral.steps = spiral.clip(0, 0);
+        } else {
+            // ring `r` starts on the last step of segment `4r - 3`, which runs east along the bottom of ring `r - 1`
+            spiral.corner = (x - (ring - 1), y + (ring - 1));
+            spiral.segment = 4 * ring - 3;
+            spiral.steps = spiral.clip(2 * ring - 1, 2 * ring - 1);
+        }
+        spiral
+    }
+}
+
+/// An iterator over every position and value in a `Grid`, spiralling inwards from the top-left corner.
+///
+/// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+/// `(Vector, &T)`
+///
+/// Constructed by [`Grid::iter_spiral`].
+pub struct Spiral<'a, T> {
+    grid: &'a Grid<T>,
+    top_left: Vector,
+    dim: Vector,
+    index: usize,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Spiral<'a, T> {
+    type Item = (Vector, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        if self.index == border_len(self.dim) {
+            self.top_left += Vector::new(1, 1);
+            self.dim -= Vector::new(2, 2);
+            self.index = 0;
+        }
+        let pos = self.top_left + border_pos(self.dim, self.index);
+        self.index += 1;
+        self.remaining -= 1;
+        Some((pos, &self.grid[pos]))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Spiral<'a, T> {}
+
+impl<'a, T> FusedIterator for Spiral<'a, T> {}
+
+/// An iterator over every position and value in a `Grid`, spiralling outwards from a position.
+///
+/// Values from this iterator come in the form of a tuple containing the position and a reference to the value:
+/// `(Vector, &T)`
+///
+/// Constructed by [`Grid::iter_spiral_from`].
+pub struct SpiralFrom<'a, T> {
+    grid: &'a Grid<T>,
+    // wide enough that the rings around any center reaching every position of the grid cannot overflow
+    corner: (i128, i128),
+    segment: i128,
+    steps: Range<i128>,
+    remaining: usize,
+}
+
+impl<'a, T> SpiralFrom<'a, T> {
+    /// Returns the direction of the current segment of the spiral.
+    ///
+    /// Segment 0 is the center alone, and segment `k` after it runs `(k + 1) / 2` steps from the end of the last.
+    fn direction(&self) -> (i128, i128) {
+        let direction = match self.segment {
+            0 => EAST,
+            k => ORTHOGONAL[((k - 1) % 4) as usize],
+        };
+        (direction.x as i128, direction.y as i128)
+    }
+
+    /// Returns the length of the current segment of the spiral.
+    fn length(&self) -> i128 {
+        (self.segment + 1) / 2
+    }
+
+    /// Returns the steps from `first` to `last` inclusive along the current segment that land in bounds,
+    /// which are always contiguous as the segment is a straight line.
+    fn clip(&self, first: i128, last: i128) -> Range<i128> {
+        let (mut start, mut end) = (first, last + 1);
+        let direction = self.direction();
+        for (corner, step, size) in [
+            (self.corner.0, direction.0, self.grid.dim.x as i128),
+            (self.corner.1, direction.1, self.grid.dim.y as i128),
+        ] {
+            match step {
+                0 if !(0..size).contains(&corner) => return 0..0,
+                1 => (start, end) = (start.max(-corner), end.min(size - corner)),
+                -1 => (start, end) = (start.max(corner - size + 1), end.min(corner + 1)),
+                _ => {}
+            }
+        }
+        start..end.max(start)
+    }
+}
+
+impl<'a, T> Iterator for SpiralFrom<'a, T> {
+    type Item = (Vector, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        loop {
+            let direction = self.direction();
+            if let Some(step) = self.steps.next() {
+                let pos = Vector::new(
+                    (self.corner.0 + direction.0 * step) as i64,
+                    (self.corner.1 + direction.1 * step) as i64,
+                );
+                self.remaining -= 1;
+                return Some((pos, &self.grid[pos]));
+            }
+            let length = self.length();
+            self.corner = (
+                self.corner.0 + direction.0 * length,
+                self.corner.1 + direction.1 * length,
+            );
+            self.segment += 1;
+            self.steps = self.clip(1, self.length());
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for SpiralFrom<'a, T> {}
+
+impl<'a, T> FusedIterator for SpiralFrom<'a, T> {}
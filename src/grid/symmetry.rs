@@ -0,0 +1,180 @@
+//! Writing to a `Grid` symmetrically, so that generated maps are symmetric by construction.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+/// A symmetry of a grid, which maps each position to a set of images that should all hold the same value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    /// Mirrored left to right, across the vertical line through the centre.
+    Horizontal,
+    /// Mirrored top to bottom, across the horizontal line through the centre.
+    Vertical,
+    /// Mirrored both left to right and top to bottom.
+    Both,
+    /// Rotated by half a turn about the centre.
+    HalfTurn,
+    /// Rotated by quarter turns about the centre, which requires a square grid.
+    QuarterTurn,
+}
+
+impl Symmetry {
+    /// Returns the images of `pos` under this symmetry in a grid with dimensions `dim`, starting with `pos` itself,
+    /// without duplicates.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, symmetry::Symmetry};
+    ///
+    /// let dim = v(5, 4);
+    ///
+    /// assert_eq!(Symmetry::Horizontal.images(v(1, 1), dim), vec![v(1, 1), v(3, 1)]);
+    /// assert_eq!(Symmetry::Horizontal.images(v(2, 1), dim), vec![v(2, 1)]);
+    /// assert_eq!(Symmetry::Both.images(v(0, 3), dim), vec![v(0, 3), v(4, 3), v(0, 0), v(4, 0)]);
+    /// assert_eq!(Symmetry::HalfTurn.images(v(0, 1), dim), vec![v(0, 1), v(4, 2)]);
+    /// assert_eq!(Symmetry::QuarterTurn.images(v(1, 0), v(4, 4)), vec![v(1, 0), v(3, 1), v(2, 3), v(0, 2)]);
+    /// ```
+    pub fn images(self, pos: Vector, dim: Vector) -> Vec<Vector> {
+        let max = dim - Vector::new(1, 1);
+        let flip_x = Vector::new(max.x - pos.x, pos.y);
+        let flip_y = Vector::new(pos.x, max.y - pos.y);
+        let mut images = match self {
+            Self::Horizontal => vec![pos, flip_x],
+            Self::Vertical => vec![pos, flip_y],
+            Self::Both => vec![pos, flip_x, flip_y, max - pos],
+            Self::HalfTurn => vec![pos, max - pos],
+            Self::QuarterTurn => vec![
+                pos,
+                Vector::new(max.x - pos.y, pos.x),
+                max - pos,
+                Vector::new(pos.y, max.y - pos.x),
+            ],
+        };
+        let mut i = 1;
+        while i < images.len() {
+            if images[..i].contains(&images[i]) {
+                images.remove(i);
+            } else {
+                i += 1;
+            }
+        }
+        images
+    }
+}
+
+/// A mutable borrow of a `Grid` that mirrors every write to all the images of the written position under a [`Symmetry`].
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, symmetry::{Symmetric, Symmetry}};
+///
+/// let mut map: Grid<char> = Grid::new(7, 5, '.');
+///
+/// let mut symmetric = Symmetric::new(&mut map, Symmetry::HalfTurn);
+/// symmetric.set(v(1, 1), 'A');
+/// symmetric.set(v(3, 2), '#');
+/// symmetric.update(v(2, 0), |c| *c = '#');
+///
+/// assert_eq!(map, Grid::from_nested_iter([
+///     "..#....".chars(),
+///     ".A.....".chars(),
+///     "...#...".chars(),
+///     ".....A.".chars(),
+///     "....#..".chars(),
+/// ]));
+/// ```
+pub struct Symmetric<'a, T> {
+    grid: &'a mut Grid<T>,
+    symmetry: Symmetry,
+}
+
+impl<'a, T> Symmetric<'a, T> {
+    /// Constructs a new `Symmetric` over the given grid.
+    ///
+    /// Values already in the grid are left as they are, so the grid is only symmetric if it was to begin with,
+    /// or once every position has been written.
+    ///
+    /// Panics if the symmetry is [`Symmetry::QuarterTurn`] and the grid is not square.
+    #[track_caller]
+    pub fn new(grid: &'a mut Grid<T>, symmetry: Symmetry) -> Self {
+        Self::try_new(grid, symmetry).or_panic()
+    }
+
+    /// Constructs a new `Symmetric` over the given grid, as in [`new`](Self::new),
+    /// or returns an error if the symmetry is [`Symmetry::QuarterTurn`] and the grid is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, symmetry::{Symmetric, Symmetry}};
+    ///
+    /// let mut map: Grid<char> = Grid::new(5, 3, '.');
+    ///
+    /// assert!(Symmetric::try_new(&mut map, Symmetry::Both).is_ok());
+    /// assert!(Symmetric::try_new(&mut map, Symmetry::QuarterTurn).is_err());
+    /// ```
+    pub fn try_new(grid: &'a mut Grid<T>, symmetry: Symmetry) -> Result<Self, GridError> {
+        if symmetry == Symmetry::QuarterTurn && grid.width() != grid.height() {
+            return Err(GridError::InvalidArgument(
+                "quarter turn symmetry requires a square grid",
+            ));
+        }
+        Ok(Self { grid, symmetry })
+    }
+
+    /// Returns a reference to the underlying grid.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        self.grid
+    }
+
+    /// Returns the symmetry that writes are mirrored under.
+    #[inline]
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.grid.get(pos)
+    }
+
+    /// Returns the images of the given position under the symmetry, starting with `pos` itself,
+    /// or an empty `Vec` if out of bounds.
+    pub fn images(&self, pos: Vector) -> Vec<Vector> {
+        if !self.grid.in_bounds(pos) {
+            return Vec::new();
+        }
+        self.symmetry.images(pos, self.grid.dim())
+    }
+
+    /// Sets the value at the given position and all its images.
+    ///
+    /// Returns `false` if `pos` is out of bounds, in which case nothing changes, or `true` otherwise.
+    pub fn set(&mut self, pos: Vector, value: T) -> bool
+    where
+        T: Clone,
+    {
+        self.update(pos, |old| *old = value.clone())
+    }
+
+    /// Calls `f` on the value at the given position and on the value at each of its images.
+    ///
+    /// Returns `false` if `pos` is out of bounds, in which case nothing changes, or `true` otherwise.
+    pub fn update<F>(&mut self, pos: Vector, mut f: F) -> bool
+    where
+        F: FnMut(&mut T),
+    {
+        let images = self.images(pos);
+        for &image in &images {
+            f(&mut self.grid[image]);
+        }
+        !images.is_empty()
+    }
+}
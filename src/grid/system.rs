@@ -0,0 +1,347 @@
+//! Composable simulation steps over a `Grid`, a runner that schedules them, and iteration until a grid stops changing.
+
+use crate::{
+    grid::{set::PositionSet, Grid},
+    trace::{event, span},
+};
+
+/// A rule that updates a grid in place, once per simulation step.
+///
+/// This is implemented for every closure of type `FnMut(&mut Grid<T>)`.
+pub trait System<T> {
+    /// Advances the grid by one step of this system.
+    fn step(&mut self, grid: &mut Grid<T>);
+
+    /// Advances the grid by one step of this system, adding every position whose value it changed to `changes`.
+    ///
+    /// Systems that know which positions they change, such as [`FallingSand`](crate::sand::FallingSand) and [`Tracked`],
+    /// override this to record them as they go, in which case they may also report positions that were changed and then changed back within the step.
+    /// The default implementation is for systems that cannot track their own changes, such as plain closures:
+    /// it reports every position of the grid, since any of them may have changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet, system::System};
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(3, 2, 0);
+    /// let mut changes = PositionSet::new();
+    ///
+    /// let mut system = |grid: &mut Grid<u8>| grid[v(1, 1)] = 5;
+    /// system.step_changes(&mut grid, &mut changes);
+    ///
+    /// assert_eq!(changes.len(), 6);
+    /// ```
+    fn step_changes(&mut self, grid: &mut Grid<T>, changes: &mut PositionSet) {
+        self.step(grid);
+        changes.extend(grid.positions());
+    }
+}
+
+impl<T, F> System<T> for F
+where
+    F: FnMut(&mut Grid<T>),
+{
+    #[inline]
+    fn step(&mut self, grid: &mut Grid<T>) {
+        self(grid)
+    }
+}
+
+/// A [`System`] made from a closure that records the positions it changes, so that [`System::step_changes`] reports only those.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, set::PositionSet, system::{System, Tracked}};
+///
+/// let mut grid: Grid<u8> = Grid::new(3, 3, 0);
+/// let mut changes = PositionSet::new();
+///
+/// let mut system = Tracked::new(|grid: &mut Grid<u8>, changes: &mut PositionSet| {
+///     grid[v(1, 1)] = 5;
+///     changes.insert(v(1, 1));
+/// });
+///
+/// system.step_changes(&mut grid, &mut changes);
+///
+/// assert_eq!(changes.iter().collect::<Vec<_>>(), vec![v(1, 1)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Tracked<F> {
+    f: F,
+}
+
+impl<F> Tracked<F> {
+    /// Constructs a new `Tracked` system from a closure that changes the grid and inserts every position it changed into the given set.
+    #[inline]
+    pub fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<T, F> System<T> for Tracked<F>
+where
+    F: FnMut(&mut Grid<T>, &mut PositionSet),
+{
+    #[inline]
+    fn step(&mut self, grid: &mut Grid<T>) {
+        (self.f)(grid, &mut PositionSet::new())
+    }
+
+    #[inline]
+    fn step_changes(&mut self, grid: &mut Grid<T>, changes: &mut PositionSet) {
+        (self.f)(grid, changes)
+    }
+}
+
+/// A callback run after every step of a [`Runner`].
+type Hook<'a, T> = Box<dyn FnMut(&Grid<T>, usize) + 'a>;
+
+/// Runs a sequence of [`System`]s over a grid, one step at a time.
+///
+/// Each step of the runner steps every system in the order they were added, then calls every hook.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, system::Runner};
+///
+/// let mut grid: Grid<u32> = Grid::new(4, 3, 0);
+/// let mut totals = Vec::new();
+///
+/// let mut runner = Runner::new()
+///     .with_system(|grid: &mut Grid<u32>| grid[v(0, 0)] += 1)
+///     .with_system(|grid: &mut Grid<u32>| grid[v(3, 2)] = grid[v(0, 0)] * 10)
+///     .on_step(|grid: &Grid<u32>, _| totals.push(grid.iter().sum::<u32>()));
+///
+/// runner.run(&mut grid, 3);
+///
+/// assert_eq!(runner.steps(), 3);
+/// drop(runner);
+/// assert_eq!(totals, vec![11, 22, 33]);
+/// ```
+pub struct Runner<'a, T> {
+    systems: Vec<Box<dyn System<T> + 'a>>,
+    hooks: Vec<Hook<'a, T>>,
+    steps: usize,
+}
+
+impl<'a, T> Runner<'a, T> {
+    /// Constructs a new `Runner` with no systems or hooks.
+    pub fn new() -> Self {
+        Self {
+            systems: Vec::new(),
+            hooks: Vec::new(),
+            steps: 0,
+        }
+    }
+
+    /// Returns `self` with `system` added after the existing systems.
+    pub fn with_system<S>(mut self, system: S) -> Self
+    where
+        S: System<T> + 'a,
+    {
+        self.systems.push(Box::new(system));
+        self
+    }
+
+    /// Returns `self` with `hook` added after the existing hooks.
+    ///
+    /// Hooks are called after every step with the grid and the number of steps run so far, including that step.
+    pub fn on_step<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(&Grid<T>, usize) + 'a,
+    {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    /// Returns the number of steps run so far.
+    #[inline]
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Runs a single step of every system, then calls every hook.
+    pub fn step(&mut self, grid: &mut Grid<T>) {
+        span!(
+            "runner_step",
+            step = self.steps + 1,
+            systems = self.systems.len()
+        );
+        for system in &mut self.systems {
+            system.step(grid);
+        }
+        self.steps += 1;
+        for hook in &mut self.hooks {
+            hook(grid, self.steps);
+        }
+    }
+
+    /// Runs a single step of every system, then calls every hook, returning the positions reported as changed by any system,
+    /// as in [`System::step_changes`].
+    ///
+    /// A position changed by one system and changed back by a later one in the same step is still included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet, system::{Runner, Tracked}};
+    ///
+    /// let mut grid: Grid<u32> = Grid::new(4, 3, 0);
+    ///
+    /// let mut runner = Runner::new()
+    ///     .with_system(Tracked::new(|grid: &mut Grid<u32>, changes: &mut PositionSet| {
+    ///         grid[v(0, 0)] += 1;
+    ///         changes.insert(v(0, 0));
+    ///     }))
+    ///     .with_system(Tracked::new(|grid: &mut Grid<u32>, changes: &mut PositionSet| {
+    ///         if grid[v(3, 2)] != 7 {
+    ///             grid[v(3, 2)] = 7;
+    ///             changes.insert(v(3, 2));
+    ///         }
+    ///     }));
+    ///
+    /// assert_eq!(runner.step_changes(&mut grid).len(), 2);
+    /// assert_eq!(runner.step_changes(&mut grid).iter().collect::<Vec<_>>(), vec![v(0, 0)]);
+    /// ```
+    pub fn step_changes(&mut self, grid: &mut Grid<T>) -> PositionSet {
+        span!(
+            "runner_step",
+            step = self.steps + 1,
+            systems = self.systems.len()
+        );
+        let mut changes = PositionSet::new();
+        for system in &mut self.systems {
+            system.step_changes(grid, &mut changes);
+        }
+        self.steps += 1;
+        for hook in &mut self.hooks {
+            hook(grid, self.steps);
+        }
+        changes
+    }
+
+    /// Runs `n` steps.
+    pub fn run(&mut self, grid: &mut Grid<T>, n: usize) {
+        for _ in 0..n {
+            self.step(grid);
+        }
+    }
+
+    /// Runs steps until a step leaves the grid unchanged, or until `max_steps` steps have been run.
+    ///
+    /// Returns the number of steps run, including the final step that left the grid unchanged,
+    /// or `None` if the grid was still changing after `max_steps` steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, system::Runner};
+    ///
+    /// // sand falls one cell per step until it comes to rest
+    /// let mut grid: Grid<bool> = Grid::from_nested_iter([
+    ///     [true, false, true],
+    ///     [false, false, false],
+    ///     [false, false, true],
+    ///     [false, false, false],
+    /// ]);
+    ///
+    /// let mut runner = Runner::new().with_system(|grid: &mut Grid<bool>| {
+    ///     for y in (0..grid.height() - 1).rev() {
+    ///         for x in 0..grid.width() {
+    ///             if grid[v(x, y)] && !grid[v(x, y + 1)] {
+    ///                 grid[v(x, y)] = false;
+    ///                 grid[v(x, y + 1)] = true;
+    ///             }
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(runner.run_until_stable(&mut grid, 100), Some(4));
+    /// assert_eq!(runner.run_until_stable(&mut grid, 100), Some(1));
+    /// assert_eq!(runner.steps(), 5);
+    /// assert!(grid[v(0, 3)] && grid[v(2, 3)] && grid[v(2, 2)]);
+    /// ```
+    pub fn run_until_stable(&mut self, grid: &mut Grid<T>, max_steps: usize) -> Option<usize>
+    where
+        T: Clone + PartialEq,
+    {
+        span!("runner_run_until_stable", max_steps);
+        for steps in 1..=max_steps {
+            let previous = grid.clone();
+            self.step(grid);
+            if *grid == previous {
+                event!(steps, "grid stable");
+                return Some(steps);
+            }
+        }
+        event!(steps = max_steps, "grid still changing");
+        None
+    }
+}
+
+impl<'a, T> Default for Runner<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Repeatedly replaces `grid` with `step(&grid)` until a step returns a grid equal to its input, or until `max_steps` steps have been run.
+///
+/// Returns the final grid, and the number of steps run, including the final step that left the grid unchanged,
+/// as in [`Runner::run_until_stable`]. If the grid was still changing after `max_steps` steps,
+/// the latest grid is returned along with `None`.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, system::iterate_until_stable};
+///
+/// // each cell takes the maximum of itself and its orthogonal neighbours
+/// let grid: Grid<u8> = Grid::from_nested_iter([
+///     [0, 0, 0, 0],
+///     [0, 0, 0, 0],
+///     [0, 0, 0, 9],
+/// ]);
+///
+/// let (grid, steps) = iterate_until_stable(
+///     grid,
+///     |grid| {
+///         grid.pos_map(|pos, &value| {
+///             ORTHOGONAL
+///                 .iter()
+///                 .filter_map(|&offset| grid.get(pos + offset))
+///                 .fold(value, |max, &neighbor| max.max(neighbor))
+///         })
+///     },
+///     100,
+/// );
+///
+/// assert_eq!(steps, Some(6));
+/// assert!(grid.iter().all(|&value| value == 9));
+///
+/// let (grid, steps) = iterate_until_stable(grid, |grid| grid.map(|&value| value.wrapping_add(1)), 3);
+///
+/// assert_eq!(steps, None);
+/// assert!(grid.iter().all(|&value| value == 12));
+/// ```
+pub fn iterate_until_stable<T, F>(
+    mut grid: Grid<T>,
+    mut step: F,
+    max_steps: usize,
+) -> (Grid<T>, Option<usize>)
+where
+    T: PartialEq,
+    F: FnMut(&Grid<T>) -> Grid<T>,
+{
+    for steps in 1..=max_steps {
+        let next = step(&grid);
+        if next == grid {
+            return (next, Some(steps));
+        }
+        grid = next;
+    }
+    (grid, None)
+}
@@ -0,0 +1,324 @@
+//! Heightmap and terrain processing: classification of values into labels, descent tracing and flow accumulation.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::{constants::ADJACENT, Vector},
+};
+
+impl<T: PartialOrd> Grid<T> {
+    /// Returns a grid of labels, in which each value is given the label paired with the first threshold it is less than.
+    ///
+    /// Values that are not less than any threshold are given the label of the last pair,
+    /// so with thresholds in increasing order the last pair acts as a catch-all for the highest values.
+    ///
+    /// Panics if `thresholds` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<f32> = Grid::from_nested_iter([[0.1, 0.35, 0.6], [0.9, 0.5, 0.2]]);
+    ///
+    /// let biomes = height.classify(&[(0.3, "water"), (0.4, "sand"), (0.8, "grass"), (f32::INFINITY, "snow")]);
+    ///
+    /// assert_eq!(biomes[v(0, 0)], "water");
+    /// assert_eq!(biomes[v(1, 0)], "sand");
+    /// assert_eq!(biomes[v(2, 0)], "grass");
+    /// assert_eq!(biomes[v(0, 1)], "snow");
+    /// assert_eq!(biomes[v(1, 1)], "grass");
+    /// ```
+    #[track_caller]
+    pub fn classify<L: Clone>(&self, thresholds: &[(T, L)]) -> Grid<L> {
+        self.try_classify(thresholds).or_panic()
+    }
+
+    /// Returns a grid of labels, as in [`classify`](Self::classify), or an error if `thresholds` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<u8> = Grid::from_nested_iter([[1, 5, 9]]);
+    ///
+    /// assert_eq!(height.try_classify(&[(5, 'L'), (10, 'H')]), Ok(Grid::from_nested_iter([['L', 'H', 'H']])));
+    /// assert!(height.try_classify::<char>(&[]).is_err());
+    /// ```
+    pub fn try_classify<L: Clone>(&self, thresholds: &[(T, L)]) -> Result<Grid<L>, GridError> {
+        if thresholds.is_empty() {
+            return Err(GridError::InvalidArgument(
+                "cannot classify with no thresholds",
+            ));
+        }
+        Ok(self.map(|value| {
+            thresholds[band(value, thresholds.iter().map(|(t, _)| t))]
+                .1
+                .clone()
+        }))
+    }
+
+    /// Returns a grid of labels looked up from a table by two grids of values, such as height and moisture.
+    ///
+    /// Each value of `self` is placed in the band of the first threshold in `thresholds` it is less than,
+    /// or the last band if there is none, which selects a column of `table`.
+    /// Likewise each value of `other` selects a row of `table` by `other_thresholds`.
+    ///
+    /// Panics if the dimensions of `self` and `other` differ, if either set of thresholds is empty,
+    /// or if the width of `table` is not the length of `thresholds` or its height is not the length of `other_thresholds`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<f32> = Grid::from_nested_iter([[0.2, 0.7, 0.9]]);
+    /// let moisture: Grid<f32> = Grid::from_nested_iter([[0.8, 0.1, 0.6]]);
+    ///
+    /// let table: Grid<&str> = Grid::from_nested_iter([
+    ///     ["desert", "rock"],    // dry
+    ///     ["forest", "tundra"],  // wet
+    /// ]);
+    ///
+    /// let biomes = height.classify_2d(&moisture, &[0.5, 1.0], &[0.5, 1.0], &table);
+    ///
+    /// assert_eq!(biomes[v(0, 0)], "forest");
+    /// assert_eq!(biomes[v(1, 0)], "rock");
+    /// assert_eq!(biomes[v(2, 0)], "tundra");
+    /// ```
+    #[track_caller]
+    pub fn classify_2d<U, L>(
+        &self,
+        other: &Grid<U>,
+        thresholds: &[T],
+        other_thresholds: &[U],
+        table: &Grid<L>,
+    ) -> Grid<L>
+    where
+        U: PartialOrd,
+        L: Clone,
+    {
+        self.try_classify_2d(other, thresholds, other_thresholds, table)
+            .or_panic()
+    }
+
+    /// Returns a grid of labels looked up from a table by two grids of values, as in [`classify_2d`](Self::classify_2d),
+    /// or an error if the dimensions of `self` and `other` differ, if either set of thresholds is empty,
+    /// or if the dimensions of `table` do not match the numbers of thresholds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<u8> = Grid::from_nested_iter([[1, 8]]);
+    /// let moisture: Grid<u8> = Grid::from_nested_iter([[9, 2]]);
+    /// let table: Grid<char> = Grid::from_nested_iter([['a', 'b'], ['c', 'd']]);
+    ///
+    /// assert_eq!(height.try_classify_2d(&moisture, &[5, 10], &[5, 10], &table), Ok(Grid::from_nested_iter([['c', 'b']])));
+    /// assert_eq!(
+    ///     height.try_classify_2d(&moisture, &[10], &[5, 10], &table),
+    ///     Err(GridError::DimensionMismatch { expected: v(1, 2), found: v(2, 2) }),
+    /// );
+    /// ```
+    pub fn try_classify_2d<U, L>(
+        &self,
+        other: &Grid<U>,
+        thresholds: &[T],
+        other_thresholds: &[U],
+        table: &Grid<L>,
+    ) -> Result<Grid<L>, GridError>
+    where
+        U: PartialOrd,
+        L: Clone,
+    {
+        if other.dim != self.dim {
+            return Err(GridError::DimensionMismatch {
+                expected: self.dim,
+                found: other.dim,
+            });
+        }
+        if thresholds.is_empty() || other_thresholds.is_empty() {
+            return Err(GridError::InvalidArgument(
+                "cannot classify with no thresholds",
+            ));
+        }
+        let expected = Vector::new(thresholds.len() as i64, other_thresholds.len() as i64);
+        if table.dim != expected {
+            return Err(GridError::DimensionMismatch {
+                expected,
+                found: table.dim,
+            });
+        }
+        Ok(self.pos_map(|pos, value| {
+            let x = band(value, thresholds.iter());
+            let y = band(&other[pos], other_thresholds.iter());
+            table[Vector::new(x as i64, y as i64)].clone()
+        }))
+    }
+}
+
+impl<T: PartialOrd> Grid<T> {
+    /// Returns the position that water at `pos` would flow to: its lowest adjacent neighbour, if lower than `pos` itself.
+    ///
+    /// Of several equally low neighbours, the first in the order of [`ADJACENT`] is chosen.
+    fn downhill(&self, pos: Vector) -> Option<Vector> {
+        let mut lowest = pos;
+        for offset in ADJACENT {
+            let neighbor = pos + offset;
+            if self
+                .get(neighbor)
+                .is_some_and(|value| value < &self[lowest])
+            {
+                lowest = neighbor;
+            }
+        }
+        (lowest != pos).then_some(lowest)
+    }
+
+    /// Returns the path of steepest descent from `start`, treating `self` as a heightmap.
+    ///
+    /// From each position the path moves to the lowest of its 8 adjacent neighbours, as long as that is strictly lower,
+    /// and stops at a local minimum or on reaching the edge of the grid. The path includes `start`.
+    ///
+    /// Panics if `start` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<u8> = Grid::from_nested_iter([
+    ///     [9, 9, 9, 9, 9],
+    ///     [9, 8, 7, 6, 9],
+    ///     [9, 9, 9, 5, 9],
+    ///     [9, 9, 9, 4, 9],
+    ///     [9, 9, 9, 3, 9],
+    /// ]);
+    ///
+    /// assert_eq!(height.trace_descent(v(1, 1)), vec![v(1, 1), v(2, 1), v(3, 2), v(3, 3), v(3, 4)]);
+    /// assert_eq!(height.trace_descent(v(3, 3)), vec![v(3, 3), v(3, 4)]);
+    /// ```
+    #[track_caller]
+    pub fn trace_descent(&self, start: Vector) -> Vec<Vector> {
+        self.try_trace_descent(start).or_panic()
+    }
+
+    /// Returns the path of steepest descent from `start`, as in [`trace_descent`](Self::trace_descent),
+    /// or an error if `start` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<u8> = Grid::from_nested_iter([[3, 2, 1]]);
+    ///
+    /// assert_eq!(height.try_trace_descent(v(0, 0)), Ok(vec![v(0, 0)]));
+    /// assert!(height.try_trace_descent(v(3, 0)).is_err());
+    /// ```
+    pub fn try_trace_descent(&self, start: Vector) -> Result<Vec<Vector>, GridError> {
+        if !self.in_bounds(start) {
+            return Err(GridError::out_of_bounds(self.dim, start));
+        }
+        let mut path = vec![start];
+        let mut pos = start;
+        while !self.is_border(pos) {
+            match self.downhill(pos) {
+                Some(next) => {
+                    pos = next;
+                    path.push(pos);
+                }
+                None => break,
+            }
+        }
+        Ok(path)
+    }
+
+    /// Returns the flow accumulation of each position, treating `self` as a heightmap.
+    ///
+    /// Every position receives one unit of rain, and passes everything it holds on to its lowest adjacent neighbour,
+    /// if that is strictly lower. The flow accumulation of a position is the total that passes through it,
+    /// including its own unit, so it counts the positions that drain through it. High values trace out rivers.
+    ///
+    /// The heights must be totally ordered, apart from values such as NaN that cannot even be compared with themselves.
+    ///
+    /// Panics if any value cannot be compared with itself, such as a NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<u8> = Grid::from_nested_iter([
+    ///     [5, 4, 5],
+    ///     [4, 3, 4],
+    ///     [3, 2, 3],
+    ///     [5, 1, 5],
+    /// ]);
+    ///
+    /// let flow = height.accumulate_flow();
+    ///
+    /// assert_eq!(flow[v(0, 0)], 1);
+    /// assert_eq!(flow[v(1, 1)], 4);
+    /// assert_eq!(flow[v(1, 3)], 12);
+    /// ```
+    #[track_caller]
+    pub fn accumulate_flow(&self) -> Grid<u64> {
+        self.try_accumulate_flow().or_panic()
+    }
+
+    /// Returns the flow accumulation of each position, as in [`accumulate_flow`](Self::accumulate_flow),
+    /// or an error if any value cannot be compared with itself, such as a NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let height: Grid<f32> = Grid::from_nested_iter([[2.0, 1.0, f32::NAN]]);
+    ///
+    /// assert!(height.try_accumulate_flow().is_err());
+    /// assert_eq!(height.subgrid(v(0, 0), v(2, 1)).try_accumulate_flow(), Ok(Grid::from_nested_iter([[1, 2]])));
+    ///
+    /// let empty: Grid<f32> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.try_accumulate_flow().unwrap().dim(), v(0, 2));
+    /// ```
+    pub fn try_accumulate_flow(&self) -> Result<Grid<u64>, GridError> {
+        if self
+            .iter()
+            .any(|height| height.partial_cmp(height).is_none())
+        {
+            return Err(GridError::InvalidArgument(
+                "every height must be comparable with itself",
+            ));
+        }
+        let mut order: Vec<Vector> = self.positions().collect();
+        // with no NaN-like values left, the heights are totally ordered, so the comparison is consistent
+        order.sort_by(|&a, &b| {
+            self[b]
+                .partial_cmp(&self[a])
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        let mut flow = self.map(|_| 1);
+        for pos in order {
+            if let Some(next) = self.downhill(pos) {
+                flow[next] += flow[pos];
+            }
+        }
+        Ok(flow)
+    }
+}
+
+/// Returns the index of the first threshold that `value` is less than, or of the last threshold if there is none.
+fn band<'a, T: PartialOrd + 'a>(
+    value: &T,
+    thresholds: impl ExactSizeIterator<Item = &'a T>,
+) -> usize {
+    let last = thresholds.len() - 1;
+    let mut thresholds = thresholds;
+    thresholds
+        .position(|threshold| value < threshold)
+        .unwrap_or(last)
+}
@@ -0,0 +1,414 @@
+//! A `MapTile` trait for the cells of a game map, and a [`TileMap`] that uses it for pathfinding, sight, light and rendering.
+//!
+//! The free functions and `Grid` methods elsewhere in this crate take closures saying which positions can be entered
+//! or seen through. Implementing [`MapTile`] says this once on the cell type, so long-lived game code does not have to
+//! pass the same closures to every call.
+
+use crate::{
+    error::GridError,
+    grid::{
+        aoe::AreaOfEffect,
+        light::LightMap,
+        pathfinding::{Path, Searcher},
+        Grid,
+    },
+    vector::{Metric, Vector},
+};
+
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// The behaviour of a cell of a [`TileMap`].
+///
+/// Only [`glyph`](Self::glyph) must be implemented. By default a tile can be walked on at a cost of `1`,
+/// and does not block sight or light.
+///
+/// # Examples
+///
+/// ```
+/// use grid::tile::MapTile;
+///
+/// #[derive(Clone, Copy, PartialEq, Eq)]
+/// enum Cell {
+///     Floor,
+///     Mud,
+///     Window,
+///     Wall,
+/// }
+///
+/// impl MapTile for Cell {
+///     fn walkable(&self) -> bool {
+///         matches!(self, Cell::Floor | Cell::Mud)
+///     }
+///
+///     fn cost(&self) -> Option<u64> {
+///         match self {
+///             Cell::Floor => Some(1),
+///             Cell::Mud => Some(3),
+///             _ => None,
+///         }
+///     }
+///
+///     fn opacity(&self) -> u8 {
+///         if *self == Cell::Wall { 15 } else { 0 }
+///     }
+///
+///     fn glyph(&self) -> char {
+///         match self {
+///             Cell::Floor => '.',
+///             Cell::Mud => '~',
+///             Cell::Window => '=',
+///             Cell::Wall => '#',
+///         }
+///     }
+/// }
+///
+/// assert!(Cell::Window.is_transparent() && !Cell::Window.walkable());
+/// assert_eq!(Cell::Mud.cost(), Some(3));
+/// ```
+pub trait MapTile {
+    /// Returns `true` if the tile can be walked on.
+    ///
+    /// The default implementation returns `true`.
+    #[inline]
+    fn walkable(&self) -> bool {
+        true
+    }
+
+    /// Returns the cost of walking onto the tile, or `None` if it cannot be walked on.
+    ///
+    /// The default implementation returns `Some(1)` if the tile is [`walkable`](Self::walkable), or `None` otherwise.
+    #[inline]
+    fn cost(&self) -> Option<u64> {
+        self.walkable().then_some(1)
+    }
+
+    /// Returns how strongly the tile blocks light, as in [`Grid::propagate_light`].
+    /// Any opacity above `0` also blocks sight.
+    ///
+    /// The default implementation returns `0`.
+    #[inline]
+    fn opacity(&self) -> u8 {
+        0
+    }
+
+    /// Returns `true` if the tile can be seen through, which is when its [`opacity`](Self::opacity) is `0`.
+    #[inline]
+    fn is_transparent(&self) -> bool {
+        self.opacity() == 0
+    }
+
+    /// Returns the character the tile is drawn as when the map is rendered as text.
+    fn glyph(&self) -> char;
+}
+
+/// A map of [`MapTile`]s, with pathfinding, field of view, light and rendering that take their rules from the tiles.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{tile::{MapTile, TileMap}, prelude::*};
+///
+/// #[derive(Clone, Copy, PartialEq, Eq)]
+/// struct Cell(char);
+///
+/// impl MapTile for Cell {
+///     fn walkable(&self) -> bool {
+///         self.0 != '#'
+///     }
+///
+///     fn opacity(&self) -> u8 {
+///         if self.0 == '#' { 15 } else { 0 }
+///     }
+///
+///     fn glyph(&self) -> char {
+///         self.0
+///     }
+/// }
+///
+/// let grid = Grid::parse_with("\
+/// ..#..
+/// ..#..
+/// .....", |c| Some(Cell(c))).unwrap();
+///
+/// let mut map = TileMap::new(grid);
+///
+/// let path = map.path(v(0, 0), v(4, 0)).unwrap();
+/// assert_eq!(path.cost(), 8);
+///
+/// assert!(!map.line_of_sight(v(0, 0), v(4, 0)));
+/// assert!(map.line_of_sight(v(0, 2), v(4, 2)));
+///
+/// map[v(2, 2)] = Cell('#');
+///
+/// assert_eq!(map.path(v(0, 0), v(4, 0)), None);
+/// assert_eq!(map.to_string(), "..#..\n..#..\n..#..");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct TileMap<T> {
+    grid: Grid<T>,
+}
+
+impl<T: MapTile> TileMap<T> {
+    /// Constructs a new `TileMap` from a grid of tiles.
+    #[inline]
+    pub fn new(grid: Grid<T>) -> Self {
+        Self { grid }
+    }
+
+    /// Returns a reference to the grid of tiles.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Returns a mutable reference to the grid of tiles.
+    #[inline]
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+
+    /// Consumes the map, returning the grid of tiles.
+    #[inline]
+    pub fn into_grid(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Returns the dimensions of the map.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.grid.dim
+    }
+
+    /// Returns a reference to the tile at `pos`, or `None` if it is out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.grid.get(pos)
+    }
+
+    /// Returns a shortest path from `start` to `goal` moving in the 4 orthogonal directions,
+    /// where entering a tile costs its [`MapTile::cost`], or `None` if there is no path.
+    ///
+    /// This is [`astar`](crate::pathfinding::astar) with the costs of the tiles.
+    ///
+    /// Panics if `start` or `goal` are out of bounds.
+    #[track_caller]
+    pub fn path(&self, start: Vector, goal: Vector) -> Option<Path> {
+        self.searcher(start, goal).finish()
+    }
+
+    /// Returns a shortest path from `start` to `goal`, as in [`path`](Self::path),
+    /// or an error if `start` or `goal` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{tile::{MapTile, TileMap}, prelude::*};
+    ///
+    /// struct Open;
+    ///
+    /// impl MapTile for Open {
+    ///     fn glyph(&self) -> char {
+    ///         '.'
+    ///     }
+    /// }
+    ///
+    /// let map = TileMap::new(Grid::from_fn(3, 3, |_| Open));
+    ///
+    /// assert_eq!(map.try_path(v(0, 0), v(2, 2)).unwrap().unwrap().cost(), 4);
+    /// assert!(map.try_path(v(0, 0), v(3, 3)).is_err());
+    /// ```
+    pub fn try_path(&self, start: Vector, goal: Vector) -> Result<Option<Path>, GridError> {
+        Ok(self.try_searcher(start, goal)?.finish())
+    }
+
+    /// Returns a [`Searcher`] for a path from `start` to `goal`, where entering a tile costs its [`MapTile::cost`],
+    /// for searches that need other movement rules or are spread over several frames.
+    ///
+    /// Panics if `start` or `goal` are out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{pathfinding::MovementRules, tile::{MapTile, TileMap}, prelude::*};
+    ///
+    /// struct Open;
+    ///
+    /// impl MapTile for Open {
+    ///     fn glyph(&self) -> char {
+    ///         '.'
+    ///     }
+    /// }
+    ///
+    /// let map = TileMap::new(Grid::from_fn(4, 4, |_| Open));
+    ///
+    /// let path = map
+    ///     .searcher(v(0, 0), v(3, 3))
+    ///     .with_rules(MovementRules::EIGHT_WAY)
+    ///     .finish()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(path.positions().len(), 4);
+    /// ```
+    #[track_caller]
+    pub fn searcher(
+        &self,
+        start: Vector,
+        goal: Vector,
+    ) -> Searcher<impl FnMut(Vector) -> Option<u64> + '_> {
+        Searcher::new(self.grid.dim, start, goal, |pos| self.grid[pos].cost())
+    }
+
+    /// Returns a [`Searcher`] for a path from `start` to `goal`, as in [`searcher`](Self::searcher),
+    /// or an error if `start` or `goal` are out of bounds.
+    pub fn try_searcher(
+        &self,
+        start: Vector,
+        goal: Vector,
+    ) -> Result<Searcher<impl FnMut(Vector) -> Option<u64> + '_>, GridError> {
+        Searcher::try_new(self.grid.dim, start, goal, |pos| self.grid[pos].cost())
+    }
+
+    /// Returns `true` if `to` can be seen from `from`, because every tile strictly between them is [transparent](MapTile::is_transparent),
+    /// as in [`Grid::line_of_sight`].
+    #[inline]
+    pub fn line_of_sight(&self, from: Vector, to: Vector) -> bool {
+        self.grid.line_of_sight(from, to, T::is_transparent)
+    }
+
+    /// Returns the in-bounds positions within `radius` of `origin` in straight line distance
+    /// that can be seen from it, in row-major order.
+    ///
+    /// A position can be seen if there is a [`line_of_sight`](Self::line_of_sight) to it,
+    /// so walls around the viewer are seen, but nothing behind them.
+    /// The result can be passed straight to [`FogOfWar::reveal`](crate::fog::FogOfWar::reveal).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{tile::{MapTile, TileMap}, prelude::*};
+    ///
+    /// struct Cell(bool);
+    ///
+    /// impl MapTile for Cell {
+    ///     fn opacity(&self) -> u8 {
+    ///         if self.0 { 15 } else { 0 }
+    ///     }
+    ///
+    ///     fn glyph(&self) -> char {
+    ///         if self.0 { '#' } else { '.' }
+    ///     }
+    /// }
+    ///
+    /// let map = TileMap::new(Grid::from_fn(7, 1, |pos| Cell(pos.x == 3)));
+    ///
+    /// assert_eq!(map.field_of_view(v(1, 0), 4), vec![v(0, 0), v(1, 0), v(2, 0), v(3, 0)]);
+    /// ```
+    pub fn field_of_view(&self, origin: Vector, radius: i64) -> Vec<Vector> {
+        let area = AreaOfEffect::Burst {
+            radius,
+            metric: Metric::Euclidean,
+        };
+        let mut positions = self.grid.area_of_effect(origin, &area);
+        positions.retain(|&pos| self.line_of_sight(origin, pos));
+        positions
+    }
+
+    /// Returns the light levels over the map, spread from the given sources and dimmed by the [`MapTile::opacity`] of each tile,
+    /// as in [`Grid::propagate_light`].
+    ///
+    /// Panics if any source is out of bounds.
+    #[track_caller]
+    pub fn light<I>(&self, sources: I) -> LightMap
+    where
+        I: IntoIterator<Item = (Vector, u8)>,
+    {
+        self.grid.propagate_light(sources, T::opacity)
+    }
+
+    /// Returns the light levels over the map, as in [`light`](Self::light), or an error if any source is out of bounds.
+    pub fn try_light<I>(&self, sources: I) -> Result<LightMap, GridError>
+    where
+        I: IntoIterator<Item = (Vector, u8)>,
+    {
+        self.grid.try_propagate_light(sources, T::opacity)
+    }
+
+    /// Renders the map as text, with one line per row and each tile drawn as its [`MapTile::glyph`],
+    /// or as `hidden` where `shown` is `false` or out of bounds.
+    ///
+    /// This is the same as the `Display` implementation when every position is shown.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{tile::{MapTile, TileMap}, prelude::*};
+    ///
+    /// struct Cell(char);
+    ///
+    /// impl MapTile for Cell {
+    ///     fn glyph(&self) -> char {
+    ///         self.0
+    ///     }
+    /// }
+    ///
+    /// let map = TileMap::new(Grid::parse_with("#..\n.@.", |c| Some(Cell(c))).unwrap());
+    /// let seen: Grid<bool> = Grid::from_fn(3, 2, |pos| pos.x > 0);
+    ///
+    /// assert_eq!(map.render_masked(&seen, ' '), " ..\n @.");
+    /// ```
+    pub fn render_masked(&self, shown: &Grid<bool>, hidden: char) -> String {
+        let mut s = String::with_capacity(self.grid.raw.len() + self.grid.dim.y as usize);
+        for (pos, tile) in self.grid.iter_positions() {
+            if pos.x == 0 && pos.y > 0 {
+                s.push('\n');
+            }
+            if shown.get(pos) == Some(&true) {
+                s.push(tile.glyph());
+            } else {
+                s.push(hidden);
+            }
+        }
+        s
+    }
+}
+
+impl<T: MapTile> From<Grid<T>> for TileMap<T> {
+    #[inline]
+    fn from(grid: Grid<T>) -> Self {
+        Self::new(grid)
+    }
+}
+
+impl<T> Index<Vector> for TileMap<T> {
+    type Output = T;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, pos: Vector) -> &Self::Output {
+        &self.grid[pos]
+    }
+}
+
+impl<T> IndexMut<Vector> for TileMap<T> {
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, pos: Vector) -> &mut Self::Output {
+        &mut self.grid[pos]
+    }
+}
+
+impl<T: MapTile> fmt::Display for TileMap<T> {
+    /// Writes the map as text, with one line per row and each tile drawn as its [`MapTile::glyph`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (pos, tile) in self.grid.iter_positions() {
+            if pos.x == 0 && pos.y > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", tile.glyph())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! Staging several edits to a `Grid` and applying them all at once, or not at all.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    grid::{set::PositionSet, Grid},
+    vector::Vector,
+};
+
+impl<T> Grid<T> {
+    /// Stages the edits made by `edit` in a [`Transaction`], then applies them all to the grid,
+    /// and rolls them all back if `validate` returns `false` for the edited grid.
+    ///
+    /// `validate` is also given the set of positions that were edited, so rules can be checked only around them.
+    ///
+    /// Returns `true` if the edits were kept, or `false` if they were rolled back.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, set::PositionSet};
+    ///
+    /// let mut map: Grid<char> = Grid::new(4, 3, '.');
+    ///
+    /// // no two towers may be adjacent
+    /// let valid = |map: &Grid<char>, edited: &PositionSet| {
+    ///     edited.iter().all(|pos| {
+    ///         map[pos] != 'T'
+    ///             || ADJACENT.iter().all(|&offset| map.get(pos + offset) != Some(&'T'))
+    ///     })
+    /// };
+    ///
+    /// assert!(map.transaction(|txn| {
+    ///     txn.set(v(0, 0), 'T');
+    ///     txn.set(v(2, 0), 'T');
+    /// }, valid));
+    ///
+    /// assert!(!map.transaction(|txn| {
+    ///     txn.set(v(0, 2), 'T');
+    ///     txn.set(v(3, 1), 'T');
+    /// }, valid));
+    ///
+    /// assert_eq!(map, Grid::from_nested_iter([
+    ///     "T.T.".chars(),
+    ///     "....".chars(),
+    ///     "....".chars(),
+    /// ]));
+    /// ```
+    pub fn transaction<E, V>(&mut self, edit: E, validate: V) -> bool
+    where
+        E: FnOnce(&mut Transaction<'_, T>),
+        V: FnOnce(&Grid<T>, &PositionSet) -> bool,
+    {
+        let mut txn = Transaction {
+            grid: self,
+            staged: BTreeMap::new(),
+        };
+        edit(&mut txn);
+        let staged = txn.staged;
+        let width = self.dim.x as usize;
+        let edited: PositionSet = staged
+            .keys()
+            .map(|&index| Vector::new((index % width) as i64, (index / width) as i64))
+            .collect();
+        let old: Vec<(usize, T)> = staged
+            .into_iter()
+            .map(|(index, value)| (index, std::mem::replace(&mut self.raw[index], value)))
+            .collect();
+        if validate(self, &edited) {
+            return true;
+        }
+        for (index, value) in old {
+            self.raw[index] = value;
+        }
+        false
+    }
+}
+
+/// Edits to a grid that have been staged but not yet applied.
+///
+/// Reads through a transaction see the staged edits, while the grid itself is unchanged until the transaction ends.
+///
+/// Constructed by [`Grid::transaction`].
+pub struct Transaction<'a, T> {
+    grid: &'a Grid<T>,
+    // keyed by index so that edits are applied in row-major order
+    staged: BTreeMap<usize, T>,
+}
+
+impl<'a, T> Transaction<'a, T> {
+    /// Returns a reference to the grid as it was before the transaction.
+    #[inline]
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    /// Returns a reference to the value at the given position, including any staged edit, or `None` if out of bounds.
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        let index = self.grid.get_index(pos)?;
+        self.staged.get(&index).or(Some(&self.grid.raw[index]))
+    }
+
+    /// Stages setting the value at the given position, replacing any edit already staged there.
+    ///
+    /// Returns `true` if the edit was staged, or `false` if `pos` is out of bounds.
+    pub fn set(&mut self, pos: Vector, value: T) -> bool {
+        let Some(index) = self.grid.get_index(pos) else {
+            return false;
+        };
+        self.staged.insert(index, value);
+        true
+    }
+
+    /// Stages setting the value at the given position to the result of `f`,
+    /// which is given the current value, including any staged edit.
+    ///
+    /// Returns `true` if the edit was staged, or `false` if `pos` is out of bounds.
+    pub fn update<F>(&mut self, pos: Vector, f: F) -> bool
+    where
+        F: FnOnce(&T) -> T,
+    {
+        let Some(value) = self.get(pos).map(f) else {
+            return false;
+        };
+        self.set(pos, value)
+    }
+
+    /// Discards the edit staged at the given position, if any.
+    pub fn revert(&mut self, pos: Vector) {
+        if let Some(index) = self.grid.get_index(pos) {
+            self.staged.remove(&index);
+        }
+    }
+
+    /// Returns the number of positions with staged edits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.staged.len()
+    }
+
+    /// Returns `true` if no edits are staged, or `false` otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.staged.is_empty()
+    }
+}
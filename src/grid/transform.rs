@@ -0,0 +1,454 @@
+//! Transposing and rotating a `Grid`.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+/// The side length of the square tiles in which the grid is traversed when rearranging its values.
+///
+/// Traversing tile by tile keeps both the reads and the scattered writes within a small working set,
+/// which is much faster than a plain row-major traversal for large grids.
+const TILE: usize = 32;
+
+impl<T: Clone> Grid<T> {
+    /// Returns a new grid with the rows and columns of `self` swapped, so that the value at `(x, y)` moves to `(y, x)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// let transposed = grid.transpose();
+    ///
+    /// assert_eq!(transposed, Grid::from_nested_iter([
+    ///     [1, 4],
+    ///     [2, 5],
+    ///     [3, 6],
+    /// ]));
+    /// ```
+    pub fn transpose(&self) -> Self {
+        let height = self.height() as usize;
+        self.remap(Vector::new(self.height(), self.width()), |x, y| {
+            x * height + y
+        })
+    }
+
+    /// Returns a new grid with the values of `self` rotated 90 degrees clockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// let rotated = grid.rotate_cw();
+    ///
+    /// assert_eq!(rotated, Grid::from_nested_iter([
+    ///     [4, 1],
+    ///     [5, 2],
+    ///     [6, 3],
+    /// ]));
+    /// ```
+    pub fn rotate_cw(&self) -> Self {
+        let height = self.height() as usize;
+        self.remap(Vector::new(self.height(), self.width()), |x, y| {
+            x * height + (height - 1 - y)
+        })
+    }
+
+    /// Returns a new grid with the values of `self` rotated 90 degrees counterclockwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// let rotated = grid.rotate_ccw();
+    ///
+    /// assert_eq!(rotated, Grid::from_nested_iter([
+    ///     [3, 6],
+    ///     [2, 5],
+    ///     [1, 4],
+    /// ]));
+    /// assert_eq!(rotated.rotate_cw(), grid);
+    /// ```
+    pub fn rotate_ccw(&self) -> Self {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        self.remap(Vector::new(self.height(), self.width()), |x, y| {
+            (width - 1 - x) * height + y
+        })
+    }
+
+    /// Returns a new grid with the values of `self` rotated 180 degrees.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// let rotated = grid.rotate_180();
+    ///
+    /// assert_eq!(rotated, Grid::from_nested_iter([
+    ///     [6, 5, 4],
+    ///     [3, 2, 1],
+    /// ]));
+    /// assert_eq!(rotated, grid.rotate_cw().rotate_cw());
+    /// ```
+    pub fn rotate_180(&self) -> Self {
+        Grid {
+            raw: self.raw.iter().rev().cloned().collect(),
+            dim: self.dim,
+        }
+    }
+
+    /// Returns a new grid with the values of `self` mirrored across its vertical axis, reversing the order of each row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// assert_eq!(grid.flip_horizontal(), Grid::from_nested_iter([
+    ///     [3, 2, 1],
+    ///     [6, 5, 4],
+    /// ]));
+    ///
+    /// let empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.flip_horizontal().dim(), v(0, 2));
+    /// ```
+    pub fn flip_horizontal(&self) -> Self {
+        Grid {
+            raw: self
+                .rows()
+                .flat_map(|row| row.iter().rev().cloned())
+                .collect(),
+            dim: self.dim,
+        }
+    }
+
+    /// Returns a new grid with the values of `self` mirrored across its horizontal axis, reversing the order of the rows.
+    ///
+    /// Together with the rotations, this gives all eight orientations of a grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// assert_eq!(grid.flip_vertical(), Grid::from_nested_iter([
+    ///     [4, 5, 6],
+    ///     [1, 2, 3],
+    /// ]));
+    /// assert_eq!(grid.flip_vertical().flip_horizontal(), grid.rotate_180());
+    ///
+    /// let orientations: Vec<Grid<u8>> = [grid.clone(), grid.flip_vertical()]
+    ///     .into_iter()
+    ///     .flat_map(|grid| [grid.rotate_cw(), grid.rotate_180(), grid.rotate_ccw(), grid])
+    ///     .collect();
+    /// assert_eq!(orientations.len(), 8);
+    /// assert!(orientations.contains(&grid.transpose()));
+    /// ```
+    pub fn flip_vertical(&self) -> Self {
+        Grid {
+            raw: self.rows().rev().flatten().cloned().collect(),
+            dim: self.dim,
+        }
+    }
+
+    /// Builds a grid with the given dimensions, where the value at `(x, y)` in `self` is moved to the raw index `index(x, y)`.
+    ///
+    /// `index` must map every position of `self` to a distinct index less than the size of the grid.
+    fn remap<F>(&self, dim: Vector, index: F) -> Self
+    where
+        F: Fn(usize, usize) -> usize,
+    {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        let mut raw = Vec::with_capacity(self.raw.len());
+        let spare = &mut raw.spare_capacity_mut()[..self.raw.len()];
+        for tile_y in (0..height).step_by(TILE) {
+            for tile_x in (0..width).step_by(TILE) {
+                for y in tile_y..(tile_y + TILE).min(height) {
+                    for x in tile_x..(tile_x + TILE).min(width) {
+                        spare[index(x, y)].write(self.raw[x + y * width].clone());
+                    }
+                }
+            }
+        }
+        // SAFETY: `index` is a bijection onto `0..self.raw.len()`, so every element has been initialised
+        unsafe { raw.set_len(self.raw.len()) };
+        Grid { raw, dim }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns a new grid with the rows and columns of `self` swapped, so that the value at `(x, y)` moves to `(y, x)`,
+    /// consuming `self`.
+    ///
+    /// Unlike [`Grid::transpose`], the values are moved rather than cloned, so this works for any `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<String> = Grid::from_fn(3, 2, |pos| pos.to_string());
+    ///
+    /// let transposed = grid.into_transpose();
+    ///
+    /// assert_eq!(transposed.dim(), v(2, 3));
+    /// assert_eq!(transposed[v(1, 2)], "(2, 1)");
+    /// ```
+    pub fn into_transpose(self) -> Self {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        let len = self.raw.len();
+        let mut raw = Vec::with_capacity(len);
+        let spare = &mut raw.spare_capacity_mut()[..len];
+        for (i, value) in self.raw.into_iter().enumerate() {
+            spare[(i % width) * height + i / width].write(value);
+        }
+        // SAFETY: `(x, y)` to `x * height + y` is a bijection onto `0..len`, so every element has been initialised
+        unsafe { raw.set_len(len) };
+        Grid {
+            raw,
+            dim: Vector::new(self.dim.y, self.dim.x),
+        }
+    }
+
+    /// Transposes the grid in place without allocating, so that the value at `(x, y)` moves to `(y, x)`.
+    ///
+    /// Panics if the grid is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2],
+    ///     [3, 4],
+    /// ]);
+    ///
+    /// grid.transpose_in_place();
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [1, 3],
+    ///     [2, 4],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn transpose_in_place(&mut self) {
+        self.try_transpose_in_place().or_panic()
+    }
+
+    /// Swaps the rows and columns of the grid in place, as in [`transpose_in_place`](Self::transpose_in_place),
+    /// or returns an error without changing the grid if it is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[1, 2]]);
+    ///
+    /// assert!(grid.try_transpose_in_place().is_err());
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2]]));
+    /// ```
+    pub fn try_transpose_in_place(&mut self) -> Result<(), GridError> {
+        self.check_square()?;
+        let size = self.width() as usize;
+        for y in 0..size {
+            for x in y + 1..size {
+                self.raw.swap(x + y * size, y + x * size);
+            }
+        }
+        Ok(())
+    }
+
+    /// Rotates the values of the grid 90 degrees clockwise in place without allocating.
+    ///
+    /// Panics if the grid is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2],
+    ///     [3, 4],
+    /// ]);
+    ///
+    /// grid.rotate_cw_in_place();
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [3, 1],
+    ///     [4, 2],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn rotate_cw_in_place(&mut self) {
+        self.try_rotate_cw_in_place().or_panic()
+    }
+
+    /// Rotates the values of the grid 90 degrees clockwise in place, as in [`rotate_cw_in_place`](Self::rotate_cw_in_place),
+    /// or returns an error without changing the grid if it is not square.
+    pub fn try_rotate_cw_in_place(&mut self) -> Result<(), GridError> {
+        self.try_transpose_in_place()?;
+        self.flip_horizontal_in_place();
+        Ok(())
+    }
+
+    /// Rotates the values of the grid 90 degrees counterclockwise in place without allocating.
+    ///
+    /// Panics if the grid is not square.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2],
+    ///     [3, 4],
+    /// ]);
+    ///
+    /// grid.rotate_ccw_in_place();
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [2, 4],
+    ///     [1, 3],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn rotate_ccw_in_place(&mut self) {
+        self.try_rotate_ccw_in_place().or_panic()
+    }
+
+    /// Rotates the values of the grid 90 degrees counterclockwise in place, as in [`rotate_ccw_in_place`](Self::rotate_ccw_in_place),
+    /// or returns an error without changing the grid if it is not square.
+    pub fn try_rotate_ccw_in_place(&mut self) -> Result<(), GridError> {
+        self.try_transpose_in_place()?;
+        self.flip_vertical_in_place();
+        Ok(())
+    }
+
+    /// Mirrors the grid in place across its vertical axis without allocating, reversing the order of the values in each row.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// grid.flip_horizontal_in_place();
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [3, 2, 1],
+    ///     [6, 5, 4],
+    /// ]));
+    ///
+    /// let mut empty: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// empty.flip_horizontal_in_place();
+    /// assert_eq!(empty.dim(), v(0, 2));
+    /// ```
+    pub fn flip_horizontal_in_place(&mut self) {
+        for row in self.rows_mut() {
+            row.reverse();
+        }
+    }
+
+    /// Mirrors the grid in place across its horizontal axis without allocating, reversing the order of the rows.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// grid.flip_vertical_in_place();
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [4, 5, 6],
+    ///     [1, 2, 3],
+    /// ]));
+    /// ```
+    pub fn flip_vertical_in_place(&mut self) {
+        let (width, height) = (self.width() as usize, self.height() as usize);
+        for y in 0..height / 2 {
+            let (top, bottom) = self.raw.split_at_mut((height - 1 - y) * width);
+            top[y * width..(y + 1) * width].swap_with_slice(&mut bottom[..width]);
+        }
+    }
+
+    /// Rotates the values of the grid 180 degrees in place without allocating.
+    ///
+    /// Unlike the quarter turn rotations, this works for grids of any dimensions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 2, 3],
+    ///     [4, 5, 6],
+    /// ]);
+    ///
+    /// grid.rotate_180_in_place();
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [6, 5, 4],
+    ///     [3, 2, 1],
+    /// ]));
+    /// ```
+    #[inline]
+    pub fn rotate_180_in_place(&mut self) {
+        self.raw.reverse();
+    }
+
+    fn check_square(&self) -> Result<(), GridError> {
+        if self.width() != self.height() {
+            return Err(GridError::InvalidArgument("the grid must be square"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+//! Turtle graphics that draw into a `Grid`, and Lindenmayer systems to drive them.
+
+use crate::{grid::Grid, vector::Vector};
+
+use std::collections::BTreeMap;
+
+/// A turtle that moves over a grid, painting the positions it passes through while its pen is down.
+///
+/// Unlike a [`Walker`](crate::walker::Walker), a turtle may wander out of bounds;
+/// it simply paints nothing until it comes back.
+/// The pen starts up.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, turtle::Turtle};
+///
+/// let mut grid: Grid<char> = Grid::new(5, 5, '.');
+/// let mut turtle = Turtle::new(&mut grid, v(0, 0), EAST);
+///
+/// turtle.pen_down('#');
+/// for _ in 0..4 {
+///     turtle.forward(4);
+///     turtle.turn_right();
+/// }
+///
+/// assert_eq!(turtle.pos(), v(0, 0));
+/// assert_eq!(grid.iter().filter(|&&c| c == '#').count(), 16);
+/// assert_eq!(grid[v(2, 2)], '.');
+/// ```
+pub struct Turtle<'a, T> {
+    grid: &'a mut Grid<T>,
+    pos: Vector,
+    dir: Vector,
+    pen: Option<T>,
+    stack: Vec<(Vector, Vector)>,
+}
+
+impl<'a, T: Clone> Turtle<'a, T> {
+    /// Constructs a new `Turtle` at the given position of the grid, facing in the given direction, with its pen up.
+    ///
+    /// The direction is the offset applied to the position by each step, and is not required to be a unit vector.
+    pub fn new(grid: &'a mut Grid<T>, pos: Vector, dir: Vector) -> Self {
+        Self {
+            grid,
+            pos,
+            dir,
+            pen: None,
+            stack: Vec::new(),
+        }
+    }
+
+    /// Returns a reference to the grid.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        self.grid
+    }
+
+    /// Returns the current position of the turtle.
+    #[inline]
+    pub fn pos(&self) -> Vector {
+        self.pos
+    }
+
+    /// Returns the direction the turtle is facing.
+    #[inline]
+    pub fn dir(&self) -> Vector {
+        self.dir
+    }
+
+    /// Sets the direction the turtle is facing.
+    #[inline]
+    pub fn set_dir(&mut self, dir: Vector) {
+        self.dir = dir;
+    }
+
+    /// Returns `true` if the pen is down, or `false` otherwise.
+    #[inline]
+    pub fn is_pen_down(&self) -> bool {
+        self.pen.is_some()
+    }
+
+    /// Puts the pen down with the given value, painting the current position if it is in bounds.
+    pub fn pen_down(&mut self, value: T) {
+        self.pen = Some(value);
+        self.paint();
+    }
+
+    /// Lifts the pen, so that the turtle stops painting.
+    #[inline]
+    pub fn pen_up(&mut self) {
+        self.pen = None;
+    }
+
+    /// Moves the turtle to the given position without painting, keeping its direction.
+    #[inline]
+    pub fn jump(&mut self, pos: Vector) {
+        self.pos = pos;
+    }
+
+    /// Moves the turtle `steps` steps in the direction it is facing, painting every in bounds position it moves to if the pen is down.
+    pub fn forward(&mut self, steps: i64) {
+        for _ in 0..steps {
+            self.pos += self.dir;
+            self.paint();
+        }
+    }
+
+    /// Turns the turtle 90 degrees to the left.
+    #[inline]
+    pub fn turn_left(&mut self) {
+        self.dir = -self.dir.perp();
+    }
+
+    /// Turns the turtle 90 degrees to the right.
+    #[inline]
+    pub fn turn_right(&mut self) {
+        self.dir = self.dir.perp();
+    }
+
+    /// Saves the current position and direction of the turtle.
+    #[inline]
+    pub fn push(&mut self) {
+        self.stack.push((self.pos, self.dir));
+    }
+
+    /// Restores the most recently saved position and direction of the turtle, without painting.
+    ///
+    /// Returns `false` and leaves the turtle as it is if nothing is saved, or `true` otherwise.
+    pub fn pop(&mut self) -> bool {
+        match self.stack.pop() {
+            Some((pos, dir)) => {
+                self.pos = pos;
+                self.dir = dir;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Follows a string of turtle commands, where each forward move is `length` steps:
+    /// * `F` and `G` move forward, painting if the pen is down
+    /// * `f` moves forward without painting
+    /// * `+` turns left and `-` turns right
+    /// * `[` saves the position and direction, and `]` restores them
+    ///
+    /// Every other character is ignored, so the output of an [`LSystem`] can be followed directly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, turtle::Turtle};
+    ///
+    /// let mut grid: Grid<bool> = Grid::new(7, 7, false);
+    /// let mut turtle = Turtle::new(&mut grid, v(3, 6), NORTH);
+    ///
+    /// turtle.pen_down(true);
+    /// turtle.follow("F[+F][-F]F", 2);
+    ///
+    /// assert_eq!(turtle.pos(), v(3, 2));
+    /// assert!(grid[v(1, 4)] && grid[v(5, 4)] && grid[v(3, 2)]);
+    /// assert_eq!(grid.iter().filter(|&&b| b).count(), 9);
+    /// ```
+    pub fn follow(&mut self, commands: &str, length: i64) {
+        for command in commands.chars() {
+            match command {
+                'F' | 'G' => self.forward(length),
+                'f' => self.jump(self.pos + self.dir * length),
+                '+' => self.turn_left(),
+                '-' => self.turn_right(),
+                '[' => self.push(),
+                ']' => {
+                    self.pop();
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn paint(&mut self) {
+        if let (Some(value), Some(cell)) = (&self.pen, self.grid.get_mut(self.pos)) {
+            *cell = value.clone();
+        }
+    }
+}
+
+/// A Lindenmayer system, which rewrites every character of a string in parallel according to a set of rules.
+///
+/// Characters without a rule are left unchanged.
+///
+/// # Examples
+///
+/// The dragon curve:
+///
+/// ```
+/// use grid::{prelude::*, turtle::{LSystem, Turtle}};
+///
+/// let dragon = LSystem::new("FX").with_rule('X', "X+YF+").with_rule('Y', "-FX-Y");
+///
+/// assert_eq!(dragon.expand(1), "FX+YF+");
+/// assert_eq!(dragon.expand(2), "FX+YF++-FX-YF+");
+///
+/// let mut grid: Grid<bool> = Grid::new(64, 64, false);
+/// let mut turtle = Turtle::new(&mut grid, v(16, 24), EAST);
+/// turtle.pen_down(true);
+/// turtle.follow(&dragon.expand(8), 2);
+///
+/// // 256 segments of length 2, some of which meet at their corners
+/// assert_eq!(grid.iter().filter(|&&b| b).count(), 446);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct LSystem {
+    axiom: String,
+    rules: BTreeMap<char, String>,
+}
+
+impl LSystem {
+    /// Constructs a new `LSystem` with the given starting string and no rules.
+    pub fn new(axiom: &str) -> Self {
+        Self {
+            axiom: axiom.to_string(),
+            rules: BTreeMap::new(),
+        }
+    }
+
+    /// Returns `self` with a rule that rewrites `from` as `to`, replacing any existing rule for `from`.
+    pub fn with_rule(mut self, from: char, to: &str) -> Self {
+        self.rules.insert(from, to.to_string());
+        self
+    }
+
+    /// Returns the starting string.
+    #[inline]
+    pub fn axiom(&self) -> &str {
+        &self.axiom
+    }
+
+    /// Returns the string produced by applying the rules `iterations` times to the starting string.
+    pub fn expand(&self, iterations: usize) -> String {
+        let mut current = self.axiom.clone();
+        for _ in 0..iterations {
+            let mut next = String::with_capacity(current.len() * 2);
+            for c in current.chars() {
+                match self.rules.get(&c) {
+                    Some(to) => next.push_str(to),
+                    None => next.push(c),
+                }
+            }
+            current = next;
+        }
+        current
+    }
+}
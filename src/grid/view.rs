@@ -0,0 +1,1007 @@
+//! Borrowed rectangular windows into a `Grid`, immutable or mutable.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{check_region, iterators::Positions, Grid},
+    rect::Rect,
+    vector::Vector,
+};
+
+use std::{
+    iter::{Flatten, FusedIterator},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+impl<T> Grid<T> {
+    /// Returns a view of the rectangular region of the grid covered by `rect`, without copying.
+    ///
+    /// Panics if `rect` is empty or not entirely in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(6, 5, |pos| pos.x + pos.y * 6);
+    ///
+    /// let view = grid.view(Rect::new(v(2, 1), v(3, 2)));
+    ///
+    /// assert_eq!(view.dim(), v(3, 2));
+    /// assert_eq!(view[v(0, 0)], 8);
+    /// assert_eq!(view.get(v(2, 1)), Some(&16));
+    /// assert_eq!(view.get(v(3, 1)), None);
+    /// assert_eq!(view.iter().sum::<i64>(), 8 + 9 + 10 + 14 + 15 + 16);
+    /// ```
+    #[track_caller]
+    pub fn view(&self, rect: Rect) -> GridView<'_, T> {
+        self.try_view(rect).or_panic()
+    }
+
+    /// Returns a view of the rectangular region of the grid covered by `rect`, without copying,
+    /// or an error if `rect` is empty or not entirely in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(4, 3, 0);
+    ///
+    /// assert_eq!(grid.try_view(Rect::new(v(1, 1), v(3, 2))).map(|view| view.dim()), Ok(v(3, 2)));
+    /// assert_eq!(
+    ///     grid.try_view(Rect::new(v(1, 1), v(4, 2))).err(),
+    ///     Some(GridError::OutOfBounds { dim: v(4, 3), region: Rect::new(v(1, 1), v(4, 2)) }),
+    /// );
+    /// assert_eq!(
+    ///     grid.try_view(Rect::new(v(1, 1), v(0, 2))).err(),
+    ///     Some(GridError::InvalidDimensions { width: 0, height: 2 }),
+    /// );
+    /// ```
+    pub fn try_view(&self, rect: Rect) -> Result<GridView<'_, T>, GridError> {
+        check_region(self.dim, rect)?;
+        Ok(GridView::new(
+            self.raw.as_ptr(),
+            self.width() as usize,
+            rect,
+        ))
+    }
+
+    /// Returns a view of the whole grid, which is empty if the grid is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([[], []]);
+    /// let view = grid.as_view();
+    ///
+    /// assert_eq!(view.dim(), v(0, 2));
+    /// assert_eq!(view.rows().count(), 2);
+    /// assert_eq!(view.iter().count(), 0);
+    /// ```
+    #[inline]
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView::new(
+            self.raw.as_ptr(),
+            self.width() as usize,
+            Rect::new(Vector::new(0, 0), self.dim),
+        )
+    }
+
+    /// Returns an iterator over every overlapping window of the given dimensions that fits in the grid,
+    /// as the position of its top-left corner and a view of it, in row-major order of the top-left corners.
+    ///
+    /// There are no windows if `dim` is larger than the grid in either dimension.
+    ///
+    /// Panics if the window dimensions are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     "M.S.".chars(),
+    ///     ".A..".chars(),
+    ///     "M.S.".chars(),
+    /// ]);
+    ///
+    /// let found: Vec<Vector> = grid
+    ///     .windows(v(3, 3))
+    ///     .filter(|(_, w)| w[v(1, 1)] == 'A')
+    ///     .filter(|(_, w)| [w[v(0, 0)], w[v(2, 2)]] == ['M', 'S'] && [w[v(0, 2)], w[v(2, 0)]] == ['M', 'S'])
+    ///     .map(|(pos, _)| pos)
+    ///     .collect();
+    ///
+    /// assert_eq!(found, vec![v(0, 0)]);
+    /// assert_eq!(grid.windows(v(2, 2)).count(), 6);
+    /// assert_eq!(grid.windows(v(5, 1)).count(), 0);
+    /// ```
+    #[track_caller]
+    pub fn windows(&self, dim: Vector) -> Windows<'_, T> {
+        self.try_windows(dim).or_panic()
+    }
+
+    /// Returns an iterator over every overlapping window of the given dimensions, as in [`windows`](Self::windows),
+    /// or an error if the window dimensions are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// assert_eq!(grid.try_windows(v(2, 2)).map(Iterator::count), Ok(4));
+    /// assert_eq!(grid.try_windows(v(0, 2)).err(), Some(GridError::InvalidDimensions { width: 0, height: 2 }));
+    /// ```
+    pub fn try_windows(&self, dim: Vector) -> Result<Windows<'_, T>, GridError> {
+        if dim.x <= 0 || dim.y <= 0 {
+            return Err(GridError::InvalidDimensions {
+                width: dim.x,
+                height: dim.y,
+            });
+        }
+        Ok(Windows {
+            grid: self,
+            dim,
+            positions: Positions::new(Vector::new(0, 0), self.dim - dim + Vector::new(1, 1)),
+        })
+    }
+
+    /// Returns an iterator over the grid split into non-overlapping chunks of the given dimensions,
+    /// as the position of the top-left corner of each chunk and a view of it, in row-major order.
+    ///
+    /// The chunks cover the entire grid, so if the dimensions of the grid are not multiples of `dim`,
+    /// the chunks on the right and bottom edges are smaller. Use [`chunks_exact`](Self::chunks_exact) to skip them instead.
+    ///
+    /// Panics if the chunk dimensions are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::from_nested_iter([
+    ///     [1, 1, 2, 2, 3],
+    ///     [1, 1, 2, 2, 3],
+    ///     [4, 4, 5, 5, 6],
+    /// ]);
+    ///
+    /// let chunks: Vec<(Vector, Vector)> = grid.chunks(v(2, 2)).map(|(pos, chunk)| (pos, chunk.dim())).collect();
+    ///
+    /// assert_eq!(chunks, vec![
+    ///     (v(0, 0), v(2, 2)), (v(2, 0), v(2, 2)), (v(4, 0), v(1, 2)),
+    ///     (v(0, 2), v(2, 1)), (v(2, 2), v(2, 1)), (v(4, 2), v(1, 1)),
+    /// ]);
+    /// ```
+    #[track_caller]
+    pub fn chunks(&self, dim: Vector) -> Chunks<'_, T> {
+        self.try_chunks(dim).or_panic()
+    }
+
+    /// Returns an iterator over the grid split into non-overlapping chunks, as in [`chunks`](Self::chunks),
+    /// or an error if the chunk dimensions are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// assert_eq!(grid.try_chunks(v(2, 2)).map(Iterator::count), Ok(4));
+    /// assert!(grid.try_chunks(v(2, -1)).is_err());
+    /// ```
+    pub fn try_chunks(&self, dim: Vector) -> Result<Chunks<'_, T>, GridError> {
+        Chunks::new(self, dim, false)
+    }
+
+    /// Returns an iterator over the grid split into non-overlapping chunks of exactly the given dimensions,
+    /// as the position of the top-left corner of each chunk and a view of it, in row-major order.
+    ///
+    /// If the dimensions of the grid are not multiples of `dim`, the values on the right and bottom edges
+    /// that do not fill a whole chunk are skipped.
+    ///
+    /// Panics if the chunk dimensions are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u64> = Grid::from_fn(5, 4, |pos| (pos.x + pos.y) as u64);
+    ///
+    /// // downsample by averaging 2 by 2 blocks
+    /// let mut small = Grid::new(2, 2, 0);
+    /// for (pos, chunk) in grid.chunks_exact(v(2, 2)) {
+    ///     small[pos / 2] = chunk.iter().sum::<u64>() / 4;
+    /// }
+    ///
+    /// assert_eq!(small, Grid::from_nested_iter([[1, 3], [3, 5]]));
+    /// ```
+    #[track_caller]
+    pub fn chunks_exact(&self, dim: Vector) -> Chunks<'_, T> {
+        self.try_chunks_exact(dim).or_panic()
+    }
+
+    /// Returns an iterator over the grid split into non-overlapping chunks of exactly the given dimensions,
+    /// as in [`chunks_exact`](Self::chunks_exact), or an error if the chunk dimensions are not positive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// assert_eq!(grid.try_chunks_exact(v(2, 2)).map(Iterator::count), Ok(1));
+    /// assert!(grid.try_chunks_exact(v(0, 0)).is_err());
+    /// ```
+    pub fn try_chunks_exact(&self, dim: Vector) -> Result<Chunks<'_, T>, GridError> {
+        Chunks::new(self, dim, true)
+    }
+
+    /// Returns a mutable view of the rectangular region of the grid covered by `rect`.
+    ///
+    /// Panics if `rect` is empty or not entirely in bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(5, 4, 0);
+    ///
+    /// let mut view = grid.view_mut(Rect::new(v(1, 1), v(3, 2)));
+    /// view[v(0, 0)] = 1;
+    /// for value in view.iter_mut() {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([
+    ///     [0, 0, 0, 0, 0],
+    ///     [0, 2, 1, 1, 0],
+    ///     [0, 1, 1, 1, 0],
+    ///     [0, 0, 0, 0, 0],
+    /// ]));
+    /// ```
+    #[track_caller]
+    pub fn view_mut(&mut self, rect: Rect) -> GridViewMut<'_, T> {
+        self.try_view_mut(rect).or_panic()
+    }
+
+    /// Returns a mutable view of the rectangular region of the grid covered by `rect`,
+    /// or an error if `rect` is empty or not entirely in bounds.
+    pub fn try_view_mut(&mut self, rect: Rect) -> Result<GridViewMut<'_, T>, GridError> {
+        check_region(self.dim, rect)?;
+        let width = self.width() as usize;
+        Ok(GridViewMut::new(self.raw.as_mut_ptr(), width, rect))
+    }
+
+    /// Returns a mutable view of the whole grid, which is empty if the grid is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::from_nested_iter([[], [], []]);
+    /// let view = grid.as_view_mut();
+    ///
+    /// assert_eq!(view.dim(), v(0, 3));
+    /// assert!(view.split_at_row(1).1.get(v(0, 0)).is_none());
+    /// ```
+    #[inline]
+    pub fn as_view_mut(&mut self) -> GridViewMut<'_, T> {
+        let width = self.width() as usize;
+        GridViewMut::new(
+            self.raw.as_mut_ptr(),
+            width,
+            Rect::new(Vector::new(0, 0), self.dim),
+        )
+    }
+}
+
+/// An immutable view of a rectangular region of a `Grid`, indexed by positions relative to the top-left corner of the region.
+///
+/// Constructed by [`Grid::view`].
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let grid: Grid<char> = Grid::from_nested_iter([
+///     "#####".chars(),
+///     "#..x#".chars(),
+///     "#.x.#".chars(),
+///     "#####".chars(),
+/// ]);
+///
+/// let room = grid.view(Rect::new(v(1, 1), v(3, 2)));
+///
+/// let found: Vec<Vector> = room.iter_positions().filter(|&(_, &c)| c == 'x').map(|(pos, _)| pos).collect();
+/// assert_eq!(found, vec![v(2, 0), v(1, 1)]);
+/// assert_eq!(room.rows().collect::<Vec<_>>(), vec![&['.', '.', 'x'][..], &['.', 'x', '.'][..]]);
+///
+/// let corner = room.view(Rect::new(v(1, 0), v(2, 2)));
+/// assert_eq!(corner.origin(), v(2, 1));
+/// assert_eq!(corner.to_grid(), Grid::from_nested_iter([".x".chars(), "x.".chars()]));
+/// ```
+pub struct GridView<'a, T> {
+    /// Points to the top-left value of the view.
+    ptr: *const T,
+    /// The distance between the starts of consecutive rows, which is the width of the underlying grid.
+    stride: usize,
+    rect: Rect,
+    marker: PhantomData<&'a T>,
+}
+
+// SAFETY: a `GridView` only gives out shared references to the values in its region, like a `&[T]`
+unsafe impl<'a, T: Sync> Send for GridView<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for GridView<'a, T> {}
+
+impl<'a, T> GridView<'a, T> {
+    /// Constructs a view of the region `rect` of a grid with raw values starting at `base` and the given width,
+    /// where `rect` must be in bounds, or be empty and start no further into the values than their end, as in a zero-width grid.
+    fn new(base: *const T, stride: usize, rect: Rect) -> Self {
+        Self {
+            // SAFETY: `rect.pos` is in bounds, or the view is empty and starts no further than the end, so the offset is within the allocation
+            ptr: unsafe { base.add(rect.pos.y as usize * stride + rect.pos.x as usize) },
+            stride,
+            rect,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the dimensions of the view.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.rect.dim
+    }
+
+    /// Returns the width of the view.
+    #[inline]
+    pub fn width(&self) -> i64 {
+        self.rect.dim.x
+    }
+
+    /// Returns the height of the view.
+    #[inline]
+    pub fn height(&self) -> i64 {
+        self.rect.dim.y
+    }
+
+    /// Returns the position in the underlying grid of the top-left corner of the view.
+    #[inline]
+    pub fn origin(&self) -> Vector {
+        self.rect.pos
+    }
+
+    /// Returns the region of the underlying grid covered by the view.
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Returns `true` if the given position is within the view, or `false` otherwise.
+    #[inline]
+    pub fn in_bounds(&self, pos: Vector) -> bool {
+        (0..self.width()).contains(&pos.x) && (0..self.height()).contains(&pos.y)
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&'a T> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        // SAFETY: `pos` is within the view, so the value is in the region the view borrows
+        Some(unsafe { &*self.ptr.add(pos.y as usize * self.stride + pos.x as usize) })
+    }
+
+    /// Returns a view of the rectangular region of this view covered by `rect`, relative to the top-left corner of this view.
+    ///
+    /// Panics if `rect` is empty or not entirely in bounds.
+    #[track_caller]
+    pub fn view(&self, rect: Rect) -> GridView<'a, T> {
+        self.try_view(rect).or_panic()
+    }
+
+    /// Returns a view of the rectangular region of this view covered by `rect`, relative to the top-left corner of this view,
+    /// or an error if `rect` is empty or not entirely in bounds.
+    pub fn try_view(&self, rect: Rect) -> Result<GridView<'a, T>, GridError> {
+        check_region(self.dim(), rect)?;
+        let mut view = GridView::new(self.ptr, self.stride, rect);
+        view.rect.pos += self.rect.pos;
+        Ok(view)
+    }
+
+    /// Returns a slice of the row of the view at the given `y` coordinate, or `None` if out of bounds.
+    pub fn get_row(&self, y: i64) -> Option<&'a [T]> {
+        if !(0..self.height()).contains(&y) {
+            return None;
+        }
+        // SAFETY: row `y` is within the view, so its values are in the region the view borrows
+        Some(unsafe {
+            slice::from_raw_parts(
+                self.ptr.add(y as usize * self.stride),
+                self.width() as usize,
+            )
+        })
+    }
+
+    /// Returns an iterator over slices of the rows of the view, from top to bottom.
+    #[inline]
+    pub fn rows(&self) -> ViewRows<'a, T> {
+        ViewRows {
+            view: *self,
+            front: 0,
+            back: self.height(),
+        }
+    }
+
+    /// Returns an iterator over references to the values in the view, in row-major order.
+    #[inline]
+    pub fn iter(&self) -> Flatten<ViewRows<'a, T>> {
+        self.rows().flatten()
+    }
+
+    /// Returns an iterator over every position in the view, in row-major order.
+    #[inline]
+    pub fn positions(&self) -> Positions {
+        Positions::new(Vector::new(0, 0), self.dim())
+    }
+
+    /// Returns an iterator over every position in the view and a reference to its value, in row-major order.
+    #[inline]
+    pub fn iter_positions(&self) -> ViewPositionIter<'a, T> {
+        ViewPositionIter {
+            view: *self,
+            positions: self.positions(),
+        }
+    }
+
+    /// Returns a new grid containing copies of the values in the view.
+    pub fn to_grid(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            raw: self.iter().cloned().collect(),
+            dim: self.dim(),
+        }
+    }
+}
+
+impl<'a, T> Clone for GridView<'a, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, T> Copy for GridView<'a, T> {}
+
+impl<'a, T> Index<Vector> for GridView<'a, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, pos: Vector) -> &Self::Output {
+        let Some(value) = self.get(pos) else {
+            panic!(
+                "position out of bounds: the dimensions are {} but the position is {pos}",
+                self.dim()
+            )
+        };
+        value
+    }
+}
+
+/// An iterator over slices of the rows of a [`GridView`], from top to bottom.
+pub struct ViewRows<'a, T> {
+    view: GridView<'a, T>,
+    front: i64,
+    back: i64,
+}
+
+impl<'a, T> Iterator for ViewRows<'a, T> {
+    type Item = &'a [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        self.view.get_row(self.front - 1)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.back - self.front) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ViewRows<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        self.view.get_row(self.back)
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ViewRows<'a, T> {}
+
+impl<'a, T> FusedIterator for ViewRows<'a, T> {}
+
+/// An iterator over every position in a [`GridView`] and a reference to its value, in row-major order.
+pub struct ViewPositionIter<'a, T> {
+    view: GridView<'a, T>,
+    positions: Positions,
+}
+
+impl<'a, T> Iterator for ViewPositionIter<'a, T> {
+    type Item = (Vector, &'a T);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        Some((pos, self.view.get(pos)?))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let pos = self.positions.nth(n)?;
+        Some((pos, self.view.get(pos)?))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ViewPositionIter<'a, T> {}
+
+impl<'a, T> FusedIterator for ViewPositionIter<'a, T> {}
+
+/// An iterator over every overlapping window of a `Grid` with fixed dimensions, as the position of its top-left corner and a [`GridView`] of it.
+///
+/// Constructed by [`Grid::windows`].
+pub struct Windows<'a, T> {
+    grid: &'a Grid<T>,
+    dim: Vector,
+    positions: Positions,
+}
+
+impl<'a, T> Iterator for Windows<'a, T> {
+    type Item = (Vector, GridView<'a, T>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let pos = self.positions.next()?;
+        let view = GridView::new(
+            self.grid.raw.as_ptr(),
+            self.grid.width() as usize,
+            Rect::new(pos, self.dim),
+        );
+        Some((pos, view))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.positions.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Windows<'a, T> {}
+
+impl<'a, T> FusedIterator for Windows<'a, T> {}
+
+/// An iterator over a `Grid` split into non-overlapping chunks, as the position of the top-left corner of each chunk and a [`GridView`] of it.
+///
+/// Constructed by [`Grid::chunks`] and [`Grid::chunks_exact`].
+pub struct Chunks<'a, T> {
+    grid: &'a Grid<T>,
+    dim: Vector,
+    chunks: Positions,
+}
+
+impl<'a, T> Chunks<'a, T> {
+    fn new(grid: &'a Grid<T>, dim: Vector, exact: bool) -> Result<Self, GridError> {
+        if dim.x <= 0 || dim.y <= 0 {
+            return Err(GridError::InvalidDimensions {
+                width: dim.x,
+                height: dim.y,
+            });
+        }
+        let count = |len: i64, size: i64| {
+            if exact || len % size == 0 {
+                len / size
+            } else {
+                len / size + 1
+            }
+        };
+        Ok(Self {
+            grid,
+            dim,
+            chunks: Positions::new(
+                Vector::new(0, 0),
+                Vector::new(count(grid.dim.x, dim.x), count(grid.dim.y, dim.y)),
+            ),
+        })
+    }
+}
+
+impl<'a, T> Iterator for Chunks<'a, T> {
+    type Item = (Vector, GridView<'a, T>);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let chunk = self.chunks.next()?;
+        let pos = Vector::new(chunk.x * self.dim.x, chunk.y * self.dim.y);
+        let view = GridView::new(
+            self.grid.raw.as_ptr(),
+            self.grid.width() as usize,
+            Rect::new(pos, self.dim.min(self.grid.dim - pos)),
+        );
+        Some((pos, view))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.chunks.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Chunks<'a, T> {}
+
+impl<'a, T> FusedIterator for Chunks<'a, T> {}
+
+/// A mutable view of a rectangular region of a `Grid`, indexed by positions relative to the top-left corner of the region.
+///
+/// A mutable view can be split into smaller views of regions that do not overlap, which can then be modified independently,
+/// even on different threads.
+///
+/// Constructed by [`Grid::view_mut`].
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let mut grid: Grid<u32> = Grid::new(6, 4, 0);
+///
+/// let (top, bottom) = grid.as_view_mut().split_at_row(2);
+/// let (top_left, top_right) = top.split_at_col(3);
+/// let (bottom_left, bottom_right) = bottom.split_at_col(3);
+///
+/// std::thread::scope(|scope| {
+///     for (i, mut quadrant) in [top_left, top_right, bottom_left, bottom_right].into_iter().enumerate() {
+///         scope.spawn(move || {
+///             for value in quadrant.iter_mut() {
+///                 *value = i as u32;
+///             }
+///         });
+///     }
+/// });
+///
+/// assert_eq!(grid, Grid::from_nested_iter([
+///     [0, 0, 0, 1, 1, 1],
+///     [0, 0, 0, 1, 1, 1],
+///     [2, 2, 2, 3, 3, 3],
+///     [2, 2, 2, 3, 3, 3],
+/// ]));
+/// ```
+pub struct GridViewMut<'a, T> {
+    /// Points to the top-left value of the view.
+    ptr: *mut T,
+    /// The distance between the starts of consecutive rows, which is the width of the underlying grid.
+    stride: usize,
+    rect: Rect,
+    marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: a `GridViewMut` has exclusive access to the values in its region, like a `&mut [T]`
+unsafe impl<'a, T: Send> Send for GridViewMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for GridViewMut<'a, T> {}
+
+impl<'a, T> GridViewMut<'a, T> {
+    /// Constructs a mutable view of the region `rect` of a grid with raw values starting at `base` and the given width,
+    /// where `rect` must be in bounds, or be empty and start no further into the values than their end, as in a zero-width grid,
+    /// and must not overlap any other view.
+    fn new(base: *mut T, stride: usize, rect: Rect) -> Self {
+        Self {
+            // SAFETY: `rect.pos` is in bounds, or the view is empty and starts no further than the end, so the offset is within the allocation
+            ptr: unsafe { base.add(rect.pos.y as usize * stride + rect.pos.x as usize) },
+            stride,
+            rect,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the dimensions of the view.
+    #[inline]
+    pub fn dim(&self) -> Vector {
+        self.rect.dim
+    }
+
+    /// Returns the width of the view.
+    #[inline]
+    pub fn width(&self) -> i64 {
+        self.rect.dim.x
+    }
+
+    /// Returns the height of the view.
+    #[inline]
+    pub fn height(&self) -> i64 {
+        self.rect.dim.y
+    }
+
+    /// Returns the position in the underlying grid of the top-left corner of the view.
+    #[inline]
+    pub fn origin(&self) -> Vector {
+        self.rect.pos
+    }
+
+    /// Returns the region of the underlying grid covered by the view.
+    #[inline]
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Returns `true` if the given position is within the view, or `false` otherwise.
+    #[inline]
+    pub fn in_bounds(&self, pos: Vector) -> bool {
+        (0..self.width()).contains(&pos.x) && (0..self.height()).contains(&pos.y)
+    }
+
+    /// Returns an immutable view of the same region, for reading.
+    #[inline]
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            ptr: self.ptr,
+            stride: self.stride,
+            rect: self.rect,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns a reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get(&self, pos: Vector) -> Option<&T> {
+        self.as_view().get(pos)
+    }
+
+    /// Returns a mutable reference to the value at the given position, or `None` if out of bounds.
+    #[inline]
+    pub fn get_mut(&mut self, pos: Vector) -> Option<&mut T> {
+        if !self.in_bounds(pos) {
+            return None;
+        }
+        // SAFETY: `pos` is within the view, so the value is in the region the view borrows exclusively
+        Some(unsafe { &mut *self.ptr.add(pos.y as usize * self.stride + pos.x as usize) })
+    }
+
+    /// Returns a mutable slice of the row of the view at the given `y` coordinate, or `None` if out of bounds.
+    pub fn get_row_mut(&mut self, y: i64) -> Option<&mut [T]> {
+        self.rows_mut().nth(usize::try_from(y).ok()?)
+    }
+
+    /// Returns an iterator over slices of the rows of the view, from top to bottom.
+    #[inline]
+    pub fn rows(&self) -> ViewRows<'_, T> {
+        self.as_view().rows()
+    }
+
+    /// Returns an iterator over mutable slices of the rows of the view, from top to bottom.
+    #[inline]
+    pub fn rows_mut(&mut self) -> ViewRowsMut<'_, T> {
+        ViewRowsMut {
+            ptr: self.ptr,
+            stride: self.stride,
+            width: self.width() as usize,
+            front: 0,
+            back: self.height() as usize,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over references to the values in the view, in row-major order.
+    #[inline]
+    pub fn iter(&self) -> Flatten<ViewRows<'_, T>> {
+        self.rows().flatten()
+    }
+
+    /// Returns an iterator over mutable references to the values in the view, in row-major order.
+    #[inline]
+    pub fn iter_mut(&mut self) -> Flatten<ViewRowsMut<'_, T>> {
+        self.rows_mut().flatten()
+    }
+
+    /// Returns a mutable view of the rectangular region of this view covered by `rect`, relative to the top-left corner of this view.
+    ///
+    /// Panics if `rect` is empty or not entirely in bounds.
+    #[track_caller]
+    pub fn view_mut(&mut self, rect: Rect) -> GridViewMut<'_, T> {
+        self.try_view_mut(rect).or_panic()
+    }
+
+    /// Returns a mutable view of the rectangular region of this view covered by `rect`, relative to the top-left corner of this view,
+    /// or an error if `rect` is empty or not entirely in bounds.
+    pub fn try_view_mut(&mut self, rect: Rect) -> Result<GridViewMut<'_, T>, GridError> {
+        check_region(self.dim(), rect)?;
+        Ok(self.sub(rect))
+    }
+
+    /// Splits the view into two at the given column, returning the columns to the left of `x` and the columns from `x` onwards.
+    ///
+    /// Panics if either half would be empty, that is if `x` is not between `1` and `width - 1` inclusive.
+    #[track_caller]
+    pub fn split_at_col(self, x: i64) -> (GridViewMut<'a, T>, GridViewMut<'a, T>) {
+        self.try_split_at_col(x).or_panic()
+    }
+
+    /// Splits the view into two at the given column, as in [`split_at_col`](Self::split_at_col),
+    /// or returns an error if either half would be empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let mut grid: Grid<u8> = Grid::new(3, 2, 0);
+    /// let mut view = grid.as_view_mut();
+    ///
+    /// assert!(view.view_mut(Rect::new(v(0, 0), v(3, 2))).try_split_at_col(3).is_err());
+    ///
+    /// let (mut left, mut right) = view.try_split_at_col(1).unwrap();
+    /// left[v(0, 0)] = 1;
+    /// right[v(0, 0)] = 2;
+    ///
+    /// assert_eq!(grid, Grid::from_nested_iter([[1, 2, 0], [0, 0, 0]]));
+    /// ```
+    pub fn try_split_at_col(
+        self,
+        x: i64,
+    ) -> Result<(GridViewMut<'a, T>, GridViewMut<'a, T>), GridError> {
+        if !(1..self.width()).contains(&x) {
+            return Err(GridError::InvalidArgument(
+                "a split must leave both halves non-empty",
+            ));
+        }
+        let (width, height) = (self.width(), self.height());
+        Ok((
+            self.sub(Rect::new(Vector::new(0, 0), Vector::new(x, height))),
+            self.sub(Rect::new(Vector::new(x, 0), Vector::new(width - x, height))),
+        ))
+    }
+
+    /// Splits the view into two at the given row, returning the rows above `y` and the rows from `y` onwards.
+    ///
+    /// Panics if either half would be empty, that is if `y` is not between `1` and `height - 1` inclusive.
+    #[track_caller]
+    pub fn split_at_row(self, y: i64) -> (GridViewMut<'a, T>, GridViewMut<'a, T>) {
+        self.try_split_at_row(y).or_panic()
+    }
+
+    /// Splits the view into two at the given row, as in [`split_at_row`](Self::split_at_row),
+    /// or returns an error if either half would be empty.
+    pub fn try_split_at_row(
+        self,
+        y: i64,
+    ) -> Result<(GridViewMut<'a, T>, GridViewMut<'a, T>), GridError> {
+        if !(1..self.height()).contains(&y) {
+            return Err(GridError::InvalidArgument(
+                "a split must leave both halves non-empty",
+            ));
+        }
+        let (width, height) = (self.width(), self.height());
+        Ok((
+            self.sub(Rect::new(Vector::new(0, 0), Vector::new(width, y))),
+            self.sub(Rect::new(Vector::new(0, y), Vector::new(width, height - y))),
+        ))
+    }
+
+    /// Returns a view of the region `rect` of this view, which must be in bounds, as in [`GridViewMut::new`],
+    /// with any lifetime, so the caller must make sure it does not overlap any other view.
+    fn sub<'b>(&self, rect: Rect) -> GridViewMut<'b, T> {
+        let mut view = GridViewMut::new(self.ptr, self.stride, rect);
+        view.rect.pos += self.rect.pos;
+        view
+    }
+}
+
+impl<'a, T> Index<Vector> for GridViewMut<'a, T> {
+    type Output = T;
+
+    #[track_caller]
+    fn index(&self, pos: Vector) -> &Self::Output {
+        let dim = self.dim();
+        let Some(value) = self.get(pos) else {
+            panic!("position out of bounds: the dimensions are {dim} but the position is {pos}")
+        };
+        value
+    }
+}
+
+impl<'a, T> IndexMut<Vector> for GridViewMut<'a, T> {
+    #[track_caller]
+    fn index_mut(&mut self, pos: Vector) -> &mut Self::Output {
+        let dim = self.dim();
+        let Some(value) = self.get_mut(pos) else {
+            panic!("position out of bounds: the dimensions are {dim} but the position is {pos}")
+        };
+        value
+    }
+}
+
+/// An iterator over mutable slices of the rows of a [`GridViewMut`], from top to bottom.
+pub struct ViewRowsMut<'a, T> {
+    ptr: *mut T,
+    stride: usize,
+    width: usize,
+    front: usize,
+    back: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+// SAFETY: the iterator has exclusive access to the rows it has not yet returned
+unsafe impl<'a, T: Send> Send for ViewRowsMut<'a, T> {}
+unsafe impl<'a, T: Sync> Sync for ViewRowsMut<'a, T> {}
+
+impl<'a, T> ViewRowsMut<'a, T> {
+    /// Returns the row at index `y`, which must not have been returned before.
+    fn row(&mut self, y: usize) -> &'a mut [T] {
+        // SAFETY: row `y` is within the view, and each row is only returned once, so no two references overlap
+        unsafe { slice::from_raw_parts_mut(self.ptr.add(y * self.stride), self.width) }
+    }
+}
+
+impl<'a, T> Iterator for ViewRowsMut<'a, T> {
+    type Item = &'a mut [T];
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.front += 1;
+        Some(self.row(self.front - 1))
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n).min(self.back);
+        self.next()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.back - self.front;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for ViewRowsMut<'a, T> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.front == self.back {
+            return None;
+        }
+        self.back -= 1;
+        Some(self.row(self.back))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for ViewRowsMut<'a, T> {}
+
+impl<'a, T> FusedIterator for ViewRowsMut<'a, T> {}
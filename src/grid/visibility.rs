@@ -0,0 +1,121 @@
+//! Lines between positions, and line of sight checks over a `Grid`.
+
+use crate::{grid::Grid, vector::Vector};
+
+use std::iter::FusedIterator;
+
+/// An iterator over the positions on the straight line between two positions, from the first to the last inclusive,
+/// computed with Bresenham's line algorithm.
+///
+/// Consecutive positions on the line are adjacent, either orthogonally or diagonally.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, visibility::Line};
+///
+/// assert_eq!(Line::new(v(0, 0), v(4, 2)).collect::<Vec<_>>(), vec![
+///     v(0, 0), v(1, 1), v(2, 1), v(3, 2), v(4, 2),
+/// ]);
+/// assert_eq!(Line::new(v(1, 1), v(1, -1)).collect::<Vec<_>>(), vec![v(1, 1), v(1, 0), v(1, -1)]);
+/// assert_eq!(Line::new(v(3, 3), v(3, 3)).count(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Line {
+    pos: Vector,
+    end: Vector,
+    delta: Vector,
+    step: Vector,
+    error: i64,
+    done: bool,
+}
+
+impl Line {
+    /// Constructs a new `Line` from `start` to `end`.
+    pub fn new(start: Vector, end: Vector) -> Self {
+        let diff = end - start;
+        let delta = Vector::new(diff.x.abs(), -diff.y.abs());
+        Self {
+            pos: start,
+            end,
+            delta,
+            step: diff.signum(),
+            error: delta.x + delta.y,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Line {
+    type Item = Vector;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let pos = self.pos;
+        if pos == self.end {
+            self.done = true;
+            return Some(pos);
+        }
+        let error = 2 * self.error;
+        if error >= self.delta.y {
+            self.error += self.delta.y;
+            self.pos.x += self.step.x;
+        }
+        if error <= self.delta.x {
+            self.error += self.delta.x;
+            self.pos.y += self.step.y;
+        }
+        Some(pos)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.done {
+            0
+        } else {
+            let remaining = (self.end - self.pos).abs();
+            remaining.x.max(remaining.y) as usize + 1
+        };
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+
+impl FusedIterator for Line {}
+
+impl<T> Grid<T> {
+    /// Returns `true` if every position strictly between `from` and `to` on the [`Line`] joining them is in bounds,
+    /// and `transparent` returns `true` for its value, or `false` otherwise.
+    ///
+    /// The values at `from` and `to` themselves are not checked, so a wall can be seen, but not seen through.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<char> = Grid::from_nested_iter([
+    ///     ".....".chars(),
+    ///     "..#..".chars(),
+    ///     ".....".chars(),
+    /// ]);
+    ///
+    /// let transparent = |&c: &char| c != '#';
+    ///
+    /// assert!(grid.line_of_sight(v(0, 0), v(4, 0), transparent));
+    /// assert!(!grid.line_of_sight(v(0, 1), v(4, 1), transparent));
+    /// assert!(grid.line_of_sight(v(0, 1), v(2, 1), transparent));
+    /// assert!(!grid.line_of_sight(v(0, 0), v(4, 2), transparent));
+    /// ```
+    pub fn line_of_sight<F>(&self, from: Vector, to: Vector, mut transparent: F) -> bool
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut line = Line::new(from, to);
+        line.next();
+        line.take_while(|&pos| pos != to)
+            .all(|pos| self.get(pos).is_some_and(&mut transparent))
+    }
+}
@@ -0,0 +1,313 @@
+//! Assignment of every position in a `Grid` to its nearest seed.
+//!
+//! There are two independent ways of doing this, and neither calls the other:
+//!
+//! - [`Grid::voronoi`] is exact, and lets the caller choose how ties are labelled.
+//!   Use it by default, and always for [`Metric::Manhattan`] and [`Metric::Chebyshev`], where it is already as fast as a flood.
+//! - [`Grid::jump_flood`] is approximate, and only faster for [`Metric::Euclidean`] with many seeds,
+//!   where `voronoi` compares every position against every seed. Use it when speed matters more than
+//!   a few positions near the boundaries between regions being assigned to a slightly further seed.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::{
+        constants::{ADJACENT, ORTHOGONAL},
+        Metric, Vector,
+    },
+};
+
+/// How [`Grid::voronoi`] labels a position that is equally near to more than one seed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Ties {
+    /// The position is assigned to the nearest seed with the lowest index.
+    #[default]
+    LowestIndex,
+    /// The position is not assigned to any seed.
+    Unassigned,
+}
+
+/// The nearest seed found so far for a position: the lowest index among the nearest seeds,
+/// and whether there is more than one nearest seed.
+type Nearest = (usize, bool);
+
+impl<T> Grid<T> {
+    /// Returns a grid of the same dimensions as `self`, in which each position holds the index in `seeds` of its nearest seed
+    /// under the given metric, so that each seed's region of the grid is its Voronoi cell.
+    ///
+    /// Positions equally near to more than one seed are labelled according to `ties`,
+    /// and every position is `None` if there are no seeds.
+    ///
+    /// For [`Metric::Manhattan`] and [`Metric::Chebyshev`] the labels are flooded out from every seed at once,
+    /// taking time proportional to the size of the grid however many seeds there are.
+    /// For [`Metric::Euclidean`] every position is compared against every seed,
+    /// taking time proportional to the size of the grid times the number of seeds,
+    /// so for thousands of seeds consider the approximate [`Grid::jump_flood`] instead.
+    ///
+    /// Panics if any seed is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, voronoi::Ties};
+    ///
+    /// let grid: Grid<()> = Grid::new(5, 1, ());
+    ///
+    /// let labels = grid.voronoi(&[v(0, 0), v(4, 0)], Metric::Manhattan, Ties::LowestIndex);
+    /// assert_eq!(labels.iter().copied().collect::<Vec<_>>(), vec![Some(0), Some(0), Some(0), Some(1), Some(1)]);
+    ///
+    /// let labels = grid.voronoi(&[v(0, 0), v(4, 0)], Metric::Manhattan, Ties::Unassigned);
+    /// assert_eq!(labels[v(2, 0)], None);
+    ///
+    /// // a grid with no positions has no labels
+    /// let empty: Grid<()> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.voronoi(&[], Metric::Euclidean, Ties::LowestIndex).dim(), v(0, 2));
+    /// ```
+    ///
+    /// ```
+    /// use grid::{prelude::*, voronoi::Ties};
+    ///
+    /// let grid: Grid<u8> = Grid::new(9, 9, 0);
+    /// let seeds = [v(1, 1), v(7, 2), v(4, 7)];
+    ///
+    /// for metric in [Metric::Manhattan, Metric::Chebyshev, Metric::Euclidean] {
+    ///     let labels = grid.voronoi(&seeds, metric, Ties::LowestIndex);
+    ///     for (pos, label) in labels.iter_positions() {
+    ///         let nearest = seeds
+    ///             .iter()
+    ///             .map(|&seed| metric.distance(pos, seed))
+    ///             .fold(f64::INFINITY, f64::min);
+    ///         assert_eq!(metric.distance(pos, seeds[label.unwrap()]), nearest);
+    ///     }
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn voronoi(&self, seeds: &[Vector], metric: Metric, ties: Ties) -> Grid<Option<usize>> {
+        self.try_voronoi(seeds, metric, ties).or_panic()
+    }
+
+    /// Returns the index of the nearest seed to each position, as in [`voronoi`](Self::voronoi),
+    /// or an error if any seed is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, voronoi::Ties};
+    ///
+    /// let grid: Grid<()> = Grid::new(3, 3, ());
+    ///
+    /// assert!(grid.try_voronoi(&[v(0, 0)], Metric::Chebyshev, Ties::LowestIndex).is_ok());
+    /// assert_eq!(
+    ///     grid.try_voronoi(&[v(0, 0), v(3, 1)], Metric::Chebyshev, Ties::LowestIndex).err(),
+    ///     Some(GridError::OutOfBounds { dim: v(3, 3), region: Rect::new(v(3, 1), v(1, 1)) }),
+    /// );
+    ///
+    /// let empty: Grid<()> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.try_voronoi(&[], Metric::Manhattan, Ties::Unassigned).unwrap().dim(), v(0, 2));
+    /// ```
+    pub fn try_voronoi(
+        &self,
+        seeds: &[Vector],
+        metric: Metric,
+        ties: Ties,
+    ) -> Result<Grid<Option<usize>>, GridError> {
+        self.check_seeds(seeds)?;
+        if self.raw.is_empty() {
+            return Ok(self.map(|_| None));
+        }
+        let nearest = match metric {
+            Metric::Manhattan => flood(self.dim, seeds, &ORTHOGONAL),
+            Metric::Chebyshev => flood(self.dim, seeds, &ADJACENT),
+            Metric::Euclidean => compare_all(self.dim, seeds),
+        };
+        Ok(nearest.map_into(|nearest| match (nearest, ties) {
+            (Some((_, true)), Ties::Unassigned) | (None, _) => None,
+            (Some((index, _)), _) => Some(index),
+        }))
+    }
+
+    /// Returns a grid of the same dimensions as `self`, in which each position holds the index in `seeds` of a seed
+    /// that is nearest, or very nearly nearest, to it under the given metric, computed with the jump flooding algorithm.
+    ///
+    /// Jump flooding takes time proportional to the size of the grid times its logarithm, however many seeds there are,
+    /// but unlike [`Grid::voronoi`] it is approximate: a small fraction of positions, usually near the boundaries
+    /// between regions, may be assigned to a seed slightly further away than the nearest.
+    /// An extra final pass is made to reduce these errors.
+    ///
+    /// Positions equally near to more than one of the seeds they are compared against are assigned to the one with the lowest index,
+    /// and every position is `None` if there are no seeds.
+    ///
+    /// Panics if any seed is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, voronoi::Ties};
+    ///
+    /// let grid: Grid<()> = Grid::new(128, 128, ());
+    /// let seeds: Vec<Vector> = (0..200).map(|i| v(i * 37 % 128, i * 91 % 127)).collect();
+    ///
+    /// let exact = grid.voronoi(&seeds, Metric::Euclidean, Ties::LowestIndex);
+    /// let fast = grid.jump_flood(&seeds, Metric::Euclidean);
+    ///
+    /// let wrong = grid
+    ///     .positions()
+    ///     .filter(|&pos| {
+    ///         let distance = |label: Option<usize>| Metric::Euclidean.distance(pos, seeds[label.unwrap()]);
+    ///         distance(fast[pos]) > distance(exact[pos])
+    ///     })
+    ///     .count();
+    ///
+    /// assert!(wrong * 1000 < grid.positions().count());
+    /// ```
+    #[track_caller]
+    pub fn jump_flood(&self, seeds: &[Vector], metric: Metric) -> Grid<Option<usize>> {
+        self.try_jump_flood(seeds, metric).or_panic()
+    }
+
+    /// Returns the index of a nearest or nearly nearest seed to each position, as in [`jump_flood`](Self::jump_flood),
+    /// or an error if any seed is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let grid: Grid<()> = Grid::new(4, 4, ());
+    ///
+    /// assert_eq!(grid.try_jump_flood(&[v(1, 1)], Metric::Euclidean).unwrap()[v(3, 3)], Some(0));
+    /// assert!(grid.try_jump_flood(&[v(-1, 1)], Metric::Euclidean).is_err());
+    ///
+    /// let empty: Grid<()> = Grid::from_nested_iter([[], []]);
+    /// assert_eq!(empty.try_jump_flood(&[], Metric::Euclidean).unwrap().dim(), v(0, 2));
+    /// ```
+    pub fn try_jump_flood(
+        &self,
+        seeds: &[Vector],
+        metric: Metric,
+    ) -> Result<Grid<Option<usize>>, GridError> {
+        self.check_seeds(seeds)?;
+        let mut labels: Grid<Option<usize>> = self.map(|_| None);
+        for (index, &seed) in seeds.iter().enumerate().rev() {
+            labels[seed] = Some(index);
+        }
+        let mut step = 1;
+        while step * 2 < self.dim.x.max(self.dim.y) {
+            step *= 2;
+        }
+        let mut steps = Vec::new();
+        while step > 0 {
+            steps.push(step);
+            step /= 2;
+        }
+        steps.push(1);
+        for step in steps {
+            labels = labels.pos_map(|pos, &current| {
+                let mut best =
+                    current.map(|index| (distance_key(metric, pos, seeds[index]), index));
+                for offset in ADJACENT {
+                    let Some(&Some(index)) = labels.get(pos + offset * step) else {
+                        continue;
+                    };
+                    let candidate = (distance_key(metric, pos, seeds[index]), index);
+                    if best.is_none_or(|best| candidate < best) {
+                        best = Some(candidate);
+                    }
+                }
+                best.map(|(_, index)| index)
+            });
+        }
+        Ok(labels)
+    }
+
+    /// Returns an error for the first seed that is out of bounds, if any.
+    fn check_seeds(&self, seeds: &[Vector]) -> Result<(), GridError> {
+        match seeds.iter().find(|&&seed| !self.in_bounds(seed)) {
+            Some(&seed) => Err(GridError::out_of_bounds(self.dim, seed)),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Returns a value that orders distances under the given metric in the same way as the distances themselves.
+#[inline]
+fn distance_key(metric: Metric, a: Vector, b: Vector) -> i64 {
+    match metric {
+        Metric::Manhattan => a.manhattan(b),
+        Metric::Chebyshev => a.chebyshev(b),
+        Metric::Euclidean => (a - b).dot(a - b),
+    }
+}
+
+/// Floods outwards from every seed at once, one layer of neighbours at a time.
+///
+/// With 4 orthogonal neighbours, the layers are exactly the manhattan distances from the nearest seed,
+/// and with 8 adjacent neighbours, the chebyshev distances.
+/// Every nearest seed of a position is also a nearest seed of one of its neighbours in the previous layer,
+/// so the nearest seeds of a position are exactly the union of those of its neighbours in the previous layer.
+fn flood(dim: Vector, seeds: &[Vector], neighbors: &[Vector]) -> Grid<Option<Nearest>> {
+    let mut nearest: Grid<Option<Nearest>> = Grid::new(dim.x, dim.y, None);
+    let mut layer: Grid<usize> = Grid::new(dim.x, dim.y, usize::MAX);
+    let mut frontier = Vec::new();
+    for (index, &seed) in seeds.iter().enumerate() {
+        match nearest[seed] {
+            Some(ref mut found) => found.1 = true,
+            None => {
+                nearest[seed] = Some((index, false));
+                layer[seed] = 0;
+                frontier.push(seed);
+            }
+        }
+    }
+    let mut next = Vec::new();
+    let mut depth = 0;
+    while !frontier.is_empty() {
+        depth += 1;
+        for &pos in &frontier {
+            let from = nearest[pos];
+            for &offset in neighbors {
+                let neighbor = pos + offset;
+                match layer.get(neighbor) {
+                    Some(&usize::MAX) => {
+                        layer[neighbor] = depth;
+                        nearest[neighbor] = from;
+                        next.push(neighbor);
+                    }
+                    Some(&d) if d == depth => nearest[neighbor] = merge(nearest[neighbor], from),
+                    _ => {}
+                }
+            }
+        }
+        std::mem::swap(&mut frontier, &mut next);
+        next.clear();
+    }
+    nearest
+}
+
+/// Compares every position against every seed by squared euclidean distance.
+fn compare_all(dim: Vector, seeds: &[Vector]) -> Grid<Option<Nearest>> {
+    Grid::from_fn(dim.x, dim.y, |pos| {
+        let mut best = i64::MAX;
+        let mut nearest = None;
+        for (index, &seed) in seeds.iter().enumerate() {
+            let diff = pos - seed;
+            let distance = diff.dot(diff);
+            if distance < best {
+                best = distance;
+                nearest = Some((index, false));
+            } else if distance == best {
+                nearest = merge(nearest, Some((index, false)));
+            }
+        }
+        nearest
+    })
+}
+
+#[inline]
+fn merge(a: Option<Nearest>, b: Option<Nearest>) -> Option<Nearest> {
+    match (a, b) {
+        (Some((i, a_many)), Some((j, b_many))) => Some((i.min(j), a_many || b_many || i != j)),
+        (a, None) => a,
+        (None, b) => b,
+    }
+}
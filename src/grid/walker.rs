@@ -0,0 +1,306 @@
+//! A cursor that walks over a `Grid`, with a position and a facing direction.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::Grid,
+    vector::Vector,
+};
+
+use std::ops::{Deref, DerefMut};
+
+/// What a [`Walker`] does when it steps off the edge of its grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum EdgePolicy {
+    /// The walker refuses to step off the edge, and stays where it is.
+    #[default]
+    Stop,
+    /// The walker reappears on the opposite edge of the grid.
+    Wrap,
+    /// The walker leaves the grid, after which it has no current value and cannot step any further.
+    Exit,
+}
+
+/// A cursor over a `Grid`, holding a position and a facing direction.
+///
+/// The grid can be held by anything that dereferences to a `Grid<T>`: a shared or mutable reference,
+/// or a smart pointer such as `Box<Grid<T>>` or `Rc<Grid<T>>` for a walker that owns its grid.
+/// The values under the walker can be modified if the grid is held mutably.
+///
+/// # Examples
+///
+/// Langton's ant on a wrapping grid:
+///
+/// ```
+/// use grid::{prelude::*, walker::{EdgePolicy, Walker}};
+///
+/// let mut grid: Grid<bool> = Grid::new(11, 11, false);
+/// let mut ant = Walker::new(&mut grid, v(5, 5), NORTH).with_policy(EdgePolicy::Wrap);
+///
+/// for _ in 0..200 {
+///     if *ant.current().unwrap() {
+///         ant.turn_left();
+///     } else {
+///         ant.turn_right();
+///     }
+///     let black = ant.current_mut().unwrap();
+///     *black = !*black;
+///     ant.step();
+/// }
+///
+/// assert!(ant.grid().in_bounds(ant.pos()));
+/// assert_eq!(grid.iter().filter(|&&black| black).count(), 40);
+///
+/// // a walker that owns its grid
+/// let mut owner = Walker::new(Box::new(Grid::new(3, 1, 0)), v(0, 0), EAST);
+/// while owner.step() {
+///     *owner.current_mut().unwrap() += 1;
+/// }
+/// assert_eq!(*owner.into_grid(), Grid::from_nested_iter([[0, 1, 1]]));
+/// ```
+#[derive(Clone)]
+pub struct Walker<G> {
+    grid: G,
+    pos: Vector,
+    dir: Vector,
+    policy: EdgePolicy,
+    exited: bool,
+}
+
+impl<G, T> Walker<G>
+where
+    G: Deref<Target = Grid<T>>,
+{
+    /// Constructs a new `Walker` at the given position of the grid, facing in the given direction,
+    /// which stops at the edges of the grid.
+    ///
+    /// The direction is the offset applied to the position by each step, and is not required to be a unit vector.
+    ///
+    /// Panics if `pos` is out of bounds.
+    #[track_caller]
+    pub fn new(grid: G, pos: Vector, dir: Vector) -> Self {
+        Self::try_new(grid, pos, dir).or_panic()
+    }
+
+    /// Constructs a new `Walker` as in [`new`](Self::new), or returns an error if `pos` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, walker::Walker};
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// assert!(Walker::try_new(&grid, v(2, 2), WEST).is_ok());
+    /// assert_eq!(
+    ///     Walker::try_new(&grid, v(3, 2), WEST).err(),
+    ///     Some(GridError::OutOfBounds { dim: v(3, 3), region: Rect::new(v(3, 2), v(1, 1)) }),
+    /// );
+    /// ```
+    pub fn try_new(grid: G, pos: Vector, dir: Vector) -> Result<Self, GridError> {
+        if !grid.in_bounds(pos) {
+            return Err(GridError::out_of_bounds(grid.dim(), pos));
+        }
+        Ok(Self {
+            grid,
+            pos,
+            dir,
+            policy: EdgePolicy::Stop,
+            exited: false,
+        })
+    }
+
+    /// Returns `self` with the given policy for stepping off the edge of the grid.
+    #[inline]
+    pub fn with_policy(mut self, policy: EdgePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns a reference to the grid.
+    #[inline]
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Returns the grid, consuming the walker.
+    #[inline]
+    pub fn into_grid(self) -> G {
+        self.grid
+    }
+
+    /// Returns the current position of the walker.
+    ///
+    /// After the walker has exited the grid, this is the out of bounds position it stepped to.
+    #[inline]
+    pub fn pos(&self) -> Vector {
+        self.pos
+    }
+
+    /// Returns the direction the walker is facing.
+    #[inline]
+    pub fn dir(&self) -> Vector {
+        self.dir
+    }
+
+    /// Sets the direction the walker is facing.
+    #[inline]
+    pub fn set_dir(&mut self, dir: Vector) {
+        self.dir = dir;
+    }
+
+    /// Returns the policy for stepping off the edge of the grid.
+    #[inline]
+    pub fn policy(&self) -> EdgePolicy {
+        self.policy
+    }
+
+    /// Returns `true` if the walker has stepped off the edge of the grid with [`EdgePolicy::Exit`], or `false` otherwise.
+    #[inline]
+    pub fn has_exited(&self) -> bool {
+        self.exited
+    }
+
+    /// Moves the walker to the given position.
+    ///
+    /// Returns `false` and leaves the walker where it is if `pos` is out of bounds, or `true` otherwise.
+    pub fn jump(&mut self, pos: Vector) -> bool {
+        if !self.grid.in_bounds(pos) {
+            return false;
+        }
+        self.pos = pos;
+        self.exited = false;
+        true
+    }
+
+    /// Turns the walker 90 degrees to the left, which is counterclockwise when `y` points down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, walker::Walker};
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    /// let mut walker = Walker::new(&grid, v(1, 1), NORTH);
+    ///
+    /// walker.turn_left();
+    /// assert_eq!(walker.dir(), WEST);
+    /// walker.turn_right();
+    /// walker.turn_right();
+    /// assert_eq!(walker.dir(), EAST);
+    /// walker.turn_around();
+    /// assert_eq!(walker.dir(), WEST);
+    /// ```
+    #[inline]
+    pub fn turn_left(&mut self) {
+        self.dir = -self.dir.perp();
+    }
+
+    /// Turns the walker 90 degrees to the right, which is clockwise when `y` points down.
+    #[inline]
+    pub fn turn_right(&mut self) {
+        self.dir = self.dir.perp();
+    }
+
+    /// Turns the walker 180 degrees.
+    #[inline]
+    pub fn turn_around(&mut self) {
+        self.dir = -self.dir;
+    }
+
+    /// Returns a reference to the value under the walker, or `None` if it has exited the grid.
+    #[inline]
+    pub fn current(&self) -> Option<&T> {
+        if self.exited {
+            return None;
+        }
+        self.grid.get(self.pos)
+    }
+
+    /// Returns the position the walker would move to if it stepped, or `None` if it cannot step.
+    pub fn next_pos(&self) -> Option<Vector> {
+        if self.exited {
+            return None;
+        }
+        let next = self.pos + self.dir;
+        if self.grid.in_bounds(next) {
+            return Some(next);
+        }
+        match self.policy {
+            EdgePolicy::Stop | EdgePolicy::Exit => None,
+            EdgePolicy::Wrap => Some(self.grid.wrap_pos(next)),
+        }
+    }
+
+    /// Returns a reference to the value the walker would move onto if it stepped, or `None` if there is no such value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, walker::{EdgePolicy, Walker}};
+    ///
+    /// let grid: Grid<i64> = Grid::from_fn(3, 3, |pos| pos.x + pos.y * 3);
+    ///
+    /// let walker = Walker::new(&grid, v(2, 1), EAST);
+    /// assert_eq!(walker.peek(), None);
+    ///
+    /// let walker = walker.with_policy(EdgePolicy::Wrap);
+    /// assert_eq!(walker.peek(), Some(&3));
+    /// ```
+    pub fn peek(&self) -> Option<&T> {
+        self.next_pos().map(|pos| &self.grid[pos])
+    }
+
+    /// Moves the walker one step in the direction it is facing, following its [`EdgePolicy`] at the edges of the grid.
+    ///
+    /// Returns `true` if the walker moved to a position in the grid, or `false` if it stayed where it was or exited the grid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::{prelude::*, walker::{EdgePolicy, Walker}};
+    ///
+    /// let grid: Grid<u8> = Grid::new(3, 3, 0);
+    ///
+    /// let mut walker = Walker::new(&grid, v(1, 1), EAST);
+    /// assert!(walker.step());
+    /// assert!(!walker.step());
+    /// assert_eq!(walker.pos(), v(2, 1));
+    ///
+    /// let mut walker = walker.with_policy(EdgePolicy::Exit);
+    /// assert!(!walker.step());
+    /// assert!(walker.has_exited());
+    /// assert_eq!(walker.pos(), v(3, 1));
+    /// assert_eq!(walker.current(), None);
+    /// ```
+    pub fn step(&mut self) -> bool {
+        if let Some(next) = self.next_pos() {
+            self.pos = next;
+            return true;
+        }
+        if !self.exited && self.policy == EdgePolicy::Exit {
+            self.pos += self.dir;
+            self.exited = true;
+        }
+        false
+    }
+}
+
+impl<G, T> Walker<G>
+where
+    G: DerefMut<Target = Grid<T>>,
+{
+    /// Returns a mutable reference to the value under the walker, or `None` if it has exited the grid.
+    #[inline]
+    pub fn current_mut(&mut self) -> Option<&mut T> {
+        if self.exited {
+            return None;
+        }
+        self.grid.get_mut(self.pos)
+    }
+
+    /// Returns a mutable reference to the grid.
+    #[inline]
+    pub fn grid_mut(&mut self) -> &mut Grid<T> {
+        &mut self.grid
+    }
+}
@@ -0,0 +1,187 @@
+//! Iterating over several grids of the same dimensions in lockstep.
+//!
+//! The items in this module are implementation details of the [`zip_positions!`](crate::zip_positions)
+//! and [`try_zip_positions!`](crate::try_zip_positions) macros.
+
+use crate::{
+    error::{GridError, OrPanic},
+    grid::{iterators::Positions, Grid},
+    vector::{constants::ZERO, Vector},
+};
+
+use std::slice::{Iter, IterMut};
+
+/// A shared or mutable reference to a `Grid` that can be iterated in lockstep with others.
+pub trait ZipGrid {
+    /// The iterator over the values of the grid.
+    type Values: Iterator;
+
+    /// Returns the dimensions of the grid.
+    fn dim(&self) -> Vector;
+
+    /// Returns an iterator over the values of the grid, in row-major order.
+    fn into_values(self) -> Self::Values;
+}
+
+impl<'a, T> ZipGrid for &'a Grid<T> {
+    type Values = Iter<'a, T>;
+
+    #[inline]
+    fn dim(&self) -> Vector {
+        self.dim
+    }
+
+    #[inline]
+    fn into_values(self) -> Self::Values {
+        self.raw.iter()
+    }
+}
+
+impl<'a, T> ZipGrid for &'a mut Grid<T> {
+    type Values = IterMut<'a, T>;
+
+    #[inline]
+    fn dim(&self) -> Vector {
+        self.dim
+    }
+
+    #[inline]
+    fn into_values(self) -> Self::Values {
+        self.raw.iter_mut()
+    }
+}
+
+/// Returns an iterator over the positions of a grid with the given dimensions, in row-major order.
+#[inline]
+pub fn positions(dim: Vector) -> Positions {
+    Positions::new(ZERO, dim)
+}
+
+/// Panics if the dimensions of a grid do not match those of the first grid.
+#[track_caller]
+#[inline]
+pub fn check_dim(expected: Vector, found: Vector) {
+    try_check_dim(expected, found).or_panic()
+}
+
+/// Returns an error if the dimensions of a grid do not match those of the first grid.
+#[inline]
+pub fn try_check_dim(expected: Vector, found: Vector) -> Result<(), GridError> {
+    if expected != found {
+        return Err(GridError::DimensionMismatch { expected, found });
+    }
+    Ok(())
+}
+
+/// Iterates over several grids of the same dimensions in lockstep, in row-major order.
+///
+/// Each argument must be a `&Grid<_>` or a `&mut Grid<_>`.
+/// For arguments `a, b, ...`, each item is a tuple `(pos, a_value, b_value, ...)`,
+/// where each value is a shared or mutable reference matching the corresponding argument.
+///
+/// The dimensions of the grids are compared once up front, and no bounds checks or index computations are needed for individual positions.
+///
+/// Panics if the grids do not all have the same dimensions.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, zip_positions};
+///
+/// let height: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.x);
+/// let water: Grid<i64> = Grid::from_fn(4, 3, |pos| pos.y);
+/// let mut level: Grid<i64> = Grid::new(4, 3, 0);
+///
+/// for (pos, h, w, l) in zip_positions!(&height, &water, &mut level) {
+///     *l = h + w + pos.x * pos.y;
+/// }
+///
+/// assert_eq!(level[v(3, 2)], 3 + 2 + 6);
+/// ```
+///
+/// ```should_panic
+/// use grid::{prelude::*, zip_positions};
+///
+/// let a: Grid<u8> = Grid::new(4, 3, 0);
+/// let b: Grid<u8> = Grid::new(3, 4, 0);
+///
+/// // panics, because the dimensions differ
+/// zip_positions!(&a, &b);
+/// ```
+#[macro_export]
+macro_rules! zip_positions {
+    (@closure $p:pat => $tup:expr) => {
+        |$p| $tup
+    };
+    (@closure $p:pat => ($($tup:tt)*), $_grid:expr $(, $tail:expr)*) => {
+        $crate::zip_positions!(@closure ($p, value) => ($($tup)*, value) $(, $tail)*)
+    };
+    ($first:expr $(, $rest:expr)* $(,)?) => {{
+        let grid = $first;
+        let dim = $crate::__private::ZipGrid::dim(&grid);
+        let iter = ::core::iter::Iterator::zip(
+            $crate::__private::positions(dim),
+            $crate::__private::ZipGrid::into_values(grid),
+        );
+        $(
+            let grid = $rest;
+            $crate::__private::check_dim(dim, $crate::__private::ZipGrid::dim(&grid));
+            let iter = ::core::iter::Iterator::zip(iter, $crate::__private::ZipGrid::into_values(grid));
+        )*
+        ::core::iter::Iterator::map(
+            iter,
+            $crate::zip_positions!(@closure (pos, value) => (pos, value) $(, $rest)*),
+        )
+    }};
+}
+
+/// Iterates over several grids of the same dimensions in lockstep, as in [`zip_positions!`](crate::zip_positions),
+/// or returns an error if the grids do not all have the same dimensions.
+///
+/// The dimensions of each grid are compared with those of the first, and the first mismatch is reported.
+///
+/// # Examples
+///
+/// ```
+/// use grid::{prelude::*, try_zip_positions};
+///
+/// let a: Grid<u8> = Grid::new(4, 3, 1);
+/// let mut b: Grid<u8> = Grid::new(4, 3, 2);
+/// let c: Grid<u8> = Grid::new(3, 4, 3);
+///
+/// for (_, a, b) in try_zip_positions!(&a, &mut b).unwrap() {
+///     *b += a;
+/// }
+/// assert_eq!(b[v(3, 2)], 3);
+///
+/// assert_eq!(
+///     try_zip_positions!(&a, &b, &c).err(),
+///     Some(GridError::DimensionMismatch { expected: v(4, 3), found: v(3, 4) }),
+/// );
+///
+/// assert_eq!(try_zip_positions!(&c).unwrap().count(), 12);
+/// ```
+#[macro_export]
+macro_rules! try_zip_positions {
+    ($first:expr $(, $rest:expr)* $(,)?) => {'zip: {
+        let grid = $first;
+        let dim = $crate::__private::ZipGrid::dim(&grid);
+        let iter = ::core::iter::Iterator::zip(
+            $crate::__private::positions(dim),
+            $crate::__private::ZipGrid::into_values(grid),
+        );
+        $(
+            let grid = $rest;
+            if let ::core::result::Result::Err(error) =
+                $crate::__private::try_check_dim(dim, $crate::__private::ZipGrid::dim(&grid))
+            {
+                break 'zip ::core::result::Result::Err(error);
+            }
+            let iter = ::core::iter::Iterator::zip(iter, $crate::__private::ZipGrid::into_values(grid));
+        )*
+        ::core::result::Result::<_, $crate::prelude::GridError>::Ok(::core::iter::Iterator::map(
+            iter,
+            $crate::zip_positions!(@closure (pos, value) => (pos, value) $(, $rest)*),
+        ))
+    }};
+}
@@ -34,13 +34,41 @@
 //!
 //! assert_eq!(pos, v(5, 2));
 //! ```
+//!
+//! # Determinism
+//!
+//! Every algorithm in this crate returns the same output for the same input on every run,
+//! so results can be relied on in replays and tests:
+//! * nothing depends on the iteration order of hashed collections, and ordered outputs such as sets of positions,
+//!   region ids and paths have a documented order, usually row-major
+//! * ties, such as between equally short paths, are broken in a fixed order unless a seed is given,
+//!   as with [`Searcher::with_tie_break`](pathfinding::Searcher::with_tie_break)
+//! * generators that take a `seed` use a small built-in pseudorandom number generator,
+//!   so a seed gives the same sequence of random choices on every platform
 
+mod error;
 mod grid;
+mod rect;
+mod rng;
+mod trace;
 mod vector;
 
-pub use crate::grid::iterators;
+pub use crate::grid::{
+    aoe, blocks, bsp, cave, column, cover, crossword, diagonal, diffusion, dual, dungeon, encoded,
+    entry, erosion, flow, fog, formats, frontier, groups, heap, influence, iterators, layout,
+    light, match3, meta, nd, nonogram, occupancy, packed, packing, pathfinding, regions, row, sand,
+    set, shared, spanning, spiral, symmetry, system, terrain, tile, transaction, turtle, view,
+    visibility, voronoi, walker,
+};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::grid::zip::*;
+}
 
 pub mod prelude {
+    pub use crate::error::GridError;
     pub use crate::grid::Grid;
-    pub use crate::vector::{constants::*, v, Vector};
+    pub use crate::rect::Rect;
+    pub use crate::vector::{constants::*, v, Axis, Metric, Vector};
 }
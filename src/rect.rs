@@ -0,0 +1,115 @@
+//! An axis-aligned rectangle of positions.
+
+use crate::{grid::iterators::Positions, vector::Vector};
+
+use std::fmt;
+
+/// An axis-aligned rectangle of positions, with its top-left corner at `pos` and with dimensions `dim`.
+///
+/// The rectangle contains every position `p` with `pos.x <= p.x < pos.x + dim.x` and `pos.y <= p.y < pos.y + dim.y`,
+/// so it is empty if either dimension is not positive.
+///
+/// # Examples
+///
+/// ```
+/// use grid::prelude::*;
+///
+/// let rect = Rect::new(v(1, 2), v(3, 2));
+///
+/// assert_eq!(rect.max(), v(4, 4));
+/// assert_eq!(rect.area(), 6);
+/// assert!(rect.contains(v(3, 3)));
+/// assert!(!rect.contains(v(4, 3)));
+///
+/// assert_eq!(rect.positions().collect::<Vec<_>>(), vec![
+///     v(1, 2), v(2, 2), v(3, 2),
+///     v(1, 3), v(2, 3), v(3, 3),
+/// ]);
+/// ```
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub struct Rect {
+    pub pos: Vector,
+    pub dim: Vector,
+}
+
+impl Rect {
+    /// Creates a new `Rect` with the given top-left corner and dimensions.
+    #[inline(always)]
+    pub const fn new(pos: Vector, dim: Vector) -> Self {
+        Self { pos, dim }
+    }
+
+    /// Creates a new `Rect` from its top-left corner `min` (inclusive) and bottom-right corner `max` (exclusive).
+    #[inline]
+    pub fn from_corners(min: Vector, max: Vector) -> Self {
+        Self::new(min, max - min)
+    }
+
+    /// Returns the top-left corner of the rectangle, which is inside the rectangle if it is not empty.
+    #[inline]
+    pub fn min(&self) -> Vector {
+        self.pos
+    }
+
+    /// Returns the bottom-right corner of the rectangle, which is just outside the rectangle.
+    #[inline]
+    pub fn max(&self) -> Vector {
+        self.pos + self.dim
+    }
+
+    /// Returns `true` if the rectangle contains no positions, or `false` otherwise.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.dim.x <= 0 || self.dim.y <= 0
+    }
+
+    /// Returns the number of positions in the rectangle.
+    pub fn area(&self) -> i64 {
+        if self.is_empty() {
+            0
+        } else {
+            self.dim.x * self.dim.y
+        }
+    }
+
+    /// Returns `true` if the rectangle contains the given position, or `false` otherwise.
+    pub fn contains(&self, pos: Vector) -> bool {
+        let max = self.max();
+        (self.pos.x..max.x).contains(&pos.x) && (self.pos.y..max.y).contains(&pos.y)
+    }
+
+    /// Returns the rectangle containing the positions in both `self` and `other`, or `None` if there are no such positions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// let a = Rect::new(v(0, 0), v(4, 3));
+    /// let b = Rect::new(v(2, 1), v(5, 5));
+    ///
+    /// assert_eq!(a.intersection(&b), Some(Rect::new(v(2, 1), v(2, 2))));
+    /// assert_eq!(a.intersection(&Rect::new(v(4, 0), v(1, 1))), None);
+    /// ```
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let rect = Self::from_corners(self.pos.max(other.pos), self.max().min(other.max()));
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Returns an iterator over the positions in the rectangle, in row-major order.
+    pub fn positions(&self) -> Positions {
+        Positions::new(self.pos, self.max())
+    }
+}
+
+impl fmt::Display for Rect {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (
+            self.pos.x.checked_add(self.dim.x),
+            self.pos.y.checked_add(self.dim.y),
+        ) {
+            (Some(x), Some(y)) => write!(f, "{} to {}", self.min(), Vector::new(x, y)),
+            _ => write!(f, "{} with dimensions {}", self.pos, self.dim),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! A small seeded pseudorandom number generator, used internally wherever randomness must be reproducible.
+
+use std::ops::RangeInclusive;
+
+/// A SplitMix64 generator.
+///
+/// Not suitable for cryptography, but fast, and identical output for identical seeds on every platform.
+#[derive(Debug, Clone)]
+pub(crate) struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Constructs a new `Rng` from the given seed.
+    pub(crate) fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next pseudorandom `u64`.
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a pseudorandom `i64` in the given inclusive range, which must not be empty.
+    pub(crate) fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let span = range.end().abs_diff(*range.start()) as u128 + 1;
+        let offset = (self.next_u64() as u128 * span) >> 64;
+        range.start().wrapping_add(offset as i64)
+    }
+
+    /// Returns a pseudorandom `bool`, `true` or `false` with equal probability.
+    pub(crate) fn coin(&mut self) -> bool {
+        self.next_u64() >> 63 == 1
+    }
+
+    /// Returns a pseudorandom `f32` in the range `[0, 1)`.
+    pub(crate) fn unit_f32(&mut self) -> f32 {
+        (self.next_u64() >> 40) as f32 / (1u64 << 24) as f32
+    }
+}
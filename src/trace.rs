@@ -0,0 +1,19 @@
+//! Instrumentation of expensive operations with `tracing` spans and events, which compiles to nothing without the `tracing` feature.
+
+/// Enters a debug-level span, named by a string literal and with optional fields, until the end of the enclosing block.
+macro_rules! span {
+    ($name:literal $(, $($fields:tt)*)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::debug_span!($name $(, $($fields)*)?).entered();
+    };
+}
+
+/// Emits a debug-level event, with the same arguments as [`tracing::debug!`].
+macro_rules! event {
+    ($($args:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($args)*);
+    };
+}
+
+pub(crate) use {event, span};
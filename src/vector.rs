@@ -52,6 +52,12 @@ impl Vector {
         diff.x.abs() + diff.y.abs()
     }
 
+    /// Computes the chebyshev distance from `self` to `rhs`, the larger of the horizontal and vertical distances.
+    pub fn chebyshev(self, rhs: Self) -> i64 {
+        let diff = self - rhs;
+        diff.x.abs().max(diff.y.abs())
+    }
+
     /// Returns a vector containing the signs of each coordinate of `self`.
     #[inline]
     pub fn signum(self) -> Self {
@@ -78,6 +84,14 @@ impl Vector {
         Self::new(self.x.clamp(min.x, max.x), self.y.clamp(min.y, max.y))
     }
 
+    /// Returns a vector containing the least nonnegative remainders of each coordinate of `self` divided by the corresponding coordinate of `rhs`.
+    ///
+    /// Panics if either coordinate of `rhs` is `0`.
+    #[inline]
+    pub fn rem_euclid(self, rhs: Self) -> Self {
+        Self::new(self.x.rem_euclid(rhs.x), self.y.rem_euclid(rhs.y))
+    }
+
     /// Computes the dot product of `self` and `rhs`.
     #[inline]
     pub fn dot(self, rhs: Self) -> i64 {
@@ -97,16 +111,67 @@ impl Vector {
     }
 
     /// Returns a vector equal to `self` with a `y` value of `0`.
+    #[doc(alias = "x_only")]
     #[inline]
     pub fn horizontal(self) -> Self {
         Self::new(self.x, 0)
     }
 
     /// Returns a vector equal to `self` with an `x` value of `0`.
+    #[doc(alias = "y_only")]
     #[inline]
     pub fn vertical(self) -> Self {
         Self::new(0, self.y)
     }
+
+    /// Returns the coordinate of `self` along the given axis.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// assert_eq!(v(3, -4).axis(Axis::X), 3);
+    /// assert_eq!(v(3, -4).axis(Axis::Y), -4);
+    /// ```
+    #[inline]
+    pub fn axis(self, axis: Axis) -> i64 {
+        match axis {
+            Axis::X => self.x,
+            Axis::Y => self.y,
+        }
+    }
+
+    /// Returns a vector equal to `self` with its coordinate along the given axis replaced by `value`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// assert_eq!(v(3, -4).with_axis(Axis::X, 0), v(0, -4));
+    ///
+    /// // the same code walks along either axis
+    /// for axis in [Axis::X, Axis::Y] {
+    ///     let end = v(5, 5).with_axis(axis, 9);
+    ///     assert_eq!(end.axis(axis), 9);
+    ///     assert_eq!(end.axis(axis.other()), 5);
+    /// }
+    /// ```
+    #[inline]
+    pub fn with_axis(self, axis: Axis, value: i64) -> Self {
+        match axis {
+            Axis::X => Self::new(value, self.y),
+            Axis::Y => Self::new(self.x, value),
+        }
+    }
+
+    /// Returns a vector equal to `self` with its coordinate along the other axis set to `0`,
+    /// which is [`horizontal`](Self::horizontal) for [`Axis::X`] and [`vertical`](Self::vertical) for [`Axis::Y`].
+    #[inline]
+    pub fn only(self, axis: Axis) -> Self {
+        Self::new(0, 0).with_axis(axis, self.axis(axis))
+    }
 }
 
 impl fmt::Display for Vector {
@@ -115,6 +180,65 @@ impl fmt::Display for Vector {
     }
 }
 
+/// One of the two axes of the grid, naming a direction of a parameter instead of a `bool`.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Debug)]
+pub enum Axis {
+    /// The horizontal axis, along which the `x` coordinate and the column change.
+    X,
+    /// The vertical axis, along which the `y` coordinate and the row change.
+    Y,
+}
+
+impl Axis {
+    /// Returns the other axis.
+    #[inline]
+    pub fn other(self) -> Self {
+        match self {
+            Self::X => Self::Y,
+            Self::Y => Self::X,
+        }
+    }
+
+    /// Returns the unit vector pointing in the positive direction along the axis.
+    #[inline]
+    pub fn unit(self) -> Vector {
+        Vector::new(0, 0).with_axis(self, 1)
+    }
+}
+
+/// A way of measuring the distance between two positions.
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug, Default)]
+pub enum Metric {
+    /// The sum of the horizontal and vertical distances, matching movement in the 4 orthogonal directions.
+    #[default]
+    Manhattan,
+    /// The larger of the horizontal and vertical distances, matching movement in all 8 adjacent directions.
+    Chebyshev,
+    /// The straight line distance.
+    Euclidean,
+}
+
+impl Metric {
+    /// Computes the distance from `a` to `b` under this metric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use grid::prelude::*;
+    ///
+    /// assert_eq!(Metric::Manhattan.distance(v(0, 0), v(3, -4)), 7.0);
+    /// assert_eq!(Metric::Chebyshev.distance(v(0, 0), v(3, -4)), 4.0);
+    /// assert_eq!(Metric::Euclidean.distance(v(0, 0), v(3, -4)), 5.0);
+    /// ```
+    pub fn distance(self, a: Vector, b: Vector) -> f64 {
+        match self {
+            Self::Manhattan => a.manhattan(b) as f64,
+            Self::Chebyshev => a.chebyshev(b) as f64,
+            Self::Euclidean => ((a - b).dot(a - b) as f64).sqrt(),
+        }
+    }
+}
+
 /// A convenience function for creating a `Vector` with `Vector::new()`.
 ///
 /// # Examples